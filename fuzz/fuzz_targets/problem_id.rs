@@ -0,0 +1,33 @@
+//! Fuzzes the parsers of problem and submission ids, which must never
+//! panic, and must give back every valid id they print.
+
+#![no_main]
+
+use jutge::problem_id_types::{Localized, Unlocalized};
+use jutge::{ProblemId, ProblemIdRef, RawProblemId, SubmissionId};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|s: &str| {
+    if let Ok(id) = s.parse::<ProblemId<Localized>>() {
+        assert_eq!(
+            id.to_string().parse::<ProblemId<Localized>>().ok(),
+            Some(id)
+        );
+    }
+    if let Ok(id) = s.parse::<ProblemId<Unlocalized>>() {
+        assert_eq!(
+            id.to_string().parse::<ProblemId<Unlocalized>>().ok(),
+            Some(id)
+        );
+    }
+    let _ = ProblemId::<Localized>::parse_lenient(s);
+    let _ = ProblemId::<Unlocalized>::parse_lenient(s);
+    for id in ProblemIdRef::<Localized>::scan(s) {
+        assert_eq!(id.to_problem_id(), id);
+    }
+    for id in ProblemIdRef::<Unlocalized>::scan(s) {
+        assert_eq!(id.to_problem_id(), id);
+    }
+    let _ = s.parse::<RawProblemId>();
+    let _ = s.parse::<SubmissionId>();
+});
@@ -0,0 +1,30 @@
+//! Fuzzes the statement scraper with arbitrary pages, which must never
+//! panic, whatever their layout.
+
+#![no_main]
+
+use jutge::problem_id_types::Localized;
+use jutge::sans_io::{FetchProblemInfo, FetchStatement, Operation, Response};
+use jutge::{MathMode, ProblemId};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|body: &[u8]| {
+    let problem: ProblemId<Localized> = "P068688_en"
+        .parse()
+        .expect("the fuzzed problem id should be valid");
+    let response = Response {
+        url: "https://jutge.org/problems/P68688_en",
+        body,
+    };
+
+    let operation = FetchStatement {
+        problem: problem.clone(),
+    };
+    if let Ok(statement) = operation.parse(&response) {
+        let _ = statement.text();
+        let _ = statement.nodes(MathMode::Tex);
+        let _ = statement.nodes(MathMode::Text);
+        let _ = statement.sanitized_html();
+    }
+    let _ = FetchProblemInfo { problem }.parse(&response);
+});
@@ -0,0 +1,13 @@
+//! Fuzzes the parsers of verdicts and timestamps, which must never panic.
+
+#![no_main]
+
+use jutge::{Timestamp, Verdict};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|s: &str| {
+    if let Ok(verdict) = s.parse::<Verdict>() {
+        let _ = verdict.code().parse::<Verdict>();
+    }
+    let _ = s.parse::<Timestamp>();
+});
@@ -0,0 +1,117 @@
+use scraper::Html;
+
+use crate::sans_io::{FetchAnnouncements, FetchMessages, Operation, Request, Response};
+use crate::scrape::{selector, text, text_of};
+#[cfg(feature = "client-auth")]
+use crate::Session;
+use crate::{Result, Timestamp};
+
+/// An announcement shown by <https://jutge.org> to the logged-in user, such
+/// as an upcoming exam or an update to a problem list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Announcement {
+    /// The heading of the announcement, if it has one.
+    pub title: Option<String>,
+
+    /// The text of the announcement.
+    pub body: String,
+}
+
+/// A message received by the logged-in user in their <https://jutge.org>
+/// inbox.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Message {
+    /// The time the message was sent, or `None` if the site shows it in an
+    /// unknown format.
+    pub date: Option<Timestamp>,
+
+    /// The sender of the message.
+    pub sender: String,
+
+    /// The subject of the message.
+    pub subject: String,
+}
+
+#[cfg(feature = "client-auth")]
+impl Session {
+    /// Fetches the announcements shown to the logged-in user in their
+    /// dashboard.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`](crate::Error::NotAuthenticated) if
+    /// the session has expired.
+    pub fn announcements(&self) -> Result<Vec<Announcement>> {
+        self.client().execute(&FetchAnnouncements)
+    }
+
+    /// Fetches the messages in the inbox of the logged-in user.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`](crate::Error::NotAuthenticated) if
+    /// the session has expired.
+    pub fn messages(&self) -> Result<Vec<Message>> {
+        self.client().execute(&FetchMessages)
+    }
+}
+
+impl Operation for FetchAnnouncements {
+    type Output = Vec<Announcement>;
+
+    fn request(&self) -> Request {
+        Request::page("/dashboard")
+    }
+
+    fn parse(&self, response: &Response<'_>) -> Result<Vec<Announcement>> {
+        Ok(parse_announcements(&response.html()?))
+    }
+}
+
+impl Operation for FetchMessages {
+    type Output = Vec<Message>;
+
+    fn request(&self) -> Request {
+        Request::page("/messages")
+    }
+
+    fn parse(&self, response: &Response<'_>) -> Result<Vec<Message>> {
+        Ok(parse_messages(&response.html()?))
+    }
+}
+
+fn parse_announcements(html: &Html) -> Vec<Announcement> {
+    html.select(&selector("div.alert"))
+        .filter_map(|alert| {
+            let title = text_of(alert, "h4, strong");
+            let body = text(alert);
+            let body = match &title {
+                Some(title) => body
+                    .strip_prefix(title.as_str())
+                    .unwrap_or(&body)
+                    .trim()
+                    .into(),
+                None => body,
+            };
+
+            (!body.is_empty() || title.is_some()).then_some(Announcement { title, body })
+        })
+        .collect()
+}
+
+fn parse_messages(html: &Html) -> Vec<Message> {
+    html.select(&selector("table tbody tr"))
+        .filter_map(|row| {
+            let cells: Vec<_> = row.select(&selector("td")).map(text).collect();
+
+            match cells.as_slice() {
+                [date, sender, subject, ..] => Some(Message {
+                    date: Timestamp::find_in(date),
+                    sender: sender.clone(),
+                    subject: subject.clone(),
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,238 @@
+//! Results of operations over several items.
+
+#[cfg(feature = "web-client")]
+use std::ops::ControlFlow;
+#[cfg(feature = "web-client")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "web-client")]
+use std::sync::{mpsc, Mutex, PoisonError};
+#[cfg(feature = "web-client")]
+use std::{panic, thread};
+
+use crate::{Error, Result};
+
+/// The outcome of a batch operation, such as
+/// [`Session::submit_batch()`](crate::Session::submit_batch): the result of
+/// each item of the batch, in the same order.
+///
+/// A batch doesn't stop at the first failure, so a `BatchOutcome` usually
+/// holds both successes and errors. Each of them comes with the index of its
+/// item in the batch.
+#[derive(Debug)]
+pub struct BatchOutcome<T> {
+    results: Vec<Result<T>>,
+}
+
+impl<T> BatchOutcome<T> {
+    /// Gets the number of items of the batch.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Checks whether the batch had no items.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Checks whether every item of the batch succeeded.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.results.iter().all(Result::is_ok)
+    }
+
+    /// Gets the result of an item of the batch.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&Result<T>> {
+        self.results.get(index)
+    }
+
+    /// Iterates over the items that succeeded, with their index.
+    pub fn successes(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.results
+            .iter()
+            .enumerate()
+            .filter_map(|(i, result)| Some((i, result.as_ref().ok()?)))
+    }
+
+    /// Iterates over the items that failed, with their index.
+    pub fn failures(&self) -> impl Iterator<Item = (usize, &Error)> {
+        self.results
+            .iter()
+            .enumerate()
+            .filter_map(|(i, result)| Some((i, result.as_ref().err()?)))
+    }
+
+    /// Splits the outcome into the items that succeeded and the ones that
+    /// failed, with their index.
+    #[must_use]
+    #[allow(clippy::type_complexity)]
+    pub fn into_parts(self) -> (Vec<(usize, T)>, Vec<(usize, Error)>) {
+        let mut successes = Vec::new();
+        let mut failures = Vec::new();
+
+        for (i, result) in self.results.into_iter().enumerate() {
+            match result {
+                Ok(value) => successes.push((i, value)),
+                Err(err) => failures.push((i, err)),
+            }
+        }
+
+        (successes, failures)
+    }
+
+    /// Gets the result of each item of the batch, in order.
+    #[must_use]
+    pub fn into_results(self) -> Vec<Result<T>> {
+        self.results
+    }
+}
+
+impl<T> FromIterator<Result<T>> for BatchOutcome<T> {
+    fn from_iter<I: IntoIterator<Item = Result<T>>>(iter: I) -> Self {
+        Self {
+            results: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> IntoIterator for BatchOutcome<T> {
+    type Item = Result<T>;
+    type IntoIter = std::vec::IntoIter<Result<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.results.into_iter()
+    }
+}
+
+/// Runs an operation over every item of a batch, with up to `concurrency`
+/// items being processed at the same time. A `concurrency` of 0 or 1
+/// processes them sequentially.
+#[cfg(feature = "web-client")]
+pub(crate) fn run_batch<I: Sync, T: Send>(
+    items: &[I],
+    concurrency: usize,
+    operation: impl Fn(&I) -> Result<T> + Sync,
+) -> BatchOutcome<T> {
+    let workers = concurrency.clamp(1, items.len().max(1));
+    if workers == 1 {
+        return items.iter().map(operation).collect();
+    }
+
+    let operation = &operation;
+    let mut results: Vec<_> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|worker| {
+                scope.spawn(move || {
+                    items
+                        .iter()
+                        .enumerate()
+                        .skip(worker)
+                        .step_by(workers)
+                        .map(|(i, item)| (i, operation(item)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| panic::resume_unwind(panic))
+            })
+            .collect()
+    });
+
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Runs an operation over a stream of items, with up to `concurrency` items
+/// being processed at the same time, and hands each item and its result to
+/// `consume` as soon as it's ready. A `concurrency` of 0 or 1 processes them
+/// sequentially.
+///
+/// Unlike [`run_batch()`], results are consumed in the order they're ready,
+/// and the items are pulled from `items` only as workers become free, so at
+/// most about twice `concurrency` items are held at the same time, however
+/// long the stream is.
+///
+/// The pipeline stops when `consume` breaks, or at the first error of
+/// `items`, which is returned.
+#[cfg(feature = "web-client")]
+pub(crate) fn run_pipeline<I: Send, T: Send>(
+    items: impl Iterator<Item = Result<I>> + Send,
+    concurrency: usize,
+    operation: impl Fn(&I) -> Result<T> + Sync,
+    mut consume: impl FnMut(I, Result<T>) -> ControlFlow<()>,
+) -> Result<()> {
+    let workers = concurrency.max(1);
+    if workers == 1 {
+        for item in items {
+            let item = item?;
+            let result = operation(&item);
+            if consume(item, result).is_break() {
+                break;
+            }
+        }
+        return Ok(());
+    }
+
+    let items = Mutex::new(items.fuse());
+    let stop = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::sync_channel(workers);
+    let (items, stop, operation) = (&items, &stop, &operation);
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                let sender = sender.clone();
+                scope.spawn(move || {
+                    while !stop.load(Ordering::Relaxed) {
+                        let next = items.lock().unwrap_or_else(PoisonError::into_inner).next();
+                        let message = match next {
+                            Some(Ok(item)) => {
+                                let result = operation(&item);
+                                Ok((item, result))
+                            }
+                            Some(Err(err)) => Err(err),
+                            None => break,
+                        };
+                        // The consumer is gone once the pipeline stops.
+                        if sender.send(message).is_err() {
+                            break;
+                        }
+                    }
+                })
+            })
+            .collect();
+        drop(sender);
+
+        let mut outcome = Ok(());
+        for message in &receiver {
+            let stopped = match message {
+                Ok((item, result)) => consume(item, result).is_break(),
+                Err(err) => {
+                    outcome = Err(err);
+                    true
+                }
+            };
+            if stopped {
+                stop.store(true, Ordering::Relaxed);
+                break;
+            }
+        }
+        // Unblocks the workers waiting to send a result.
+        drop(receiver);
+
+        for handle in handles {
+            handle
+                .join()
+                .unwrap_or_else(|panic| panic::resume_unwind(panic));
+        }
+        outcome
+    })
+}
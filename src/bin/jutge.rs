@@ -0,0 +1,306 @@
+//! A command line client for <https://jutge.org>, built on the `jutge`
+//! crate.
+
+#![deny(unsafe_code)]
+#![warn(clippy::all)]
+#![warn(clippy::pedantic)]
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use jutge::problem_id_types::{Localized, Unlocalized};
+use jutge::{
+    Client, Manifest, ProblemId, ProblemLanguage, Runner, Session, SubmissionId, TestCase,
+    TestOutcome,
+};
+
+const USAGE: &str = "\
+usage: jutge <command> [arguments]
+
+commands:
+  login <email>                     log in with the password in JUTGE_PASSWORD, or piped
+                                    to stdin (e.g. from a password manager)
+  get <problem>                     download the samples of a problem to ./<problem>,
+                                    unless they're up to date
+  test <problem> <program> [args]   run a program against the downloaded samples
+  submit <problem> <file> [compiler]
+                                    submit a solution and wait for its verdict
+  status <submission>               follow the status of a submission (e.g. P068688_en/S001)
+  version                           print the version and the features of this build
+
+Problems given without a language (e.g. P68688) are localized in the language
+set in JUTGE_LANGUAGE (e.g. ca), or in English.
+
+The session is stored in JUTGE_SESSION, or in the jutge-rs directory of the
+user configuration directory, next to the cache of downloaded problems. Set
+JUTGE_OFFLINE to only use the cache.
+
+The password is never read from the terminal, as it would be echoed. Set it
+with `read -rs JUTGE_PASSWORD && export JUTGE_PASSWORD` or similar instead.";
+
+type CliResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        ["login", email] => login(email),
+        ["get", problem] => get(problem),
+        ["test", problem, program, program_args @ ..] => test(problem, program, program_args),
+        ["submit", problem, file] => submit(problem, file, None),
+        ["submit", problem, file, compiler] => submit(problem, file, Some(compiler)),
+        ["status", submission] => status(submission),
+        ["version"] => {
+            println!(
+                "jutge {} ({})",
+                env!("CARGO_PKG_VERSION"),
+                jutge::features()
+            );
+            Ok(true)
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn login(email: &str) -> CliResult<bool> {
+    let password = if let Ok(password) = env::var("JUTGE_PASSWORD") {
+        password
+    } else if io::stdin().is_terminal() {
+        // Reading it here would echo it.
+        return Err("set the password in JUTGE_PASSWORD, or pipe it to stdin".into());
+    } else {
+        let mut password = String::new();
+        io::stdin().lock().read_line(&mut password)?;
+        password.trim_end_matches(['\r', '\n']).into()
+    };
+
+    let client = Client::new();
+    let session = client.login(email, &password)?;
+
+    let path = session_path()?;
+    if let Some(dir) = path.parent() {
+        create_private_dir(dir)?;
+    }
+    // The directory may have been created before, e.g. for the cache.
+    #[cfg(unix)]
+    if env::var_os("JUTGE_SESSION").is_none() {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(config_dir()?, fs::Permissions::from_mode(0o700))?;
+    }
+    session.save(&mut create_private_file(&path)?)?;
+
+    eprintln!("logged in, session saved to {}", path.display());
+    Ok(true)
+}
+
+fn get(problem: &str) -> CliResult<bool> {
+    let problem = parse_problem(problem)?;
+    let client = client()?;
+    let dir = PathBuf::from(problem.to_string());
+
+    let mut manifest = Manifest::load(Manifest::FILE_NAME)?;
+    let fingerprint = client.problem_fingerprint(&problem)?;
+    if dir.is_dir() && manifest.is_current(&problem, fingerprint) {
+        eprintln!("{} is up to date", dir.display());
+        return Ok(true);
+    }
+
+    let cases = client.samples(&problem)?;
+    fs::create_dir_all(&dir)?;
+    for case in &cases {
+        fs::write(dir.join(format!("{}.inp", case.name)), &case.input)?;
+        fs::write(dir.join(format!("{}.cor", case.name)), &case.expected)?;
+    }
+
+    manifest.insert(problem, fingerprint);
+    manifest.save(Manifest::FILE_NAME)?;
+
+    eprintln!("downloaded {} samples to {}", cases.len(), dir.display());
+    Ok(true)
+}
+
+fn test(problem: &str, program: &str, args: &[&str]) -> CliResult<bool> {
+    let dir = PathBuf::from(parse_problem(problem)?.to_string());
+    let cases = read_cases(&dir)?;
+    if cases.is_empty() {
+        return Err(format!("no samples in {}, run `jutge get` first", dir.display()).into());
+    }
+
+    let runner = args
+        .iter()
+        .fold(Runner::new(program), |runner, arg| runner.arg(arg));
+
+    let mut passed = 0;
+    for case in &cases {
+        let outcome = runner.run(case)?;
+        println!("{}: {}", case.name, outcome.verdict());
+        match &outcome {
+            TestOutcome::Passed => passed += 1,
+            TestOutcome::PresentationError(diff) | TestOutcome::WrongAnswer(diff) => {
+                print!("{diff}");
+            }
+            TestOutcome::RuntimeError { code, stderr } => {
+                println!("exit code: {code:?}");
+                print!("{stderr}");
+            }
+            TestOutcome::TimeLimitExceeded => println!("time limit exceeded"),
+        }
+    }
+
+    println!("{passed}/{} samples passed", cases.len());
+    Ok(passed == cases.len())
+}
+
+fn submit(problem: &str, file: &str, compiler: Option<&str>) -> CliResult<bool> {
+    let problem = parse_problem(problem)?;
+    let client = client()?;
+    let session = client.resume()?;
+
+    let request = session.submit_to(&problem).source_file(file);
+    let request = match compiler {
+        Some(compiler) => request.compiler(compiler.parse()?),
+        None => request,
+    };
+    let id = request.send()?;
+    println!("submitted {id}");
+
+    follow(&session, &id)
+}
+
+fn status(submission: &str) -> CliResult<bool> {
+    let client = client()?;
+    follow(&client.resume()?, &submission.parse()?)
+}
+
+fn follow(session: &Session, id: &SubmissionId) -> CliResult<bool> {
+    let mut accepted = false;
+    for status in session.verdict_updates(id) {
+        let status = status?;
+        println!("{status:?}");
+        accepted = status.verdict().is_some_and(jutge::Verdict::is_accepted);
+    }
+
+    Ok(accepted)
+}
+
+/// Creates a `Client` with the saved session, if there's one.
+fn client() -> CliResult<Client> {
+    let builder = Client::builder()
+        .user_agent(concat!("jutge-cli/", env!("CARGO_PKG_VERSION")))
+        .cache_dir(config_dir()?.join("cache"))
+        .offline(env::var_os("JUTGE_OFFLINE").is_some())
+        .language(language()?);
+
+    let builder = match File::open(session_path()?) {
+        Ok(file) => builder.session(BufReader::new(file))?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => builder,
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(builder.build())
+}
+
+fn session_path() -> CliResult<PathBuf> {
+    if let Some(path) = env::var_os("JUTGE_SESSION") {
+        return Ok(path.into());
+    }
+
+    Ok(config_dir()?.join("session.json"))
+}
+
+/// Creates a directory, and any missing parents, that only the user can
+/// read.
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(dir)
+}
+
+/// Creates or truncates a file that only the user can read, such as the
+/// saved session.
+fn create_private_file(path: &Path) -> io::Result<File> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let file = options.open(path)?;
+
+    // The mode only applies to new files.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+
+    Ok(file)
+}
+
+fn config_dir() -> CliResult<PathBuf> {
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .ok_or("can't find the configuration directory, please set JUTGE_SESSION")?;
+
+    Ok(config.join("jutge-rs"))
+}
+
+/// Gets the language set in `JUTGE_LANGUAGE`, defaulting to English.
+fn language() -> CliResult<ProblemLanguage> {
+    match env::var("JUTGE_LANGUAGE") {
+        Ok(language) => Ok(language.trim().as_bytes().try_into()?),
+        Err(_) => Ok(ProblemLanguage::English),
+    }
+}
+
+/// Parses a problem id given by the user, localizing it in the language set
+/// in `JUTGE_LANGUAGE` if it isn't localized.
+fn parse_problem(problem: &str) -> CliResult<ProblemId<Localized>> {
+    if let Ok(problem) = ProblemId::<Localized>::parse_lenient(problem) {
+        return Ok(problem);
+    }
+
+    Ok(ProblemId::<Unlocalized>::parse_lenient(problem)?.localize(language()?)?)
+}
+
+/// Reads the test cases downloaded by `jutge get`.
+fn read_cases(dir: &Path) -> CliResult<Vec<TestCase>> {
+    let mut cases = Vec::new();
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "inp") {
+            let name = path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned();
+            cases.push(TestCase {
+                input: fs::read_to_string(&path)?,
+                expected: fs::read_to_string(path.with_extension("cor"))?,
+                name,
+            });
+        }
+    }
+
+    cases.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(cases)
+}
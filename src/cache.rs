@@ -0,0 +1,135 @@
+//! Caching of the pages fetched by a [`Client`](crate::Client).
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+
+/// A bounded map that evicts the least recently used entry when it's full,
+/// and can be shared between threads.
+#[derive(Debug)]
+pub(crate) struct LruCache<K, V> {
+    capacity: usize,
+    inner: Mutex<Inner<K, V>>,
+}
+
+#[derive(Debug)]
+struct Inner<K, V> {
+    entries: HashMap<K, (V, u64)>,
+    clock: u64,
+}
+
+impl<K: Eq + Hash, V: Clone> LruCache<K, V> {
+    /// Creates a cache that holds at most `capacity` entries. A cache with no
+    /// capacity never stores anything.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::new(Inner {
+                entries: HashMap::new(),
+                clock: 0,
+            }),
+        }
+    }
+
+    /// Gets the value stored for a key, marking it as recently used.
+    pub(crate) fn get(&self, key: &K) -> Option<V> {
+        let mut inner = self.lock();
+        inner.clock += 1;
+        let clock = inner.clock;
+
+        let (value, last_use) = inner.entries.get_mut(key)?;
+        *last_use = clock;
+        Some(value.clone())
+    }
+
+    /// Stores a value, evicting the least recently used entry if the cache
+    /// is full.
+    pub(crate) fn insert(&self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut inner = self.lock();
+        inner.clock += 1;
+        let clock = inner.clock;
+
+        if inner.entries.len() >= self.capacity && !inner.entries.contains_key(&key) {
+            // Every use gets a different tick, so this only removes one entry.
+            let oldest = inner.entries.values().map(|(_, last_use)| *last_use).min();
+            inner
+                .entries
+                .retain(|_, (_, last_use)| Some(*last_use) != oldest);
+        }
+
+        inner.entries.insert(key, (value, clock));
+    }
+
+    /// Removes all the entries of the cache.
+    pub(crate) fn clear(&self) {
+        self.lock().entries.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner<K, V>> {
+        self.inner
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(feature = "cache")]
+mod disk {
+    use std::fmt::Write as _;
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// A directory that stores the contents of cached paths of the site, a file
+    /// per path.
+    #[derive(Debug, Clone)]
+    pub(crate) struct DiskCache {
+        dir: PathBuf,
+    }
+
+    impl DiskCache {
+        pub(crate) fn new(dir: PathBuf) -> Self {
+            Self { dir }
+        }
+
+        /// Reads the contents stored for a path, if any.
+        pub(crate) fn read(&self, path: &str) -> Option<Vec<u8>> {
+            fs::read(self.file(path)).ok()
+        }
+
+        /// Checks whether there are contents stored for a path.
+        pub(crate) fn contains(&self, path: &str) -> bool {
+            self.file(path).is_file()
+        }
+
+        /// Stores the contents of a path.
+        ///
+        /// The disk cache is only an optimization, so failing to write to it
+        /// isn't an error.
+        pub(crate) fn write(&self, path: &str, contents: &[u8]) {
+            if fs::create_dir_all(&self.dir).is_ok() {
+                let _ = fs::write(self.file(path), contents);
+            }
+        }
+
+        /// Gets the file that stores a path, escaping the characters that can't
+        /// be used in file names.
+        fn file(&self, path: &str) -> PathBuf {
+            let mut name = String::new();
+            for byte in path.trim_start_matches('/').bytes() {
+                if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.') {
+                    name.push(char::from(byte));
+                } else {
+                    let _ = write!(name, "%{byte:02X}");
+                }
+            }
+
+            self.dir.join(name)
+        }
+    }
+}
+
+#[cfg(feature = "cache")]
+pub(crate) use disk::DiskCache;
@@ -0,0 +1,294 @@
+//! Calendars of the deadlines of the lists and the exams of
+//! <https://jutge.org> courses, to subscribe to them from calendar apps.
+
+use crate::{ExamWindow, Timestamp};
+
+/// Something with a date in a <https://jutge.org> course.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Deadline {
+    /// The deadline of a list of problems of a course.
+    List {
+        /// The course of the list.
+        course_id: String,
+
+        /// The list.
+        list_id: String,
+
+        /// The time the list is due.
+        due: Timestamp,
+    },
+
+    /// An exam.
+    Exam {
+        /// The exam.
+        exam_id: String,
+
+        /// The time window in which the exam can be taken.
+        window: ExamWindow,
+    },
+}
+
+impl Deadline {
+    /// Gets the time the deadline starts: the due time of a list, or the
+    /// start of an exam.
+    #[must_use]
+    pub const fn start(&self) -> Timestamp {
+        match self {
+            Self::List { due, .. } => *due,
+            Self::Exam { window, .. } => window.start,
+        }
+    }
+
+    /// Gets the title of the deadline, as shown in calendars.
+    #[must_use]
+    pub fn title(&self) -> String {
+        match self {
+            Self::List {
+                course_id, list_id, ..
+            } => format!("{course_id}: {list_id} due"),
+            Self::Exam { exam_id, .. } => format!("Exam {exam_id}"),
+        }
+    }
+
+    /// Gets the path of the page of the deadline in the site.
+    #[must_use]
+    pub fn path(&self) -> String {
+        match self {
+            Self::List {
+                course_id, list_id, ..
+            } => format!("/courses/{course_id}/{list_id}"),
+            Self::Exam { exam_id, .. } => format!("/exams/{exam_id}"),
+        }
+    }
+}
+
+/// A set of [`Deadline`]s, which can be exported as an iCalendar feed with
+/// [`Deadlines::to_ics()`].
+///
+/// The deadlines are usually fetched with
+/// [`Session::deadlines()`](crate::Session::deadlines), but they can be
+/// added one by one too, e.g. from the results of
+/// [`Session::list_deadline()`](crate::Session::list_deadline) and
+/// [`Session::exam_window()`](crate::Session::exam_window).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Deadlines {
+    deadlines: Vec<Deadline>,
+}
+
+impl Deadlines {
+    /// Creates an empty set of `Deadlines`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a deadline.
+    pub fn push(&mut self, deadline: Deadline) {
+        self.deadlines.push(deadline);
+    }
+
+    /// Adds the deadline of a list of problems.
+    #[must_use]
+    pub fn list(mut self, course_id: &str, list_id: &str, due: Timestamp) -> Self {
+        self.push(Deadline::List {
+            course_id: course_id.into(),
+            list_id: list_id.into(),
+            due,
+        });
+        self
+    }
+
+    /// Adds an exam.
+    #[must_use]
+    pub fn exam(mut self, exam_id: &str, window: ExamWindow) -> Self {
+        self.push(Deadline::Exam {
+            exam_id: exam_id.into(),
+            window,
+        });
+        self
+    }
+
+    /// Iterates over the deadlines, sorted by the time they start.
+    pub fn iter(&self) -> impl Iterator<Item = &Deadline> {
+        let mut deadlines: Vec<_> = self.deadlines.iter().collect();
+        deadlines.sort_by_key(|deadline| deadline.start());
+        deadlines.into_iter()
+    }
+
+    /// Gets the deadlines that start after a time, such as
+    /// [`Timestamp::now()`], sorted by the time they start.
+    pub fn upcoming(&self, after: Timestamp) -> impl Iterator<Item = &Deadline> {
+        self.iter().filter(move |deadline| deadline.start() > after)
+    }
+
+    /// Formats the deadlines as an iCalendar (RFC 5545) feed, with an event
+    /// per deadline.
+    ///
+    /// The deadlines of lists are events without duration at their due
+    /// time, and exams last for their whole window. Each event has a stable
+    /// `UID`, so calendar apps update it (instead of duplicating it) when the
+    /// feed is fetched again, and links to its page in `base_url`, the site
+    /// the deadlines come from (such as `https://jutge.org`).
+    #[must_use]
+    pub fn to_ics(&self, base_url: &str) -> String {
+        let base_url = base_url.trim_end_matches('/');
+        let stamp = ics_time(Timestamp::now());
+
+        let mut ics = String::new();
+        let mut line = |line: &str| fold_line(&mut ics, line);
+        line("BEGIN:VCALENDAR");
+        line("VERSION:2.0");
+        line("PRODID:-//jutge-rs//Deadlines//EN");
+        line("CALSCALE:GREGORIAN");
+        line("X-WR-CALNAME:Jutge.org deadlines");
+
+        for deadline in self.iter() {
+            let (uid, end) = match deadline {
+                Deadline::List {
+                    course_id,
+                    list_id,
+                    due,
+                } => (format!("list-{course_id}-{list_id}"), *due),
+                Deadline::Exam { exam_id, window } => (format!("exam-{exam_id}"), window.end),
+            };
+
+            line("BEGIN:VEVENT");
+            line(&format!("UID:{}@jutge.org", escape_text(&uid)));
+            line(&format!("DTSTAMP:{stamp}"));
+            line(&format!("DTSTART:{}", ics_time(deadline.start())));
+            line(&format!("DTEND:{}", ics_time(end)));
+            line(&format!("SUMMARY:{}", escape_text(&deadline.title())));
+            line(&format!("URL:{base_url}{}", deadline.path()));
+            line("END:VEVENT");
+        }
+
+        line("END:VCALENDAR");
+        ics
+    }
+}
+
+/// Formats a time in UTC, as expected by iCalendar.
+fn ics_time(time: Timestamp) -> String {
+    let (year, month, day, hour, minute, second) = time.to_utc();
+    format!("{year:04}{month:02}{day:02}T{hour:02}{minute:02}{second:02}Z")
+}
+
+/// Escapes the characters with a meaning in the text values of iCalendar.
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Writes a content line, folding it so no line is longer than 75 bytes.
+fn fold_line(ics: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            ics.push_str("\r\n ");
+            // The space of the continuation counts.
+            width = 1;
+        }
+        ics.push(c);
+        width += c.len_utf8();
+    }
+    ics.push_str("\r\n");
+}
+
+#[cfg(feature = "courses")]
+mod fetch {
+    use super::Deadlines;
+    use crate::{Result, Session};
+
+    impl Session {
+        /// Fetches the deadlines of some lists of problems (given as pairs of
+        /// a course and a list) and the windows of some exams.
+        ///
+        /// Lists without a deadline are left out.
+        ///
+        /// # Errors
+        /// Fails like [`Session::list_deadline()`] and
+        /// [`Session::exam_window()`], on the first list or exam that can't
+        /// be fetched.
+        pub fn deadlines(&self, lists: &[(&str, &str)], exams: &[&str]) -> Result<Deadlines> {
+            let mut deadlines = Deadlines::new();
+            for (course_id, list_id) in lists {
+                if let Some(due) = self.list_deadline(course_id, list_id)? {
+                    deadlines = deadlines.list(course_id, list_id, due);
+                }
+            }
+            for exam_id in exams {
+                deadlines = deadlines.exam(exam_id, self.exam_window(exam_id)?);
+            }
+
+            Ok(deadlines)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape_text, fold_line, Deadlines};
+    use crate::Timestamp;
+
+    fn folded(line: &str) -> String {
+        let mut ics = String::new();
+        fold_line(&mut ics, line);
+        ics
+    }
+
+    #[test]
+    fn short_lines() {
+        assert_eq!(folded("BEGIN:VEVENT"), "BEGIN:VEVENT\r\n");
+        assert_eq!(folded(&"a".repeat(75)), format!("{}\r\n", "a".repeat(75)));
+    }
+
+    #[test]
+    fn folded_lines() {
+        let ics = folded(&"a".repeat(160));
+        let lines: Vec<_> = ics.trim_end_matches("\r\n").split("\r\n").collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "a".repeat(75));
+        assert_eq!(lines[1], format!(" {}", "a".repeat(74)));
+        assert_eq!(lines[2], format!(" {}", "a".repeat(11)));
+    }
+
+    #[test]
+    fn folded_lines_keep_characters_whole() {
+        // Each `é` takes 2 bytes, so the 38th doesn't fit in the first line.
+        let ics = folded(&"é".repeat(40));
+        let (first, rest) = ics.split_once("\r\n").unwrap();
+        assert_eq!(first, "é".repeat(37));
+        assert_eq!(rest, format!(" {}\r\n", "é".repeat(3)));
+        assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+    }
+
+    #[test]
+    fn escaped_text() {
+        assert_eq!(
+            escape_text("Exam, part 1; 2\\3"),
+            "Exam\\, part 1\\; 2\\\\3"
+        );
+        assert_eq!(escape_text("line 1\r\nline 2"), "line 1\\nline 2");
+        assert_eq!(escape_text("Lliçó: 10:00"), "Lliçó: 10:00");
+    }
+
+    #[test]
+    fn links_to_the_site() {
+        let due = Timestamp::from_madrid(2024, 3, 5, 23, 59, 0).unwrap();
+        let ics = Deadlines::new()
+            .list("PRO1", "L1", due)
+            .to_ics("http://localhost:8080/");
+        assert!(ics.contains("\r\nURL:http://localhost:8080/courses/PRO1/L1\r\n"));
+    }
+}
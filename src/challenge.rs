@@ -0,0 +1,82 @@
+use scraper::Html;
+
+use crate::problem_id_types::Unlocalized;
+use crate::sans_io::{FetchChallenges, Operation, Request, Response};
+use crate::scrape::{selector, text, text_of};
+use crate::{Client, ProblemId, Result, Timestamp};
+
+/// A periodic challenge of <https://jutge.org>, such as the problem of the
+/// day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Challenge {
+    /// The name of the challenge, such as `Problem of the day`.
+    pub name: String,
+
+    /// The problem to solve.
+    pub problem: ProblemId<Unlocalized>,
+
+    /// The time the challenge ends, if the site shows it.
+    pub deadline: Option<Timestamp>,
+}
+
+impl Client {
+    /// Fetches the challenges that are currently open in the site, such as
+    /// the problem of the day, sorted by deadline. Challenges without a
+    /// deadline go last.
+    ///
+    /// Challenges whose deadline has already passed are left out, even if
+    /// the site still lists them.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`](crate::Error::NotAuthenticated) if
+    /// the site requires logging in to see the challenges.
+    pub fn current_challenges(&self) -> Result<Vec<Challenge>> {
+        let now = Timestamp::now();
+        let mut challenges = self.execute(&FetchChallenges)?;
+        challenges.retain(|challenge| challenge.deadline.is_none_or(|deadline| deadline > now));
+        challenges.sort_by_key(|challenge| (challenge.deadline.is_none(), challenge.deadline));
+        Ok(challenges)
+    }
+}
+
+impl Operation for FetchChallenges {
+    type Output = Vec<Challenge>;
+
+    fn request(&self) -> Request {
+        Request::page("/challenges")
+    }
+
+    fn parse(&self, response: &Response<'_>) -> Result<Vec<Challenge>> {
+        Ok(parse_challenges(&response.html()?))
+    }
+}
+
+/// Parses the challenges listed in a page, one per item of a list, article
+/// or table row with a link to a problem.
+fn parse_challenges(html: &Html) -> Vec<Challenge> {
+    html.select(&selector(
+        "#challenges li, #challenges article, #challenges tr, \
+         .challenges li, .challenges article, .challenges tr",
+    ))
+    .filter_map(|item| {
+        let link = item.select(&selector("a[href]")).find(|link| {
+            link.value()
+                .attr("href")
+                .is_some_and(|href| ProblemId::<Unlocalized>::parse_lenient(href).is_ok())
+        })?;
+        let problem = ProblemId::<Unlocalized>::parse_lenient(link.value().attr("href")?).ok()?;
+
+        let deadline = text_of(item, "time, .deadline")
+            .as_deref()
+            .and_then(Timestamp::find_in)
+            .or_else(|| Timestamp::find_in(&text(item)));
+
+        Some(Challenge {
+            name: text_of(item, "h3, h4, h5, strong, .name").unwrap_or_else(|| text(link)),
+            problem,
+            deadline,
+        })
+    })
+    .collect()
+}
@@ -1,5 +1,37 @@
+use std::collections::HashSet;
+#[cfg(feature = "client-auth")]
+use std::io::Write;
+use std::io::{self, BufRead, Read};
+use std::net::SocketAddr;
+#[cfg(feature = "cache")]
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use cookie_store::CookieStore;
+use scraper::Html;
 use ureq::Agent;
 use ureq::AgentBuilder;
+use ureq::{Request, Response};
+use url::Url;
+
+#[cfg(feature = "cache")]
+use crate::cache::DiskCache;
+use crate::cache::LruCache;
+use crate::debug_log::DebugLog;
+use crate::integrity;
+use crate::metrics::MetricsRecorder;
+#[cfg(feature = "client-auth")]
+use crate::multipart::Multipart;
+use crate::politeness::PolitenessLimits;
+use crate::problem_id_types::{Localized, Unlocalized};
+use crate::rate_limit::{is_throttling, retry_after, RateLimiter};
+use crate::resolver::Overrides;
+#[cfg(feature = "client-auth")]
+use crate::scrape::{is_blocked_account_page, is_login_page};
+use crate::{
+    Error, Exchange, Metrics, Politeness, ProblemId, ProblemLanguage, RawResponse, Result, Route,
+};
 
 /// A `Client` to interact with <https://jutge.org>.
 ///
@@ -7,6 +39,85 @@ use ureq::AgentBuilder;
 #[derive(Debug)]
 pub struct Client {
     agent: Agent,
+    agent_config: AgentConfig,
+    base_url: String,
+    debug_log: DebugLog,
+    metrics: Option<MetricsRecorder>,
+    throttle: Arc<RateLimiter>,
+    politeness: Arc<PolitenessLimits>,
+    max_retry_after: Duration,
+    #[cfg(feature = "client-auth")]
+    cache_capacity: usize,
+    cache: LruCache<String, Arc<[u8]>>,
+    pub(crate) existence_cache: LruCache<String, bool>,
+    #[cfg(feature = "cache")]
+    disk_cache: Option<DiskCache>,
+    #[cfg(feature = "cache")]
+    offline: bool,
+    request_timeout: Duration,
+    download_timeout: Duration,
+    max_page_size: u64,
+    max_download_size: u64,
+    compression: bool,
+    lenient_parsing: bool,
+    submit_limiter: Arc<RateLimiter>,
+    language: Option<ProblemLanguage>,
+}
+
+/// The configuration of the `ureq` agents of a [`Client`], kept to build the
+/// agents of its sessions.
+#[derive(Debug, Clone)]
+struct AgentConfig {
+    user_agent: String,
+    proxy: Option<ureq::Proxy>,
+    proxy_from_env: bool,
+    resolve: Overrides,
+    connect_timeout: Duration,
+    read_timeout: Duration,
+    max_redirects: u32,
+    max_idle_connections: usize,
+    debug_log: DebugLog,
+    metrics: Option<MetricsRecorder>,
+    ui_language: Option<(ProblemLanguage, Url)>,
+}
+
+impl AgentConfig {
+    /// Builds an agent that keeps its cookies in `cookies`, forcing the
+    /// language of the interface of the site if it's set.
+    fn build(&self, mut cookies: CookieStore) -> Agent {
+        if let Some((language, site)) = &self.ui_language {
+            let cookie = format!(
+                "{UI_LANGUAGE_COOKIE}={}; Path=/",
+                String::from_utf8_lossy(&language.code())
+            );
+            // The cookie is well formed, and set for the site itself.
+            let _ = cookies.parse(&cookie, site);
+        }
+
+        let mut agent_builder = AgentBuilder::new()
+            .user_agent(&self.user_agent)
+            .cookie_store(cookies)
+            .try_proxy_from_env(self.proxy_from_env);
+        if let Some(proxy) = self.proxy.clone() {
+            agent_builder = agent_builder.proxy(proxy);
+        }
+        if !self.resolve.is_empty() {
+            agent_builder = agent_builder.resolver(self.resolve.clone());
+        }
+
+        agent_builder = agent_builder
+            .timeout_connect(self.connect_timeout)
+            .timeout_read(self.read_timeout)
+            .redirects(self.max_redirects)
+            .max_idle_connections(self.max_idle_connections)
+            .max_idle_connections_per_host(self.max_idle_connections)
+            .middleware(self.debug_log.clone());
+        if let Some(metrics) = self.metrics.clone() {
+            agent_builder = agent_builder.middleware(metrics);
+        }
+
+        agent_builder.build()
+    }
 }
 
 impl Client {
@@ -23,6 +134,531 @@ impl Client {
     pub fn builder() -> ClientBuilder {
         ClientBuilder::new()
     }
+
+    /// Enables or disables the debug log of the `Client`.
+    ///
+    /// See [`ClientBuilder::debug()`] for more information.
+    pub fn set_debug(&self, enabled: bool) {
+        self.debug_log.set_enabled(enabled);
+    }
+
+    /// Checks whether the debug log of the `Client` is enabled.
+    #[must_use]
+    pub fn is_debug(&self) -> bool {
+        self.debug_log.is_enabled()
+    }
+
+    /// Takes a snapshot of the metrics of the `Client`, or returns `None` if
+    /// they aren't enabled.
+    ///
+    /// See [`ClientBuilder::metrics()`] for more information.
+    #[must_use]
+    pub fn metrics(&self) -> Option<Metrics> {
+        self.metrics.as_ref().map(MetricsRecorder::snapshot)
+    }
+
+    /// Checks whether the `Client` keeps what it can parse from pages that
+    /// only partly match the layout it expects.
+    ///
+    /// See [`ClientBuilder::lenient_parsing()`] for more information.
+    #[must_use]
+    pub const fn lenient_parsing(&self) -> bool {
+        self.lenient_parsing
+    }
+
+    /// Gets the politeness policy of the `Client`.
+    ///
+    /// See [`ClientBuilder::politeness()`] for more information.
+    #[must_use]
+    pub fn politeness(&self) -> &Politeness {
+        self.politeness.policy()
+    }
+
+    /// Blocks until a bulk crawl of the `Client` may make its next request,
+    /// as set by its [`Politeness`] policy.
+    pub(crate) fn pace_bulk(&self) {
+        let wait = self.politeness.pace_bulk();
+        self.record(|metrics| metrics.record_wait(wait));
+    }
+
+    /// Caps the concurrency a bulk crawl of the `Client` asks for, as set by
+    /// its [`Politeness`] policy.
+    pub(crate) fn bulk_concurrency(&self, requested: usize) -> usize {
+        self.politeness.bulk_concurrency(requested)
+    }
+
+    /// Empties the in-memory cache of the `Client`, so the next requests
+    /// fetch fresh pages from the site (or from the disk cache, if there's
+    /// one).
+    ///
+    /// See [`ClientBuilder::cache_capacity()`] for more information.
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+        self.existence_cache.clear();
+    }
+
+    /// Gets the default language of the `Client`, if it has one.
+    ///
+    /// See [`ClientBuilder::language()`] for more information.
+    #[must_use]
+    pub const fn language(&self) -> Option<ProblemLanguage> {
+        self.language
+    }
+
+    /// Gets the language the interface of the site is forced to, if it is.
+    ///
+    /// See [`ClientBuilder::ui_language()`] for more information.
+    #[must_use]
+    pub fn ui_language(&self) -> Option<ProblemLanguage> {
+        self.agent_config
+            .ui_language
+            .as_ref()
+            .map(|(language, _)| *language)
+    }
+
+    /// Localizes a problem id in the default language of the `Client`, or in
+    /// English if it doesn't have one.
+    ///
+    /// This lets callers who always work in one language use unlocalized ids
+    /// everywhere, and pass `client.localize(&id)?` to the methods that need
+    /// a localized one.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidProblemId`] in the same cases as
+    /// [`ProblemId::localize()`].
+    pub fn localize(&self, id: &ProblemId<Unlocalized>) -> Result<ProblemId<Localized>> {
+        id.localize(self.language.unwrap_or(ProblemLanguage::English))
+    }
+
+    /// Fetches a page of the site as is, without parsing it.
+    ///
+    /// This is an escape hatch for the pages (or the parts of them) the
+    /// crate doesn't parse yet, or doesn't parse correctly after a change in
+    /// the site. The request goes through the same throttling, retries and
+    /// cookies as every other request of the `Client`, but skips its caches.
+    /// Responses with an error status are returned too, for the caller to
+    /// check [`RawResponse::status`].
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`] if the page redirects to the login
+    /// page, [`Error::Throttled`] if the server keeps throttling the request,
+    /// or [`Error::UreqError`] on network errors.
+    pub fn get_raw(&self, route: &Route) -> Result<RawResponse> {
+        let path = route.path();
+        let response = match self.get(&path) {
+            Err(Error::UreqError(err)) => match *err {
+                ureq::Error::Status(_, response) => response,
+                err @ ureq::Error::Transport(_) => return Err(err.into()),
+            },
+            response => response?,
+        };
+
+        let status = response.status();
+        let url = response.get_url().to_owned();
+        let mut names = response.headers_names();
+        // There's a name per header, so repeated headers (such as
+        // `Set-Cookie`) have theirs repeated too.
+        let mut seen = HashSet::new();
+        names.retain(|name| seen.insert(name.clone()));
+        let headers = names
+            .into_iter()
+            .flat_map(|name| {
+                response
+                    .all(&name)
+                    .into_iter()
+                    .map(|value| (name.clone(), value.to_owned()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let body = self.read_page_body(response)?;
+
+        Ok(RawResponse {
+            status,
+            url,
+            headers,
+            body,
+        })
+    }
+
+    /// Writes the cookies of the `Client` as JSON, as done by
+    /// [`Session::save()`](crate::Session::save).
+    #[cfg(feature = "client-auth")]
+    pub(crate) fn save_cookies(&self, writer: &mut impl Write) -> Result<()> {
+        cookie_store::serde::json::save_incl_expired_and_nonpersistent(
+            &self.agent.cookie_store(),
+            writer,
+        )
+        .map_err(|err| Error::InvalidSession(err.to_string()))
+    }
+
+    /// Creates a `Client` with the same configuration as this one, but with
+    /// its own cookies and in-memory caches, so the pages seen by each user
+    /// don't get mixed.
+    ///
+    /// The throttling of requests and submissions, the politeness policy,
+    /// the debug log, the metrics and the disk cache are shared with this
+    /// `Client`, as they all concern the same site.
+    #[cfg(feature = "client-auth")]
+    pub(crate) fn fork(&self, cookies: CookieStore) -> Self {
+        Self {
+            agent: self.agent_config.build(cookies),
+            agent_config: self.agent_config.clone(),
+            base_url: self.base_url.clone(),
+            debug_log: self.debug_log.clone(),
+            metrics: self.metrics.clone(),
+            throttle: Arc::clone(&self.throttle),
+            politeness: Arc::clone(&self.politeness),
+            max_retry_after: self.max_retry_after,
+            cache_capacity: self.cache_capacity,
+            cache: LruCache::new(self.cache_capacity),
+            existence_cache: LruCache::new(self.cache_capacity),
+            #[cfg(feature = "cache")]
+            disk_cache: self.disk_cache.clone(),
+            #[cfg(feature = "cache")]
+            offline: self.offline,
+            request_timeout: self.request_timeout,
+            download_timeout: self.download_timeout,
+            max_page_size: self.max_page_size,
+            max_download_size: self.max_download_size,
+            compression: self.compression,
+            lenient_parsing: self.lenient_parsing,
+            submit_limiter: Arc::clone(&self.submit_limiter),
+            language: self.language,
+        }
+    }
+
+    /// Gets a copy of the cookies of the `Client`.
+    #[cfg(feature = "client-auth")]
+    pub(crate) fn cookies(&self) -> CookieStore {
+        self.agent.cookie_store().clone()
+    }
+
+    /// Gets the base URL of the site, such as `https://jutge.org`.
+    #[cfg(feature = "courses")]
+    pub(crate) fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Builds the absolute URL of a path in the site.
+    pub(crate) fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Creates a request to a path in the site, bounded by the timeout of
+    /// regular requests.
+    fn request(&self, method: &str, path: &str) -> Request {
+        let mut request = self
+            .agent
+            .request(method, &self.url(path))
+            .timeout(self.request_timeout);
+        if !self.compression {
+            // Otherwise, `ureq` asks for gzip and decompresses the responses.
+            request = request.set("Accept-Encoding", "identity");
+        }
+
+        match self.language {
+            Some(language) => request.set(
+                "Accept-Language",
+                &String::from_utf8_lossy(&language.code()),
+            ),
+            None => request,
+        }
+    }
+
+    /// Sends a request, retrying it when the server throttles it.
+    ///
+    /// When the server answers with a 429 or 503 status and a `Retry-After`
+    /// header, all the requests of the `Client` (submissions included) are
+    /// held back for the given time, and then the request is retried. This
+    /// gives up with [`Error::Throttled`] after a few attempts, or if the
+    /// server asks to wait longer than the configured maximum.
+    ///
+    /// Requests to `path` that end up redirected to the login page fail with
+    /// [`Error::NotAuthenticated`], so the login form is never parsed as if
+    /// it were the requested page.
+    fn send(&self, path: &str, request: impl Fn() -> Result<Response>) -> Result<Response> {
+        const MAX_ATTEMPTS: usize = 3;
+
+        #[cfg(feature = "cache")]
+        if self.offline {
+            return Err(Error::Offline);
+        }
+
+        for attempt in 1.. {
+            let wait = self.throttle.wait();
+            self.record(|metrics| metrics.record_wait(wait));
+
+            let response = match self.politeness.connect(&request) {
+                Err(Error::UreqError(err)) => match *err {
+                    ureq::Error::Status(_, response) if is_throttling(&response) => response,
+                    err => return Err(err.into()),
+                },
+                Ok(response) if self.redirected_to_login(path, &response) => {
+                    let _ = response.into_string();
+                    return Err(Error::NotAuthenticated);
+                }
+                result => return result,
+            };
+
+            match retry_after(&response) {
+                Some(wait) if wait <= self.max_retry_after && attempt < MAX_ATTEMPTS => {
+                    // Reading the body lets the connection go back to the pool.
+                    let _ = response.into_string();
+                    self.throttle.pause(wait);
+                    self.submit_limiter.pause(wait);
+                    self.record(MetricsRecorder::record_retry);
+                }
+                wait => return Err(Error::Throttled(wait)),
+            }
+        }
+
+        unreachable!("the retry loop only ends by returning")
+    }
+
+    /// Checks whether the response to a request to `path` comes from a
+    /// redirect to the login page, i.e., to `/login` or a path under it.
+    ///
+    /// Redirects to the home page aren't taken as such, as the site also
+    /// redirects there for other reasons; the pages read as a user detect the
+    /// login form in them instead.
+    fn redirected_to_login(&self, path: &str, response: &Response) -> bool {
+        let url = response.get_url();
+        if url == self.url(path) {
+            return false;
+        }
+
+        let Some(final_path) = url.strip_prefix(&self.base_url) else {
+            return false;
+        };
+        is_login_path(final_path)
+    }
+
+    /// Performs a GET request to a path in the site.
+    pub(crate) fn get(&self, path: &str) -> Result<Response> {
+        self.send(path, || Ok(self.request("GET", path).call()?))
+    }
+
+    /// Performs a HEAD request to a path in the site.
+    pub(crate) fn head(&self, path: &str) -> Result<Response> {
+        self.send(path, || Ok(self.request("HEAD", path).call()?))
+    }
+
+    /// Performs a GET request to a path in the site that serves a file,
+    /// returning its contents.
+    ///
+    /// Fails with [`Error::NotAuthenticated`] if the site serves an HTML page
+    /// instead, which happens when the file requires logging in.
+    ///
+    /// Files can be big, so the request is bounded by the download timeout
+    /// instead of the one of regular requests.
+    ///
+    /// Downloads are verified to be complete (see [`integrity::verify()`]).
+    /// A corrupt download is retried once, and then it fails with
+    /// [`Error::CorruptDownload`].
+    pub(crate) fn get_file(&self, path: &str) -> Result<Vec<u8>> {
+        let mut attempts = 0;
+
+        loop {
+            let response = self.send(path, || {
+                Ok(self
+                    .request("GET", path)
+                    .timeout(self.download_timeout)
+                    .call()?)
+            })?;
+
+            if response.content_type() == "text/html" {
+                let _ = response.into_string();
+                return Err(Error::NotAuthenticated);
+            }
+
+            let expected_length = response
+                .header("Content-Length")
+                .and_then(|length| length.parse().ok());
+            let problem = match self.read_body(response, self.max_download_size) {
+                Ok(contents) => match integrity::verify(&contents, expected_length) {
+                    Ok(()) => return Ok(contents),
+                    Err(problem) => problem,
+                },
+                // A connection closed mid-download leaves the file truncated.
+                Err(Error::IoError(err)) if err.kind() == io::ErrorKind::UnexpectedEof => {
+                    err.to_string()
+                }
+                Err(err) => return Err(err),
+            };
+
+            attempts += 1;
+            if attempts == 2 {
+                return Err(Error::CorruptDownload(problem));
+            }
+            self.record(MetricsRecorder::record_retry);
+        }
+    }
+
+    /// Gets the contents of a cacheable path: from the in-memory cache, then
+    /// from the disk cache and, if it isn't cached, by calling `fetch`. With
+    /// `refresh`, the caches are skipped and updated with the fetched
+    /// contents.
+    ///
+    /// In offline mode, paths always come from the caches, and the ones that
+    /// aren't cached fail with [`Error::Offline`].
+    fn cached(
+        &self,
+        path: &str,
+        refresh: bool,
+        fetch: impl FnOnce() -> Result<Vec<u8>>,
+    ) -> Result<Arc<[u8]>> {
+        #[cfg(feature = "cache")]
+        let refresh = refresh && !self.offline;
+
+        if !refresh {
+            if let Some(contents) = self.cache.get(&path.to_owned()) {
+                return Ok(contents);
+            }
+        }
+
+        #[cfg(feature = "cache")]
+        if !refresh {
+            if let Some(contents) = self.disk_cache.as_ref().and_then(|disk| disk.read(path)) {
+                let contents: Arc<[u8]> = contents.into();
+                self.cache.insert(path.into(), Arc::clone(&contents));
+                return Ok(contents);
+            }
+            if self.offline {
+                return Err(Error::Offline);
+            }
+        }
+
+        let contents = fetch()?;
+        #[cfg(feature = "cache")]
+        if let Some(disk) = &self.disk_cache {
+            disk.write(path, &contents);
+        }
+
+        let contents: Arc<[u8]> = contents.into();
+        self.cache.insert(path.into(), Arc::clone(&contents));
+        Ok(contents)
+    }
+
+    /// Checks whether a path is in the disk cache.
+    #[cfg(feature = "cache")]
+    pub(crate) fn is_on_disk(&self, path: &str) -> bool {
+        self.disk_cache
+            .as_ref()
+            .is_some_and(|disk| disk.contains(path))
+    }
+
+    /// Same as [`Client::get_file()`], but serving the file from the caches
+    /// if it has been fetched before, unless `refresh` is set.
+    pub(crate) fn get_cached_file(&self, path: &str, refresh: bool) -> Result<Arc<[u8]>> {
+        self.cached(path, refresh, || self.get_file(path))
+    }
+
+    /// Same as [`Client::get_authenticated_page()`], but serving the page
+    /// from the caches if it has been fetched before, unless `refresh` is
+    /// set.
+    ///
+    /// This must only be used for pages that don't change between requests,
+    /// such as problem statements, and never for pages with forms.
+    pub(crate) fn get_cached_page(&self, path: &str, refresh: bool) -> Result<Html> {
+        let contents = self.get_cached_page_body(path, refresh)?;
+        Ok(Html::parse_document(&String::from_utf8_lossy(&contents)))
+    }
+
+    /// Same as [`Client::get_cached_page()`], but without parsing the page.
+    pub(crate) fn get_cached_page_body(&self, path: &str, refresh: bool) -> Result<Arc<[u8]>> {
+        self.cached(path, refresh, || self.read_page_body(self.get(path)?))
+    }
+
+    /// Performs a GET request to a path in the site and parses the response
+    /// as HTML, failing with [`Error::NotAuthenticated`] if the site
+    /// redirects to the login page or shows the login form instead.
+    #[cfg(feature = "client-auth")]
+    pub(crate) fn get_authenticated_page(&self, path: &str) -> Result<Html> {
+        self.read_authenticated_html(self.get(path)?)
+    }
+
+    /// Reads the body of a response as an HTML document.
+    #[cfg(feature = "client-auth")]
+    pub(crate) fn read_html(&self, response: Response) -> Result<Html> {
+        let body = self.read_page_body(response)?;
+        Ok(Html::parse_document(&String::from_utf8_lossy(&body)))
+    }
+
+    /// Reads the body of a response that is a page, bounded by the maximum
+    /// page size.
+    pub(crate) fn read_page_body(&self, response: Response) -> Result<Vec<u8>> {
+        self.read_body(response, self.max_page_size)
+    }
+
+    /// Same as [`read_body()`], but counting the bytes read in the metrics.
+    fn read_body(&self, response: Response, limit: u64) -> Result<Vec<u8>> {
+        let body = read_body(response, limit)?;
+        self.record(|metrics| metrics.record_bytes(body.len()));
+        Ok(body)
+    }
+
+    /// Blocks until the next submission is allowed by the submit interval.
+    #[cfg(feature = "client-auth")]
+    pub(crate) fn wait_to_submit(&self) {
+        let wait = self.submit_limiter.wait();
+        self.record(|metrics| metrics.record_wait(wait));
+    }
+
+    /// Records something in the metrics, if they're enabled.
+    fn record(&self, record: impl FnOnce(&MetricsRecorder)) {
+        if let Some(metrics) = &self.metrics {
+            record(metrics);
+        }
+    }
+
+    /// Same as [`Client::read_html()`], but fails with
+    /// [`Error::NotAuthenticated`] if the response is the login page, or with
+    /// [`Error::AccountBlocked`] if it says the account is blocked.
+    #[cfg(feature = "client-auth")]
+    pub(crate) fn read_authenticated_html(&self, response: Response) -> Result<Html> {
+        let html = self.read_html(response)?;
+
+        if is_blocked_account_page(&html) {
+            Err(Error::AccountBlocked)
+        } else if is_login_page(&html) {
+            Err(Error::NotAuthenticated)
+        } else {
+            Ok(html)
+        }
+    }
+
+    /// Performs a POST request with an url-encoded form to a path in the site.
+    #[cfg(feature = "client-auth")]
+    pub(crate) fn post_form(&self, path: &str, fields: &[(&str, &str)]) -> Result<Response> {
+        self.send(path, || Ok(self.request("POST", path).send_form(fields)?))
+    }
+
+    /// Performs a POST request with a multipart form to a path in the site.
+    #[cfg(feature = "client-auth")]
+    pub(crate) fn post_multipart(&self, path: &str, form: Multipart) -> Result<Response> {
+        let content_type = form.content_type();
+        let body = form.finish();
+
+        self.send(path, || {
+            Ok(self
+                .request("POST", path)
+                .set("Content-Type", &content_type)
+                .send_bytes(&body)?)
+        })
+    }
+
+    /// Performs a POST request with a JSON body to a URL outside the site,
+    /// such as a webhook, through the agent of the client (so with its
+    /// proxy, timeouts and user agent).
+    #[cfg(feature = "courses")]
+    pub(crate) fn post_json(&self, url: &str, json: &str) -> Result<()> {
+        self.agent
+            .post(url)
+            .timeout(self.request_timeout)
+            .set("Content-Type", "application/json")
+            .send_string(json)?;
+        Ok(())
+    }
 }
 
 impl Default for Client {
@@ -31,11 +667,69 @@ impl Default for Client {
     }
 }
 
+/// Reads the body of a response, failing with [`Error::ResponseTooLarge`] if
+/// it's longer than `limit` bytes.
+fn read_body(response: Response, limit: u64) -> Result<Vec<u8>> {
+    let length = response
+        .header("Content-Length")
+        .and_then(|length| length.parse::<u64>().ok());
+    if length.is_some_and(|length| length > limit) {
+        return Err(Error::ResponseTooLarge(limit));
+    }
+
+    // The length isn't always known in advance, so the body is still
+    // checked while reading it.
+    let mut body = Vec::new();
+    response
+        .into_reader()
+        .take(limit.saturating_add(1))
+        .read_to_end(&mut body)?;
+    if body.len() as u64 > limit {
+        return Err(Error::ResponseTooLarge(limit));
+    }
+
+    Ok(body)
+}
+
+/// The cookie where the site keeps the language of its interface.
+const UI_LANGUAGE_COOKIE: &str = "language";
+
+/// The user agent of the crate, sent with every request.
+const APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
 /// A `ClientBuilder` can be used to create a [`Client`] with custom
 /// configuration.
 #[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ClientBuilder {
-    agent_builder: AgentBuilder,
+    user_agent: String,
+    cookies: CookieStore,
+    base_url: String,
+    debug_log: DebugLog,
+    metrics: Option<MetricsRecorder>,
+    max_retry_after: Duration,
+    max_redirects: u32,
+    cache_capacity: usize,
+    #[cfg(feature = "cache")]
+    cache_dir: Option<PathBuf>,
+    #[cfg(feature = "cache")]
+    offline: bool,
+    connect_timeout: Duration,
+    read_timeout: Duration,
+    request_timeout: Duration,
+    download_timeout: Duration,
+    max_idle_connections: usize,
+    max_page_size: u64,
+    max_download_size: u64,
+    compression: bool,
+    lenient_parsing: bool,
+    resolve: Overrides,
+    proxy: Option<ureq::Proxy>,
+    proxy_from_env: bool,
+    submit_interval: Duration,
+    politeness: Politeness,
+    language: Option<ProblemLanguage>,
+    ui_language: Option<ProblemLanguage>,
 }
 
 impl ClientBuilder {
@@ -44,20 +738,437 @@ impl ClientBuilder {
     /// This is the same as [`Client::builder()`].
     #[must_use]
     pub fn new() -> Self {
-        const APP_USER_AGENT: &str =
-            concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+        const DEFAULT_BASE_URL: &str = "https://jutge.org";
+        const DEFAULT_CACHE_CAPACITY: usize = 64;
+        const DEFAULT_MAX_REDIRECTS: u32 = 5;
+        const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+        const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(30);
+        const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+        const DEFAULT_DOWNLOAD_TIMEOUT: Duration = Duration::from_mins(5);
+        const DEFAULT_MAX_IDLE_CONNECTIONS: usize = 8;
+        const DEFAULT_MAX_PAGE_SIZE: u64 = 8 << 20;
+        const DEFAULT_MAX_DOWNLOAD_SIZE: u64 = 64 << 20;
+        const DEFAULT_MAX_RETRY_AFTER: Duration = Duration::from_mins(1);
+        const DEFAULT_SUBMIT_INTERVAL: Duration = Duration::from_secs(2);
+
+        Self {
+            user_agent: APP_USER_AGENT.into(),
+            cookies: CookieStore::default(),
+            base_url: DEFAULT_BASE_URL.into(),
+            debug_log: DebugLog::new(),
+            metrics: None,
+            max_retry_after: DEFAULT_MAX_RETRY_AFTER,
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            cache_capacity: DEFAULT_CACHE_CAPACITY,
+            #[cfg(feature = "cache")]
+            cache_dir: None,
+            #[cfg(feature = "cache")]
+            offline: false,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            read_timeout: DEFAULT_READ_TIMEOUT,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            download_timeout: DEFAULT_DOWNLOAD_TIMEOUT,
+            max_idle_connections: DEFAULT_MAX_IDLE_CONNECTIONS,
+            max_page_size: DEFAULT_MAX_PAGE_SIZE,
+            max_download_size: DEFAULT_MAX_DOWNLOAD_SIZE,
+            compression: true,
+            lenient_parsing: false,
+            resolve: Overrides::default(),
+            proxy: None,
+            proxy_from_env: true,
+            submit_interval: DEFAULT_SUBMIT_INTERVAL,
+            politeness: Politeness::new(),
+            language: None,
+            ui_language: None,
+        }
+    }
+
+    /// Sets the base URL of the site. Defaults to <https://jutge.org>.
+    ///
+    /// This is mostly useful to point the `Client` to a mirror or a mock
+    /// server.
+    #[must_use]
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').into();
+        self
+    }
+
+    /// Enables the debug log, which reports a summary of every HTTP exchange
+    /// with the site (see [`Exchange`]). Disabled by default.
+    ///
+    /// By default, the log is written to the standard error. It can be
+    /// redirected with [`ClientBuilder::debug_sink()`], and toggled once the
+    /// `Client` is built with [`Client::set_debug()`].
+    #[must_use]
+    pub fn debug(self, enabled: bool) -> Self {
+        self.debug_log.set_enabled(enabled);
+        self
+    }
+
+    /// Sets the function that receives the entries of the debug log.
+    #[must_use]
+    pub fn debug_sink(mut self, sink: impl Fn(&Exchange) + Send + Sync + 'static) -> Self {
+        self.debug_log = self.debug_log.with_sink(sink);
+        self
+    }
+
+    /// Enables the metrics of the `Client`, which count its requests by
+    /// endpoint and status code, its retries, the bytes it downloads, and
+    /// the time it waits for the site and for its rate limits. Disabled by
+    /// default.
+    ///
+    /// The metrics are read with [`Client::metrics()`], which is meant to be
+    /// polled by long-running tools to report them to their monitoring
+    /// system.
+    #[must_use]
+    pub fn metrics(mut self, enabled: bool) -> Self {
+        self.metrics = enabled.then(MetricsRecorder::default);
+        self
+    }
+
+    /// Sets the longest time the `Client` will wait when the site throttles a
+    /// request and asks to retry it later. Defaults to 1 minute.
+    ///
+    /// If the site asks to wait longer, the request fails with
+    /// [`Error::Throttled`]. A zero duration disables retrying.
+    #[must_use]
+    pub fn max_retry_after(mut self, max: Duration) -> Self {
+        self.max_retry_after = max;
+        self
+    }
+
+    /// Sets how many redirects the `Client` follows in a request before
+    /// failing. Defaults to 5.
+    ///
+    /// Regardless of this limit, a request that is redirected to the login
+    /// page fails with [`Error::NotAuthenticated`].
+    #[must_use]
+    pub fn max_redirects(mut self, max: u32) -> Self {
+        self.max_redirects = max;
+        self
+    }
+
+    /// Sets how many pages the in-memory cache of the `Client` can hold.
+    /// Defaults to 64.
+    ///
+    /// The cache keeps the pages that don't change often, such as problem
+    /// statements, sample archives and whether a problem exists, so
+    /// operations that touch the same problem several times only fetch them
+    /// once. The least recently used pages are evicted when the cache is
+    /// full, and a zero capacity disables it. Pages that change, such as the
+    /// status of a submission, are never cached.
+    #[must_use]
+    pub fn cache_capacity(mut self, capacity: usize) -> Self {
+        self.cache_capacity = capacity;
+        self
+    }
+
+    /// Sets a directory where the `Client` keeps a copy of the pages it
+    /// caches (see [`ClientBuilder::cache_capacity()`]), so they're still
+    /// available in later runs. No disk cache is used by default.
+    ///
+    /// The directory is created when the first page is stored. Pages are
+    /// never evicted from it, so it can be synced ahead of time to work
+    /// [offline](ClientBuilder::offline()).
+    #[cfg(feature = "cache")]
+    #[must_use]
+    pub fn cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(dir.into());
+        self
+    }
+
+    /// Enables the offline mode, in which the `Client` never touches the
+    /// network. Disabled by default.
+    ///
+    /// Reads of cacheable pages, such as statements and samples, are served
+    /// from the [disk cache](ClientBuilder::cache_dir()). Everything else,
+    /// including cache misses, fails with [`Error::Offline`].
+    #[cfg(feature = "cache")]
+    #[must_use]
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Sets the longest time to wait for a connection to the site to be
+    /// established. Defaults to 10 seconds.
+    #[must_use]
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Sets the longest time to wait for the site to send more data while
+    /// receiving a response. Defaults to 30 seconds.
+    ///
+    /// This applies to each read, so it doesn't limit the total time a big
+    /// download can take.
+    #[must_use]
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = timeout;
+        self
+    }
+
+    /// Sets the longest time a regular request, such as fetching a page,
+    /// can take overall. Defaults to 30 seconds.
+    #[must_use]
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Sets the longest time the download of a file, such as the archive of
+    /// a problem, can take overall. Defaults to 5 minutes.
+    #[must_use]
+    pub fn download_timeout(mut self, timeout: Duration) -> Self {
+        self.download_timeout = timeout;
+        self
+    }
+
+    /// Sets how many idle connections to the site the `Client` keeps open to
+    /// reuse them in later requests. Defaults to 8.
+    ///
+    /// Reusing connections saves a TLS handshake per request. Batch
+    /// operations keep up to one connection busy per worker, so this should
+    /// be at least their concurrency. A zero disables connection reuse.
+    #[must_use]
+    pub fn max_idle_connections(mut self, max: usize) -> Self {
+        self.max_idle_connections = max;
+        self
+    }
 
-        let agent_builder = AgentBuilder::new().user_agent(APP_USER_AGENT);
+    /// Sets the maximum size, in bytes, of a page of the site. Defaults to
+    /// 8 MiB.
+    ///
+    /// Bigger responses are aborted with [`Error::ResponseTooLarge`] instead
+    /// of being read into memory.
+    #[must_use]
+    pub fn max_page_size(mut self, max: u64) -> Self {
+        self.max_page_size = max;
+        self
+    }
+
+    /// Sets the maximum size, in bytes, of a downloaded file, such as the
+    /// archive of a problem. Defaults to 64 MiB.
+    ///
+    /// Bigger downloads are aborted with [`Error::ResponseTooLarge`] instead
+    /// of being read into memory.
+    #[must_use]
+    pub fn max_download_size(mut self, max: u64) -> Self {
+        self.max_download_size = max;
+        self
+    }
+
+    /// Sets whether the `Client` asks the site to compress its responses
+    /// with gzip. Enabled by default.
+    ///
+    /// Compressed responses are decompressed transparently. Pages compress
+    /// very well, so this saves most of the bandwidth of large syncs. The
+    /// limits of [`ClientBuilder::max_page_size()`] and
+    /// [`ClientBuilder::max_download_size()`] apply to the decompressed
+    /// size.
+    #[must_use]
+    pub fn compression(mut self, enabled: bool) -> Self {
+        self.compression = enabled;
+        self
+    }
+
+    /// Makes the `Client` connect to `addr` whenever it would connect to
+    /// `host`, bypassing DNS.
+    ///
+    /// This is useful to point the site (or the host of the
+    /// [base URL](ClientBuilder::base_url())) to a mock server in tests, or
+    /// to a known address in restricted networks. The port of `addr` is used
+    /// instead of the one in the URL. Can be called several times to pin
+    /// several hosts.
+    #[must_use]
+    pub fn resolve(mut self, host: &str, addr: SocketAddr) -> Self {
+        self.resolve.insert(host, addr);
+        self
+    }
+
+    /// Sends all the requests through a proxy, given by its URL. By default,
+    /// the proxy is taken from the environment (see
+    /// [`ClientBuilder::proxy_from_env()`]).
+    ///
+    /// The URL has the form `<protocol>://<user>:<password>@<host>:<port>`,
+    /// where everything but the host is optional. The supported protocols
+    /// are `http` (the default) and, with the `socks-proxy` feature,
+    /// `socks4`, `socks4a` and `socks5`. The credentials, if any, are used to
+    /// authenticate with the proxy.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidProxy`] if the URL isn't a valid proxy URL.
+    pub fn proxy(mut self, url: &str) -> Result<Self> {
+        let proxy = ureq::Proxy::new(url).map_err(|err| Error::InvalidProxy(err.to_string()))?;
+        self.proxy = Some(proxy);
+        Ok(self)
+    }
+
+    /// Same as [`ClientBuilder::proxy()`], but with the credentials given
+    /// apart, so they don't need to be escaped in the URL.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidProxy`] if the URL isn't a valid proxy URL.
+    pub fn proxy_with_credentials(self, url: &str, user: &str, password: &str) -> Result<Self> {
+        let (protocol, server) = url.split_once("://").unwrap_or(("http", url));
+        let server = server.rsplit_once('@').map_or(server, |(_, server)| server);
+
+        self.proxy(&format!("{protocol}://{user}:{password}@{server}"))
+    }
+
+    /// Sets whether the proxy is taken from the `ALL_PROXY`, `HTTPS_PROXY`
+    /// or `HTTP_PROXY` environment variables (or their lowercase versions)
+    /// when none is given with [`ClientBuilder::proxy()`]. Enabled by
+    /// default.
+    #[must_use]
+    pub fn proxy_from_env(mut self, enabled: bool) -> Self {
+        self.proxy_from_env = enabled;
+        self
+    }
+
+    /// Identifies the application using the `Client` to the site, by
+    /// prepending `application` to the user agent of the crate.
+    ///
+    /// For example, `my-tool/1.0` results in a user agent such as
+    /// `my-tool/1.0 jutge/0.1.0`. This lets the administrators of the site
+    /// tell apart the traffic of each tool. By default, only the user agent
+    /// of the crate is sent. Control characters are removed from
+    /// `application`.
+    #[must_use]
+    pub fn user_agent(mut self, application: &str) -> Self {
+        let application: String = application.chars().filter(|c| !c.is_control()).collect();
+        let application = application.trim();
+
+        let user_agent = if application.is_empty() {
+            APP_USER_AGENT.to_owned()
+        } else {
+            format!("{application} {APP_USER_AGENT}")
+        };
+        self.user_agent = user_agent;
+        self
+    }
+
+    /// Sets the minimum time between two submissions made by the `Client`.
+    /// Defaults to 2 seconds.
+    ///
+    /// This keeps batch operations from flooding the judge queue.
+    #[must_use]
+    pub fn submit_interval(mut self, interval: Duration) -> Self {
+        self.submit_interval = interval;
+        self
+    }
+
+    /// Makes the `Client` keep what it can parse from pages that only partly
+    /// match the layout it expects, instead of failing. Disabled by default.
+    ///
+    /// When enabled, the fields of a page that can't be parsed are left
+    /// with defaults, as documented by each method. To know which fields
+    /// failed, perform the operation with [`Client::execute_partial()`].
+    #[must_use]
+    pub fn lenient_parsing(mut self, enabled: bool) -> Self {
+        self.lenient_parsing = enabled;
+        self
+    }
+
+    /// Sets the politeness policy of the `Client`, which paces its requests
+    /// and its bulk crawls. Defaults to [`Politeness::new()`].
+    #[must_use]
+    pub fn politeness(mut self, politeness: Politeness) -> Self {
+        self.politeness = politeness;
+        self
+    }
+
+    /// Sets the default language of the `Client`. By default, it has none.
+    ///
+    /// The language is sent in the `Accept-Language` header of every
+    /// request, so the site answers in it, and it's used by
+    /// [`Client::localize()`] to pick the localized version of problems.
+    ///
+    /// Languages whose code isn't made of 2 lowercase ASCII letters are
+    /// ignored.
+    #[must_use]
+    pub fn language(mut self, language: ProblemLanguage) -> Self {
+        self.language = ProblemLanguage::try_from(language.code()).ok();
+        self
+    }
+
+    /// Forces the language of the interface of the site, such as its menus
+    /// and the labels of its tables. By default, the site picks it.
+    ///
+    /// The site shows its interface in the language chosen by the user, and
+    /// keeps it in a cookie. The scrapers of the crate understand the pages
+    /// in the languages of the site, but some texts (such as the reasons
+    /// submissions are rejected for) are only recognized in some of them, so
+    /// forcing one lets them always see the same words. The cookie is set
+    /// again for each [`Session`](crate::Session), after logging in.
+    ///
+    /// Unlike [`ClientBuilder::language()`], this doesn't change the
+    /// language of the statements. Languages whose code isn't made of 2
+    /// lowercase ASCII letters are ignored.
+    #[must_use]
+    pub fn ui_language(mut self, language: ProblemLanguage) -> Self {
+        self.ui_language = ProblemLanguage::try_from(language.code()).ok();
+        self
+    }
 
-        Self { agent_builder }
+    /// Restores a session saved with [`Session::save()`](crate::Session::save),
+    /// so the `Client` starts already logged in. Use [`Client::resume()`] to
+    /// get the [`Session`](crate::Session).
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidSession`] if the session can't be read.
+    pub fn session(mut self, reader: impl BufRead) -> Result<Self> {
+        let store = cookie_store::serde::json::load_all(reader)
+            .map_err(|err| Error::InvalidSession(err.to_string()))?;
+        self.cookies = store;
+        Ok(self)
     }
 
     /// Builds a `Client` from this builder.
     #[must_use]
     pub fn build(self) -> Client {
-        let agent = self.agent_builder.build();
+        let agent_config = AgentConfig {
+            user_agent: self.user_agent,
+            proxy: self.proxy,
+            proxy_from_env: self.proxy_from_env,
+            resolve: self.resolve,
+            connect_timeout: self.connect_timeout,
+            read_timeout: self.read_timeout,
+            max_redirects: self.max_redirects,
+            max_idle_connections: self.max_idle_connections,
+            debug_log: self.debug_log.clone(),
+            metrics: self.metrics.clone(),
+            ui_language: self.ui_language.zip(Url::parse(&self.base_url).ok()),
+        };
+        let politeness = PolitenessLimits::new(self.politeness);
 
-        Client { agent }
+        Client {
+            agent: agent_config.build(self.cookies),
+            agent_config,
+            base_url: self.base_url,
+            debug_log: self.debug_log,
+            metrics: self.metrics,
+            throttle: Arc::new(politeness.throttle()),
+            politeness: Arc::new(politeness),
+            max_retry_after: self.max_retry_after,
+            #[cfg(feature = "client-auth")]
+            cache_capacity: self.cache_capacity,
+            cache: LruCache::new(self.cache_capacity),
+            existence_cache: LruCache::new(self.cache_capacity),
+            #[cfg(feature = "cache")]
+            disk_cache: self.cache_dir.map(DiskCache::new),
+            #[cfg(feature = "cache")]
+            offline: self.offline,
+            request_timeout: self.request_timeout,
+            download_timeout: self.download_timeout,
+            max_page_size: self.max_page_size,
+            max_download_size: self.max_download_size,
+            compression: self.compression,
+            lenient_parsing: self.lenient_parsing,
+            submit_limiter: Arc::new(RateLimiter::new(self.submit_interval)),
+            language: self.language,
+        }
     }
 }
 
@@ -66,3 +1177,43 @@ impl Default for ClientBuilder {
         Self::new()
     }
 }
+
+/// Turns the refusal of the site to show an instructor page into
+/// [`Error::NotAnInstructor`].
+#[cfg(feature = "client-auth")]
+pub(crate) fn instructor_only(err: Error) -> Error {
+    match err {
+        Error::UreqError(err) if matches!(*err, ureq::Error::Status(403, _)) => {
+            Error::NotAnInstructor
+        }
+        err => err,
+    }
+}
+
+/// Checks whether a path, which can include a query, is the login page or a
+/// page under it.
+fn is_login_path(path: &str) -> bool {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    path == "/login" || path.starts_with("/login/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_login_path;
+
+    #[test]
+    fn login_paths() {
+        assert!(is_login_path("/login"));
+        assert!(is_login_path("/login?next=/profile"));
+        assert!(is_login_path("/login/expired"));
+    }
+
+    #[test]
+    fn other_paths() {
+        assert!(!is_login_path(""));
+        assert!(!is_login_path("/"));
+        assert!(!is_login_path("/?next=/login"));
+        assert!(!is_login_path("/logins"));
+        assert!(!is_login_path("/problems/P068688_en"));
+    }
+}
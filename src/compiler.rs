@@ -0,0 +1,115 @@
+use std::{convert::Infallible, fmt::Display, str::FromStr};
+
+/// A compiler (or interpreter) available in <https://jutge.org>.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Compiler {
+    /// GNU C Compiler (GCC).
+    Gcc,
+
+    /// GNU C++ Compiler (G++).
+    Gxx,
+
+    /// GNU C++ Compiler with C++11 (G++11).
+    Gxx11,
+
+    /// GNU C++ Compiler with C++17 (G++17).
+    Gxx17,
+
+    /// Clang C Compiler (Clang).
+    Clang,
+
+    /// Python 3 interpreter (Python3).
+    Python3,
+
+    /// Java Development Kit (JDK).
+    Java,
+
+    /// Glasgow Haskell Compiler (GHC).
+    Haskell,
+
+    /// Rust compiler (Rust).
+    Rust,
+
+    /// A compiler not known by this crate, identified by its id in the site.
+    Other(String),
+}
+
+impl Compiler {
+    /// Returns the id the site uses for this compiler, such as `G++17`.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        match self {
+            Self::Gcc => "GCC",
+            Self::Gxx => "G++",
+            Self::Gxx11 => "G++11",
+            Self::Gxx17 => "G++17",
+            Self::Clang => "Clang",
+            Self::Python3 => "Python3",
+            Self::Java => "JDK",
+            Self::Haskell => "GHC",
+            Self::Rust => "Rust",
+            Self::Other(id) => id,
+        }
+    }
+
+    /// Returns the usual file extension of source files for this compiler,
+    /// without the leading dot.
+    #[must_use]
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Gcc | Self::Clang => "c",
+            Self::Gxx | Self::Gxx11 | Self::Gxx17 => "cc",
+            Self::Python3 => "py",
+            Self::Java => "java",
+            Self::Haskell => "hs",
+            Self::Rust => "rs",
+            Self::Other(_) => "txt",
+        }
+    }
+
+    /// Guesses the compiler of a source file from its extension (without the
+    /// leading dot), such as `cc`. C++ files are compiled with
+    /// [`Compiler::Gxx17`].
+    #[must_use]
+    pub fn for_extension(extension: &str) -> Option<Self> {
+        let compiler = match extension {
+            "c" => Self::Gcc,
+            "cc" | "cpp" | "cxx" => Self::Gxx17,
+            "py" => Self::Python3,
+            "java" => Self::Java,
+            "hs" => Self::Haskell,
+            "rs" => Self::Rust,
+            _ => return None,
+        };
+
+        Some(compiler)
+    }
+}
+
+impl Display for Compiler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+impl FromStr for Compiler {
+    type Err = Infallible;
+
+    /// Parses a compiler from its id in the site. Unknown ids are parsed as
+    /// [`Compiler::Other`].
+    fn from_str(s: &str) -> Result<Self, Infallible> {
+        Ok(match s {
+            "GCC" => Self::Gcc,
+            "G++" => Self::Gxx,
+            "G++11" => Self::Gxx11,
+            "G++17" => Self::Gxx17,
+            "Clang" => Self::Clang,
+            "Python3" => Self::Python3,
+            "JDK" => Self::Java,
+            "GHC" => Self::Haskell,
+            "Rust" => Self::Rust,
+            _ => Self::Other(s.into()),
+        })
+    }
+}
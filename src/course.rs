@@ -0,0 +1,197 @@
+use scraper::Html;
+
+use crate::sans_io::{
+    FetchCourseLists, FetchExamScore, FetchExamWindow, FetchListDeadline, FetchListScore,
+    Operation, Request, Response,
+};
+use crate::scrape::{parse_score, selector, text};
+#[cfg(feature = "courses")]
+use crate::Session;
+use crate::{Error, ExamWindow, Result, Score, Timestamp};
+
+#[cfg(feature = "courses")]
+impl Session {
+    /// Fetches the points the logged-in user has in a list of problems of a
+    /// course, as shown by the site (e.g. "you have 7/10 points in this
+    /// list").
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`] if the session has expired,
+    /// or [`Error::UnexpectedPageLayout`] if the list doesn't show any
+    /// points.
+    pub fn list_score(&self, course_id: &str, list_id: &str) -> Result<Score> {
+        self.client().execute(&FetchListScore {
+            course_id: course_id.into(),
+            list_id: list_id.into(),
+        })
+    }
+
+    /// Fetches the points the logged-in user got in an exam, as shown by
+    /// the site.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`] if the session has expired,
+    /// or [`Error::UnexpectedPageLayout`] if the exam doesn't show any
+    /// points.
+    pub fn exam_score(&self, exam_id: &str) -> Result<Score> {
+        self.client().execute(&FetchExamScore {
+            exam_id: exam_id.into(),
+        })
+    }
+
+    /// Fetches the deadline of a list of problems of a course, if it has
+    /// one.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`] if the session has expired.
+    pub fn list_deadline(&self, course_id: &str, list_id: &str) -> Result<Option<Timestamp>> {
+        self.client().execute(&FetchListDeadline {
+            course_id: course_id.into(),
+            list_id: list_id.into(),
+        })
+    }
+
+    /// Fetches the ids of the lists of problems of a course, in the order
+    /// the site shows them.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`] if the session has expired.
+    pub fn course_lists(&self, course_id: &str) -> Result<Vec<String>> {
+        self.client().execute(&FetchCourseLists {
+            course_id: course_id.into(),
+        })
+    }
+
+    /// Fetches the time window in which an exam can be taken.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`] if the session has expired,
+    /// or [`Error::UnexpectedPageLayout`] if the exam doesn't show when it
+    /// starts and ends.
+    pub fn exam_window(&self, exam_id: &str) -> Result<ExamWindow> {
+        self.client().execute(&FetchExamWindow {
+            exam_id: exam_id.into(),
+        })
+    }
+}
+
+impl Operation for FetchListScore {
+    type Output = Score;
+
+    fn request(&self) -> Request {
+        Request::page(format!("/courses/{}/{}", self.course_id, self.list_id))
+    }
+
+    fn parse(&self, response: &Response<'_>) -> Result<Score> {
+        find_points(&response.html()?, &self.request().path)
+    }
+}
+
+impl Operation for FetchExamScore {
+    type Output = Score;
+
+    fn request(&self) -> Request {
+        Request::page(format!("/exams/{}", self.exam_id))
+    }
+
+    fn parse(&self, response: &Response<'_>) -> Result<Score> {
+        find_points(&response.html()?, &self.request().path)
+    }
+}
+
+impl Operation for FetchListDeadline {
+    type Output = Option<Timestamp>;
+
+    fn request(&self) -> Request {
+        Request::page(format!("/courses/{}/{}", self.course_id, self.list_id))
+    }
+
+    fn parse(&self, response: &Response<'_>) -> Result<Option<Timestamp>> {
+        Ok(find_labelled(
+            &response.html()?,
+            &[
+                "deadline",
+                "termini",
+                "plazo",
+                "límit",
+                "límite",
+                "date limite",
+                "frist",
+            ],
+            Timestamp::find_in,
+        ))
+    }
+}
+
+impl Operation for FetchCourseLists {
+    type Output = Vec<String>;
+
+    fn request(&self) -> Request {
+        Request::page(format!("/courses/{}", self.course_id))
+    }
+
+    fn parse(&self, response: &Response<'_>) -> Result<Vec<String>> {
+        let prefix = format!("{}/", self.request().path);
+        let mut lists: Vec<String> = Vec::new();
+        for link in response.html()?.select(&selector("a[href]")) {
+            let href = link.value().attr("href").unwrap_or_default();
+            // Other pages of the course, such as its progress, aren't lists.
+            let Some(list_id) = href
+                .strip_prefix(&prefix)
+                .map(|rest| rest.trim_end_matches('/'))
+                .filter(|id| !id.is_empty() && !id.contains('/') && *id != "progress")
+            else {
+                continue;
+            };
+            if !lists.iter().any(|id| id == list_id) {
+                lists.push(list_id.into());
+            }
+        }
+
+        Ok(lists)
+    }
+}
+
+impl Operation for FetchExamWindow {
+    type Output = ExamWindow;
+
+    fn request(&self) -> Request {
+        Request::page(format!("/exams/{}", self.exam_id))
+    }
+
+    fn parse(&self, response: &Response<'_>) -> Result<ExamWindow> {
+        let html = response.html()?;
+        let find = |words: &[&str], what: &str| {
+            find_labelled(&html, words, Timestamp::find_in).ok_or_else(|| {
+                Error::unexpected_layout(self.request().path, format!("no {what} time found"))
+            })
+        };
+
+        Ok(ExamWindow {
+            start: find(&["start", "begin", "inici", "comienzo"], "start")?,
+            end: find(&["end", "final", "fin"], "end")?,
+        })
+    }
+}
+
+/// Finds the first `awarded/total` score in a text that mentions points, in
+/// any of the languages of the site.
+fn find_points(html: &Html, page: &str) -> Result<Score> {
+    find_labelled(html, &["point", "punt", "nota"], parse_score)
+        .ok_or_else(|| Error::unexpected_layout(page, "no points found"))
+}
+
+/// Finds a value in a text that mentions any of `words`, ignoring case.
+fn find_labelled<T>(html: &Html, words: &[&str], parse: impl Fn(&str) -> Option<T>) -> Option<T> {
+    html.select(&selector("p, span, div, td, strong, li"))
+        .map(text)
+        .filter(|text| {
+            let text = text.to_lowercase();
+            words.iter().any(|w| text.contains(w))
+        })
+        .filter_map(|text| Some((text.len(), parse(&text)?)))
+        // Outer elements contain the text of inner ones, so the shortest
+        // text is the one closest to the value.
+        .min_by_key(|(len, _)| *len)
+        .map(|(_, value)| value)
+}
@@ -0,0 +1,169 @@
+//! The problems of the lists of <https://jutge.org> courses, with the status
+//! the logged-in user has in each of them.
+
+use crate::problem_id_types::Unlocalized;
+use crate::ProblemId;
+
+/// The status of a problem for the logged-in user, as shown by the icon next
+/// to it in the lists of problems of the site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum UserProblemStatus {
+    /// The user hasn't submitted anything to the problem (no icon).
+    NotTried,
+
+    /// The user got a submission rejected, and none accepted (a red icon).
+    Rejected,
+
+    /// The user submitted the problem without getting it accepted nor
+    /// rejected, e.g. with a partial score (an orange icon).
+    Attempted,
+
+    /// The user got a submission accepted (a green icon).
+    Accepted,
+}
+
+impl UserProblemStatus {
+    /// Checks whether the user got the problem accepted.
+    #[must_use]
+    pub const fn is_accepted(self) -> bool {
+        matches!(self, Self::Accepted)
+    }
+
+    /// Checks whether the user submitted anything to the problem.
+    #[must_use]
+    pub const fn is_tried(self) -> bool {
+        !matches!(self, Self::NotTried)
+    }
+}
+
+/// A problem in a list of problems of a course.
+///
+/// This is fetched with
+/// [`Session::list_problems()`](crate::Session::list_problems).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ListEntry {
+    /// The problem.
+    pub problem: ProblemId<Unlocalized>,
+
+    /// The title of the problem, as shown in the list.
+    pub title: String,
+
+    /// The status of the problem for the logged-in user.
+    pub status: UserProblemStatus,
+}
+
+#[cfg(feature = "web-client")]
+mod fetch {
+    use scraper::ElementRef;
+
+    use super::{ListEntry, UserProblemStatus};
+    use crate::problem_id_types::Unlocalized;
+    use crate::sans_io::{FetchListProblems, Operation, Request, Response};
+    use crate::scrape::{selector, text};
+    #[cfg(feature = "courses")]
+    use crate::Session;
+    use crate::{ProblemId, Result};
+
+    #[cfg(feature = "courses")]
+    impl Session {
+        /// Fetches the problems of a list of problems of a course, in the
+        /// order the site shows them, with the status the logged-in user has
+        /// in each of them.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAuthenticated`](crate::Error::NotAuthenticated)
+        /// if the session has expired.
+        pub fn list_problems(&self, course_id: &str, list_id: &str) -> Result<Vec<ListEntry>> {
+            self.client().execute(&FetchListProblems {
+                course_id: course_id.into(),
+                list_id: list_id.into(),
+            })
+        }
+    }
+
+    impl Operation for FetchListProblems {
+        type Output = Vec<ListEntry>;
+
+        fn request(&self) -> Request {
+            Request::page(format!("/courses/{}/{}", self.course_id, self.list_id))
+        }
+
+        fn parse(&self, response: &Response<'_>) -> Result<Vec<ListEntry>> {
+            let html = response.html()?;
+
+            let mut entries: Vec<ListEntry> = Vec::new();
+            for link in html.select(&selector("a[href^='/problems/']")) {
+                let Some(problem) = link
+                    .value()
+                    .attr("href")
+                    .and_then(|href| ProblemId::<Unlocalized>::parse_lenient(href).ok())
+                else {
+                    continue;
+                };
+                if entries.iter().any(|entry| entry.problem == problem) {
+                    continue;
+                }
+
+                let title = text(link);
+                let title = match title.split_once('·') {
+                    Some((_, title)) => title.trim().to_owned(),
+                    None => title,
+                };
+                entries.push(ListEntry {
+                    problem,
+                    title,
+                    status: entry_status(link),
+                });
+            }
+
+            Ok(entries)
+        }
+    }
+
+    /// Finds the status icon in the row (or the item) of the list that
+    /// contains a link to a problem.
+    fn entry_status(link: ElementRef<'_>) -> UserProblemStatus {
+        let Some(row) = link
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .find(|element| matches!(element.value().name(), "li" | "tr"))
+        else {
+            return UserProblemStatus::NotTried;
+        };
+
+        row.select(&selector("img, i, span"))
+            .find_map(|icon| {
+                let element = icon.value();
+                let attributes = ["src", "class", "alt", "title"]
+                    .iter()
+                    .filter_map(|name| element.attr(name))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .to_lowercase();
+                attributes
+                    .split(|c: char| !c.is_ascii_alphanumeric())
+                    .find_map(icon_status)
+            })
+            .unwrap_or(UserProblemStatus::NotTried)
+    }
+
+    /// Finds the status shown by an icon, given a word of its attributes
+    /// (the image, the CSS classes and the alternative text).
+    fn icon_status(word: &str) -> Option<UserProblemStatus> {
+        match word {
+            "green" | "accepted" | "success" | "check" | "ok" | "ac" => {
+                Some(UserProblemStatus::Accepted)
+            }
+            "orange" | "yellow" | "warning" | "partial" | "scored" => {
+                Some(UserProblemStatus::Attempted)
+            }
+            "red" | "danger" | "rejected" | "times" | "cross" | "wrong" => {
+                Some(UserProblemStatus::Rejected)
+            }
+            _ => None,
+        }
+    }
+}
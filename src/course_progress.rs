@@ -0,0 +1,338 @@
+//! The progress of the students of a <https://jutge.org> course, as seen by
+//! its instructors.
+
+use std::fmt::Write as _;
+use std::io::Write;
+
+use crate::problem_id_types::Unlocalized;
+use crate::{ProblemId, Result, Verdict};
+
+/// The progress of the students of a course: the verdict each of them got in
+/// each problem.
+///
+/// This is fetched with
+/// [`Session::course_progress()`](crate::Session::course_progress), and can
+/// be exported with [`CourseProgress::to_csv()`] and
+/// [`CourseProgress::to_json()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CourseProgress {
+    /// The id of the course.
+    pub course_id: String,
+
+    /// The problems of the course, in the order the site lists them.
+    pub problems: Vec<ProblemId<Unlocalized>>,
+
+    /// The students of the course.
+    pub students: Vec<StudentProgress>,
+}
+
+/// The progress of a student in a course.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StudentProgress {
+    /// The name of the student.
+    pub name: String,
+
+    /// The email of the student, if the site shows it.
+    pub email: Option<String>,
+
+    /// The best verdict the student got in each problem of the course, in
+    /// the same order as [`CourseProgress::problems`], or `None` if they
+    /// didn't submit anything.
+    pub verdicts: Vec<Option<Verdict>>,
+}
+
+impl StudentProgress {
+    /// Counts the problems the student got accepted.
+    #[must_use]
+    pub fn accepted(&self) -> usize {
+        self.verdicts
+            .iter()
+            .filter(|verdict| verdict.as_ref().is_some_and(Verdict::is_accepted))
+            .count()
+    }
+}
+
+impl CourseProgress {
+    /// Writes the progress as CSV, with a row per student.
+    ///
+    /// The columns are the name and the email of the student, the verdict
+    /// code of each problem (empty if the student didn't submit anything),
+    /// the number of accepted problems and the number of problems of the
+    /// course.
+    ///
+    /// # Errors
+    /// Returns [`Error::IoError`](crate::Error::IoError) if the CSV can't be
+    /// written.
+    pub fn to_csv(&self, writer: &mut impl Write) -> Result<()> {
+        let mut header = vec!["name".to_owned(), "email".to_owned()];
+        header.extend(self.problems.iter().map(ToString::to_string));
+        header.extend(["accepted".to_owned(), "total".to_owned()]);
+        write_csv_row(writer, &header)?;
+
+        for student in &self.students {
+            let mut row = vec![
+                student.name.clone(),
+                student.email.clone().unwrap_or_default(),
+            ];
+            row.extend(self.problems.iter().enumerate().map(|(i, _)| {
+                student
+                    .verdicts
+                    .get(i)
+                    .and_then(Option::as_ref)
+                    .map(|verdict| verdict.code().to_owned())
+                    .unwrap_or_default()
+            }));
+            row.extend([
+                student.accepted().to_string(),
+                self.problems.len().to_string(),
+            ]);
+            write_csv_row(writer, &row)?;
+        }
+
+        Ok(())
+    }
+
+    /// Formats the progress as JSON.
+    ///
+    /// The JSON is an object with the `course`, its `problems` and its
+    /// `students`. Each student has a `name`, an `email` (or `null`), the
+    /// `verdicts` by problem id (`null` if they didn't submit anything), and
+    /// the number of `accepted` problems out of the `total`.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let problems: Vec<_> = self
+            .problems
+            .iter()
+            .map(|problem| json_string(problem.as_str()))
+            .collect();
+
+        let students: Vec<_> = self
+            .students
+            .iter()
+            .map(|student| {
+                let verdicts: Vec<_> = self
+                    .problems
+                    .iter()
+                    .enumerate()
+                    .map(|(i, problem)| {
+                        let verdict = student.verdicts.get(i).and_then(Option::as_ref);
+                        format!(
+                            "{}:{}",
+                            json_string(problem.as_str()),
+                            verdict.map_or_else(|| "null".into(), |v| json_string(v.code())),
+                        )
+                    })
+                    .collect();
+
+                format!(
+                    "{{\"name\":{},\"email\":{},\"verdicts\":{{{}}},\"accepted\":{},\"total\":{}}}",
+                    json_string(&student.name),
+                    student
+                        .email
+                        .as_deref()
+                        .map_or_else(|| "null".into(), json_string),
+                    verdicts.join(","),
+                    student.accepted(),
+                    self.problems.len(),
+                )
+            })
+            .collect();
+
+        format!(
+            "{{\"course\":{},\"problems\":[{}],\"students\":[{}]}}",
+            json_string(&self.course_id),
+            problems.join(","),
+            students.join(","),
+        )
+    }
+}
+
+/// Writes a CSV row, quoting the fields that need it.
+pub(crate) fn write_csv_row(writer: &mut impl Write, fields: &[String]) -> Result<()> {
+    let fields: Vec<_> = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.clone()
+            }
+        })
+        .collect();
+
+    Ok(write!(writer, "{}\r\n", fields.join(","))?)
+}
+
+/// Formats a string as a JSON string literal.
+pub(crate) fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", u32::from(c));
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(feature = "web-client")]
+mod fetch {
+    use super::{CourseProgress, StudentProgress};
+    use crate::problem_id_types::Unlocalized;
+    use crate::sans_io::{FetchCourseProgress, Operation, Request, Response};
+    use crate::scrape::{selector, text};
+    #[cfg(feature = "courses")]
+    use crate::Session;
+    use crate::{Error, Partial, ProblemId, Result};
+
+    #[cfg(feature = "courses")]
+    impl Session {
+        /// Fetches the progress of the students of a course. Only the
+        /// instructors of the course can see it.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAuthenticated`] if the session has expired,
+        /// or [`Error::UnexpectedPageLayout`] if the progress table can't be
+        /// found in the course page (e.g. because the user isn't an
+        /// instructor of the course).
+        pub fn course_progress(&self, course_id: &str) -> Result<CourseProgress> {
+            self.client().execute(&FetchCourseProgress {
+                course_id: course_id.into(),
+            })
+        }
+    }
+
+    impl Operation for FetchCourseProgress {
+        type Output = CourseProgress;
+
+        fn request(&self) -> Request {
+            Request::page(format!("/courses/{}/progress", self.course_id))
+        }
+
+        fn parse(&self, response: &Response<'_>) -> Result<CourseProgress> {
+            self.parse_partial(response)?
+                .into_result(self.request().path)
+        }
+
+        /// Problem columns that can't be found are left out, so the students
+        /// are kept without verdicts. The page still fails without a table.
+        fn parse_partial(&self, response: &Response<'_>) -> Result<Partial<CourseProgress>> {
+            let html = response.html()?;
+            let no_table =
+                || Error::unexpected_layout(self.request().path, "no progress table found");
+
+            let table = html
+                .select(&selector("table"))
+                .next()
+                .ok_or_else(no_table)?;
+            let header: Vec<_> = table
+                .select(&selector("tr"))
+                .next()
+                .ok_or_else(no_table)?
+                .select(&selector("th, td"))
+                .collect();
+
+            // The problem columns are the ones whose heading is a problem id,
+            // either as text or as a link to the problem.
+            let mut email_column = None;
+            let mut columns = Vec::new();
+            for (i, cell) in header.iter().enumerate().skip(1) {
+                let heading = text(*cell);
+                let link = cell
+                    .select(&selector("a[href]"))
+                    .filter_map(|link| link.value().attr("href"))
+                    .find_map(|href| ProblemId::<Unlocalized>::parse_lenient(href).ok());
+
+                if let Some(problem) = link.or_else(|| parse_heading(&heading)) {
+                    columns.push((i, problem));
+                } else if ["mail", "correu", "correo"]
+                    .iter()
+                    .any(|w| heading.to_lowercase().contains(w))
+                {
+                    email_column = Some(i);
+                }
+            }
+
+            let students = table
+                .select(&selector("tr"))
+                .skip(1)
+                .filter_map(|row| {
+                    let cells: Vec<_> = row.select(&selector("td")).map(text).collect();
+                    let name = cells.first().filter(|name| !name.is_empty())?.clone();
+
+                    Some(StudentProgress {
+                        name,
+                        email: email_column
+                            .and_then(|i| cells.get(i))
+                            .filter(|email| !email.is_empty())
+                            .cloned(),
+                        verdicts: columns
+                            .iter()
+                            .map(|(i, _)| cells.get(*i).and_then(|cell| cell.parse().ok()))
+                            .collect(),
+                    })
+                })
+                .collect();
+
+            let mut progress = Partial::complete(CourseProgress {
+                course_id: self.course_id.clone(),
+                problems: columns.iter().map(|(_, problem)| problem.clone()).collect(),
+                students,
+            });
+            if columns.is_empty() {
+                progress.fail("problems", "no problem columns found");
+            }
+            Ok(progress)
+        }
+    }
+
+    /// Parses the heading of a problem column, which is just its id.
+    fn parse_heading(heading: &str) -> Option<ProblemId<Unlocalized>> {
+        ProblemId::<Unlocalized>::parse_lenient(heading)
+            .ok()
+            .filter(|_| !heading.contains(' '))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{json_string, write_csv_row};
+
+    fn csv_row(fields: &[&str]) -> String {
+        let fields: Vec<String> = fields.iter().map(|&field| field.into()).collect();
+        let mut row = Vec::new();
+        write_csv_row(&mut row, &fields).unwrap();
+        String::from_utf8(row).unwrap()
+    }
+
+    #[test]
+    fn plain_csv_fields() {
+        assert_eq!(csv_row(&["Ada", "AC", ""]), "Ada,AC,\r\n");
+        assert_eq!(csv_row(&[]), "\r\n");
+    }
+
+    #[test]
+    fn quoted_csv_fields() {
+        assert_eq!(csv_row(&["Lovelace, Ada"]), "\"Lovelace, Ada\"\r\n");
+        assert_eq!(csv_row(&["the \"best\""]), "\"the \"\"best\"\"\"\r\n");
+        assert_eq!(csv_row(&["two\nlines", "x"]), "\"two\nlines\",x\r\n");
+        assert_eq!(csv_row(&["cr\r"]), "\"cr\r\"\r\n");
+    }
+
+    #[test]
+    fn json_strings() {
+        assert_eq!(json_string("Ada"), "\"Ada\"");
+        assert_eq!(json_string("\"a\\b\"\n\t"), "\"\\\"a\\\\b\\\"\\n\\t\"");
+    }
+}
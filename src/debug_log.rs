@@ -0,0 +1,196 @@
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use ureq::{Middleware, MiddlewareNext, Request, Response};
+
+/// A summary of an HTTP exchange between a [`Client`](crate::Client) and the
+/// site, as reported by its debug log.
+///
+/// Exchanges never include headers, cookies or bodies, and the values of
+/// query parameters are redacted, so they're safe to log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exchange {
+    /// The HTTP method of the request, such as `GET`.
+    pub method: String,
+
+    /// The URL of the request, with the values of its query parameters
+    /// redacted.
+    pub url: String,
+
+    /// The status code of the response, if one was received.
+    pub status: Option<u16>,
+
+    /// The time it took to receive the response headers.
+    pub duration: Duration,
+
+    /// The size of the response body, if the server reported it.
+    pub size: Option<u64>,
+
+    /// The error that made the request fail, if any.
+    pub error: Option<String>,
+}
+
+impl Display for Exchange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.method, self.url)?;
+        match self.status {
+            Some(status) => write!(f, " -> {status}")?,
+            None => f.write_str(" -> no response")?,
+        }
+        write!(f, " in {}ms", self.duration.as_millis())?;
+        if let Some(size) = self.size {
+            write!(f, ", {size} bytes")?;
+        }
+        if let Some(error) = &self.error {
+            write!(f, " ({error})")?;
+        }
+        Ok(())
+    }
+}
+
+type Sink = dyn Fn(&Exchange) + Send + Sync;
+
+/// The debug log of a `Client`, installed as a `ureq` middleware.
+///
+/// It's always installed, so it can be enabled at any time.
+#[derive(Clone)]
+pub(crate) struct DebugLog {
+    enabled: Arc<AtomicBool>,
+    sink: Arc<Sink>,
+}
+
+impl DebugLog {
+    /// Creates a disabled log that writes to the standard error.
+    pub(crate) fn new() -> Self {
+        Self {
+            enabled: Arc::new(AtomicBool::new(false)),
+            sink: Arc::new(|exchange| eprintln!("[jutge] {exchange}")),
+        }
+    }
+
+    /// Replaces the function that receives the exchanges.
+    pub(crate) fn with_sink(self, sink: impl Fn(&Exchange) + Send + Sync + 'static) -> Self {
+        Self {
+            sink: Arc::new(sink),
+            ..self
+        }
+    }
+
+    pub(crate) fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+}
+
+impl std::fmt::Debug for DebugLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DebugLog")
+            .field("enabled", &self.is_enabled())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Middleware for DebugLog {
+    fn handle(&self, request: Request, next: MiddlewareNext) -> Result<Response, ureq::Error> {
+        if !self.is_enabled() {
+            return next.handle(request);
+        }
+
+        let method = request.method().to_owned();
+        let url = redact(request.url());
+        let start = Instant::now();
+
+        let result = next.handle(request);
+
+        let duration = start.elapsed();
+        let (response, error) = match &result {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => (Some(response), None),
+            Err(ureq::Error::Transport(transport)) => (None, Some(describe(transport))),
+        };
+
+        (self.sink)(&Exchange {
+            method,
+            url,
+            status: response.map(Response::status),
+            duration,
+            size: response
+                .and_then(|response| response.header("Content-Length"))
+                .and_then(|size| size.parse().ok()),
+            error,
+        });
+
+        result
+    }
+}
+
+/// Describes a transport error without its URL, which `ureq` includes in
+/// its `Display`.
+fn describe(transport: &ureq::Transport) -> String {
+    match transport.message() {
+        Some(message) => format!("{}: {message}", transport.kind()),
+        None => transport.kind().to_string(),
+    }
+}
+
+/// Redacts the values of the query parameters of an URL.
+fn redact(url: &str) -> String {
+    let Some((path, query)) = url.split_once('?') else {
+        return url.into();
+    };
+
+    let query: Vec<_> = query
+        .split('&')
+        .map(|param| match param.split_once('=') {
+            Some((name, _)) => format!("{name}=<redacted>"),
+            None => param.into(),
+        })
+        .collect();
+
+    format!("{path}?{}", query.join("&"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::{redact, DebugLog};
+
+    #[test]
+    fn redacted_queries() {
+        assert_eq!(
+            redact("https://jutge.org/problems"),
+            "https://jutge.org/problems"
+        );
+        assert_eq!(
+            redact("https://jutge.org/users?search=alex&page"),
+            "https://jutge.org/users?search=<redacted>&page"
+        );
+    }
+
+    #[test]
+    fn redacted_transport_errors() {
+        let logged = Arc::new(Mutex::new(Vec::new()));
+        let log = DebugLog::new().with_sink({
+            let logged = Arc::clone(&logged);
+            move |exchange| logged.lock().unwrap().push(exchange.to_string())
+        });
+        log.set_enabled(true);
+
+        // Nothing listens on port 1, so the connection is refused.
+        let agent = ureq::builder().middleware(log).build();
+        assert!(agent
+            .get("http://127.0.0.1:1/login?token=secret")
+            .call()
+            .is_err());
+
+        let logged = logged.lock().unwrap();
+        assert_eq!(logged.len(), 1);
+        assert!(logged[0].contains("no response"), "{}", logged[0]);
+        assert!(!logged[0].contains("secret"), "{}", logged[0]);
+    }
+}
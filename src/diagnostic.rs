@@ -0,0 +1,419 @@
+//! Parsing of the messages of compilers and interpreters.
+
+use std::fmt::Display;
+
+/// A message from a compiler about a source file, such as an error or a
+/// warning.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The file the message is about, as reported by the compiler.
+    pub file: String,
+
+    /// The line the message is about, starting at 1.
+    pub line: u32,
+
+    /// The column the message is about, starting at 1, if the compiler
+    /// reports it.
+    pub column: Option<u32>,
+
+    /// How serious the message is.
+    pub severity: Severity,
+
+    /// The text of the message.
+    pub message: String,
+}
+
+/// The severity of a [`Diagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Additional information about another diagnostic.
+    Note,
+
+    /// A problem that doesn't prevent compilation.
+    Warning,
+
+    /// A problem that makes compilation fail.
+    Error,
+}
+
+impl Display for Diagnostic {
+    /// Formats the diagnostic like `file:line:column: severity: message`,
+    /// which most editors understand.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)?;
+        if let Some(column) = self.column {
+            write!(f, ":{column}")?;
+        }
+        let severity = match self.severity {
+            Severity::Note => "note",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, ": {severity}: {}", self.message)
+    }
+}
+
+/// Extracts the diagnostics from the output of a compiler, such as the one
+/// of a compilation error.
+///
+/// The formats of GCC and Clang (also used by GHC), `javac`, `rustc` and
+/// Python tracebacks are understood. Lines that aren't part of a diagnostic
+/// are ignored.
+#[must_use]
+pub fn parse_diagnostics(output: &str) -> Vec<Diagnostic> {
+    let lines: Vec<&str> = output.lines().collect();
+    let mut diagnostics = Vec::new();
+
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        i += 1;
+
+        if let Some(diagnostic) = parse_rustc(line, lines.get(i..).unwrap_or_default()) {
+            diagnostics.push(diagnostic);
+        } else if let Some(mut diagnostic) = parse_gcc(line) {
+            // GHC puts the message in the following, indented, lines.
+            if diagnostic.message.is_empty() {
+                diagnostic.message = lines
+                    .get(i)
+                    .filter(|next| next.starts_with(char::is_whitespace))
+                    .map_or_else(String::new, |next| next.trim().into());
+            }
+            diagnostics.push(diagnostic);
+        } else if let Some(diagnostic) = parse_python(line, lines.get(i..).unwrap_or_default()) {
+            diagnostics.push(diagnostic);
+        }
+    }
+
+    diagnostics
+}
+
+fn parse_severity(s: &str) -> Option<Severity> {
+    match s.trim().to_lowercase().as_str() {
+        "error" | "fatal error" => Some(Severity::Error),
+        "warning" => Some(Severity::Warning),
+        "note" | "info" => Some(Severity::Note),
+        _ => None,
+    }
+}
+
+/// Parses a line like `main.cc:3:5: error: message` or
+/// `Main.java:3: error: message`.
+fn parse_gcc(line: &str) -> Option<Diagnostic> {
+    let (file, rest) = line.split_once(':')?;
+    let (line_number, rest) = rest.split_once(':')?;
+    let line_number = line_number.trim().parse().ok()?;
+
+    let (column, rest) = match rest.split_once(':') {
+        Some((column, rest)) if column.trim().parse::<u32>().is_ok() => {
+            (column.trim().parse().ok(), rest)
+        }
+        _ => (None, rest),
+    };
+    let (severity, message) = rest.split_once(':').unwrap_or((rest, ""));
+    let severity = parse_severity(severity)?;
+
+    if file.trim().is_empty() {
+        return None;
+    }
+
+    Some(Diagnostic {
+        file: file.trim().into(),
+        line: line_number,
+        column,
+        severity,
+        message: message.trim().into(),
+    })
+}
+
+/// Parses a rustc diagnostic: a line like `error[E0425]: message`, followed
+/// by a line like `  --> src/main.rs:3:5`.
+fn parse_rustc(line: &str, following: &[&str]) -> Option<Diagnostic> {
+    let (header, message) = line.split_once(": ")?;
+    let severity = parse_severity(header.split('[').next()?)?;
+
+    let location = following.first()?.trim().strip_prefix("--> ")?;
+    let mut parts = location.rsplitn(3, ':');
+    let column = parts.next()?.parse().ok()?;
+    let line_number = parts.next()?.parse().ok()?;
+    let file = parts.next()?;
+
+    Some(Diagnostic {
+        file: file.into(),
+        line: line_number,
+        column: Some(column),
+        severity,
+        message: message.trim().into(),
+    })
+}
+
+/// Parses a Python traceback entry: a line like
+/// `  File "main.py", line 3, in <module>`. The message is taken from the
+/// exception at the end of the traceback.
+fn parse_python(line: &str, following: &[&str]) -> Option<Diagnostic> {
+    let rest = line.trim().strip_prefix("File \"")?;
+    let (file, rest) = rest.split_once('"')?;
+    let line_number = rest
+        .strip_prefix(", line ")?
+        .split(',')
+        .next()?
+        .trim()
+        .parse()
+        .ok()?;
+
+    // Only the innermost entry of a traceback is reported.
+    if following
+        .iter()
+        .take_while(|line| line.starts_with(char::is_whitespace))
+        .any(|line| line.trim().starts_with("File \""))
+    {
+        return None;
+    }
+
+    let message = following
+        .iter()
+        .find(|line| !line.starts_with(char::is_whitespace))
+        .map_or_else(String::new, |line| line.trim().into());
+
+    Some(Diagnostic {
+        file: file.into(),
+        line: line_number,
+        column: None,
+        severity: Severity::Error,
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostic(
+        file: &str,
+        line: u32,
+        column: Option<u32>,
+        severity: Severity,
+        message: &str,
+    ) -> Diagnostic {
+        Diagnostic {
+            file: file.into(),
+            line,
+            column,
+            severity,
+            message: message.into(),
+        }
+    }
+
+    #[test]
+    fn gcc_diagnostics() {
+        let output = "\
+main.cc:1:10: fatal error: vector.h: No such file or directory
+main.cc: In function 'int main()':
+main.cc:6:6: error: no matching function for call to 'f(int, int)'
+    6 |     f(1, 2);
+      |     ~^~~~~~
+main.cc:2:6: note: candidate: 'void f(int)'
+    2 | void f(int x) {}
+      |      ^
+main.cc:2:6: note:   candidate expects 1 argument, 2 provided
+main.cc:5:9: warning: unused variable 'y' [-Wunused-variable]
+    5 |     int y;
+      |         ^
+compilation terminated.
+";
+        assert_eq!(
+            parse_diagnostics(output),
+            [
+                diagnostic(
+                    "main.cc",
+                    1,
+                    Some(10),
+                    Severity::Error,
+                    "vector.h: No such file or directory",
+                ),
+                diagnostic(
+                    "main.cc",
+                    6,
+                    Some(6),
+                    Severity::Error,
+                    "no matching function for call to 'f(int, int)'",
+                ),
+                diagnostic(
+                    "main.cc",
+                    2,
+                    Some(6),
+                    Severity::Note,
+                    "candidate: 'void f(int)'",
+                ),
+                diagnostic(
+                    "main.cc",
+                    2,
+                    Some(6),
+                    Severity::Note,
+                    "candidate expects 1 argument, 2 provided",
+                ),
+                diagnostic(
+                    "main.cc",
+                    5,
+                    Some(9),
+                    Severity::Warning,
+                    "unused variable 'y' [-Wunused-variable]",
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn javac_diagnostics() {
+        let output = "\
+Main.java:5: error: cannot find symbol
+        System.out.println(x);
+                           ^
+  symbol:   variable x
+  location: class Main
+Main.java:3: warning: [removal] Integer(int) in Integer has been deprecated
+        Integer i = new Integer(3);
+                    ^
+1 error
+1 warning
+";
+        assert_eq!(
+            parse_diagnostics(output),
+            [
+                diagnostic("Main.java", 5, None, Severity::Error, "cannot find symbol"),
+                diagnostic(
+                    "Main.java",
+                    3,
+                    None,
+                    Severity::Warning,
+                    "[removal] Integer(int) in Integer has been deprecated",
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn rustc_diagnostics() {
+        let output = "\
+error[E0061]: this function takes 1 argument but 2 arguments were supplied
+ --> main.rs:2:5
+  |
+2 |     f(1, 2);
+  |     ^    - unexpected argument of type `{integer}`
+  |
+note: function defined here
+ --> main.rs:6:4
+  |
+6 | fn f(x: i32) {}
+  |    ^ ------
+help: remove the extra argument
+  |
+2 -     f(1, 2);
+2 +     f(1);
+  |
+
+warning: unused variable: `y`
+ --> main.rs:3:9
+  |
+3 |     let y = 1;
+  |         ^ help: if this is intentional, prefix it with an underscore: `_y`
+  |
+  = note: `#[warn(unused_variables)]` on by default
+
+error: aborting due to 1 previous error; 1 warning emitted
+
+For more information about this error, try `rustc --explain E0061`.
+";
+        assert_eq!(
+            parse_diagnostics(output),
+            [
+                diagnostic(
+                    "main.rs",
+                    2,
+                    Some(5),
+                    Severity::Error,
+                    "this function takes 1 argument but 2 arguments were supplied",
+                ),
+                diagnostic(
+                    "main.rs",
+                    6,
+                    Some(4),
+                    Severity::Note,
+                    "function defined here"
+                ),
+                diagnostic(
+                    "main.rs",
+                    3,
+                    Some(9),
+                    Severity::Warning,
+                    "unused variable: `y`",
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn python_diagnostics() {
+        let output = "\
+Traceback (most recent call last):
+  File \"main.py\", line 7, in <module>
+    main()
+  File \"main.py\", line 4, in main
+    print(1 / n)
+          ~~^~~
+ZeroDivisionError: division by zero
+";
+        assert_eq!(
+            parse_diagnostics(output),
+            [diagnostic(
+                "main.py",
+                4,
+                None,
+                Severity::Error,
+                "ZeroDivisionError: division by zero",
+            )]
+        );
+
+        let output = "\
+  File \"main.py\", line 2
+    print(\"hi\"
+         ^
+SyntaxError: '(' was never closed
+";
+        assert_eq!(
+            parse_diagnostics(output),
+            [diagnostic(
+                "main.py",
+                2,
+                None,
+                Severity::Error,
+                "SyntaxError: '(' was never closed",
+            )]
+        );
+    }
+
+    #[test]
+    fn unrecognised_output() {
+        let output = "\
+/usr/bin/ld: /tmp/ccq1x2.o: in function `main':
+main.cc:(.text+0x5): undefined reference to `f()'
+collect2: error: ld returned 1 exit status
+make: *** [Makefile:2: main] Error 1
+Segmentation fault (core dumped)
+";
+        assert_eq!(parse_diagnostics(output), []);
+        assert_eq!(parse_diagnostics(""), []);
+    }
+
+    #[test]
+    fn displayed_diagnostics() {
+        assert_eq!(
+            diagnostic("main.cc", 6, Some(6), Severity::Error, "oops").to_string(),
+            "main.cc:6:6: error: oops"
+        );
+        assert_eq!(
+            diagnostic("Main.java", 3, None, Severity::Warning, "hmm").to_string(),
+            "Main.java:3: warning: hmm"
+        );
+    }
+}
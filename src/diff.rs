@@ -0,0 +1,248 @@
+use std::fmt::Display;
+
+/// The number of lines shown around the first difference by default.
+const DEFAULT_CONTEXT: usize = 2;
+
+/// A report of how an output differs from the expected one.
+///
+/// A `Diff` focuses on the first differing line, including some lines of
+/// context around it, so it can be shown to users (with colors, if wanted)
+/// without dumping the whole outputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diff {
+    first_difference: usize,
+    lines: Vec<DiffLine>,
+    whitespace_only: bool,
+}
+
+/// A line shown in a [`Diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    /// Where the line comes from.
+    pub kind: DiffLineKind,
+
+    /// The number of the line in its output, starting at 1.
+    pub number: usize,
+
+    /// The contents of the line, without the line break.
+    pub text: String,
+}
+
+/// The origin of a [`DiffLine`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiffLineKind {
+    /// A line that is the same in both outputs.
+    Context,
+
+    /// A line of the expected output.
+    Expected,
+
+    /// A line of the actual output.
+    Got,
+}
+
+impl Diff {
+    /// Compares an output with the expected one, returning `None` if they're
+    /// equal.
+    #[must_use]
+    pub fn between(expected: &str, got: &str) -> Option<Self> {
+        Self::with_context(expected, got, DEFAULT_CONTEXT)
+    }
+
+    /// Same as [`Diff::between()`], but showing `context` lines around the
+    /// first difference.
+    #[must_use]
+    pub fn with_context(expected: &str, got: &str, context: usize) -> Option<Self> {
+        if expected == got {
+            return None;
+        }
+
+        let expected_lines: Vec<_> = expected.split('\n').collect();
+        let got_lines: Vec<_> = got.split('\n').collect();
+
+        let first = expected_lines
+            .iter()
+            .zip(&got_lines)
+            .position(|(e, g)| e != g)
+            .unwrap_or_else(|| expected_lines.len().min(got_lines.len()));
+
+        let mut lines = Vec::new();
+        let line = |kind, index: usize, text: &str| DiffLine {
+            kind,
+            number: index + 1,
+            text: text.into(),
+        };
+
+        for (i, text) in expected_lines
+            .iter()
+            .enumerate()
+            .take(first)
+            .skip(first.saturating_sub(context))
+        {
+            lines.push(line(DiffLineKind::Context, i, text));
+        }
+        for (kind, output) in [
+            (DiffLineKind::Expected, &expected_lines),
+            (DiffLineKind::Got, &got_lines),
+        ] {
+            for (i, text) in output.iter().enumerate().skip(first).take(context + 1) {
+                lines.push(line(kind, i, text));
+            }
+        }
+
+        let whitespace_only = expected.split_whitespace().eq(got.split_whitespace());
+
+        Some(Self {
+            first_difference: first + 1,
+            lines,
+            whitespace_only,
+        })
+    }
+
+    /// Gets the number of the first line that differs, starting at 1.
+    #[must_use]
+    pub const fn first_difference(&self) -> usize {
+        self.first_difference
+    }
+
+    /// Gets the lines of the report: the context before the first difference,
+    /// followed by the expected lines and then the actual ones.
+    #[must_use]
+    pub fn lines(&self) -> &[DiffLine] {
+        &self.lines
+    }
+
+    /// Checks whether the outputs only differ in whitespace, which the judge
+    /// reports as a presentation error.
+    #[must_use]
+    pub const fn is_whitespace_only(&self) -> bool {
+        self.whitespace_only
+    }
+}
+
+impl Display for Diff {
+    /// Renders the report as plain text, with whitespace made visible.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "first difference at line {}", self.first_difference)?;
+
+        for line in &self.lines {
+            let marker = match line.kind {
+                DiffLineKind::Context => ' ',
+                DiffLineKind::Expected => '-',
+                DiffLineKind::Got => '+',
+            };
+            writeln!(
+                f,
+                "{marker} {:>4} | {}",
+                line.number,
+                visualize_whitespace(&line.text)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Makes the whitespace of a line visible: spaces are shown as `·`, tabs as
+/// `→`, carriage returns as `␍` and the end of the line as `¶`.
+#[must_use]
+pub fn visualize_whitespace(line: &str) -> String {
+    let mut visible: String = line
+        .chars()
+        .map(|c| match c {
+            ' ' => '·',
+            '\t' => '→',
+            '\r' => '␍',
+            c => c,
+        })
+        .collect();
+    visible.push('¶');
+    visible
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{visualize_whitespace, Diff, DiffLine, DiffLineKind};
+
+    fn line(kind: DiffLineKind, number: usize, text: &str) -> DiffLine {
+        DiffLine {
+            kind,
+            number,
+            text: text.into(),
+        }
+    }
+
+    #[test]
+    fn equal_outputs() {
+        assert_eq!(Diff::between("1\n2\n", "1\n2\n"), None);
+        assert_eq!(Diff::between("", ""), None);
+    }
+
+    #[test]
+    fn first_difference_with_context() {
+        let diff = Diff::with_context("1\n2\n3\n4\n5\n", "1\n2\n3\nx\n5\n", 1).unwrap();
+
+        assert_eq!(diff.first_difference(), 4);
+        assert!(!diff.is_whitespace_only());
+        assert_eq!(
+            diff.lines(),
+            [
+                line(DiffLineKind::Context, 3, "3"),
+                line(DiffLineKind::Expected, 4, "4"),
+                line(DiffLineKind::Expected, 5, "5"),
+                line(DiffLineKind::Got, 4, "x"),
+                line(DiffLineKind::Got, 5, "5"),
+            ]
+        );
+    }
+
+    #[test]
+    fn difference_in_the_first_line() {
+        let diff = Diff::with_context("a\n", "b\n", 3).unwrap();
+
+        assert_eq!(diff.first_difference(), 1);
+        assert_eq!(diff.lines()[0], line(DiffLineKind::Expected, 1, "a"));
+    }
+
+    #[test]
+    fn missing_and_extra_lines() {
+        let missing = Diff::with_context("1\n2\n", "1\n", 0).unwrap();
+        assert_eq!(missing.first_difference(), 2);
+        assert_eq!(
+            missing.lines(),
+            [
+                line(DiffLineKind::Expected, 2, "2"),
+                line(DiffLineKind::Got, 2, ""),
+            ]
+        );
+
+        let extra = Diff::with_context("1", "1\n2", 0).unwrap();
+        assert_eq!(extra.first_difference(), 2);
+        assert_eq!(extra.lines(), [line(DiffLineKind::Got, 2, "2")]);
+    }
+
+    #[test]
+    fn whitespace_only_differences() {
+        assert!(Diff::between("1 2\n", "1  2\n")
+            .unwrap()
+            .is_whitespace_only());
+        assert!(Diff::between("1\n", "1").unwrap().is_whitespace_only());
+        assert!(Diff::between("1\r\n", "1\n").unwrap().is_whitespace_only());
+        assert!(!Diff::between("1 2\n", "12\n").unwrap().is_whitespace_only());
+    }
+
+    #[test]
+    fn rendering() {
+        let diff = Diff::with_context("a\nb c\n", "a\nb\tc\n", 1).unwrap();
+        assert_eq!(
+            diff.to_string(),
+            "first difference at line 2\n\
+             \x20    1 | a¶\n\
+             -    2 | b·c¶\n\
+             -    3 | ¶\n\
+             +    2 | b→c¶\n\
+             +    3 | ¶\n"
+        );
+        assert_eq!(visualize_whitespace("\r"), "␍¶");
+    }
+}
@@ -7,9 +7,28 @@ pub type Result<T> = std::result::Result<T, Error>;
 #[derive(Error, Debug)]
 pub enum Error {
     /// Wraps arround errors in the [`ureq`] crate.
-    #[cfg(feature="dep:ureq")]
+    #[cfg(feature = "web-client")]
     #[error("ureq error: {0}")]
-    UreqError(#[from] ureq::Error),
+    UreqError(#[from] Box<ureq::Error>),
+
+    /// Wraps arround errors in the [`zip`] crate.
+    #[cfg(feature = "archive")]
+    #[error("zip error: {0}")]
+    ZipError(#[from] zip::result::ZipError),
+
+    /// Wraps arround errors in the [`sled`] crate.
+    #[cfg(feature = "sled")]
+    #[error("sled error: {0}")]
+    SledError(#[from] sled::Error),
+
+    /// Wraps arround errors in the [`rusqlite`] crate.
+    #[cfg(feature = "sqlite")]
+    #[error("SQLite error: {0}")]
+    SqliteError(#[from] rusqlite::Error),
+
+    /// Wraps arround I/O errors.
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
 
     /// Indicates an impossible conversion from a `char` to a
     /// [`ProblemType`](crate::ProblemType).
@@ -17,7 +36,8 @@ pub enum Error {
     NotAProblemType,
 
     /// Indicates an impossible conversion from a char array to a
-    /// [`ProblemLanguage`](crate::ProblemLanguage).
+    /// [`ProblemLanguage`](crate::ProblemLanguage), because it isn't made of
+    /// 2 lowercase ASCII letters.
     #[error("chars don't represent a valid problem language")]
     NotAProblemLanguage,
 
@@ -25,4 +45,176 @@ pub enum Error {
     /// because the given inputs don't constitute a valid problem id.
     #[error("invalid problem id: {0}")]
     InvalidProblemId(String),
+
+    /// Indicates an impossible conversion from a string to a
+    /// [`Verdict`](crate::Verdict).
+    #[error("string doesn't represent a valid verdict")]
+    NotAVerdict,
+
+    /// Indicates that a string doesn't represent a valid
+    /// [`SubmissionId`](crate::SubmissionId).
+    #[error("invalid submission id: {0}")]
+    InvalidSubmissionId(String),
+
+    /// Indicates that a string doesn't represent a valid
+    /// [`Timestamp`](crate::Timestamp), or that a time can't be represented
+    /// as one.
+    #[error("invalid timestamp: {0}")]
+    InvalidTimestamp(String),
+
+    /// Indicates that a string doesn't represent a valid
+    /// [`ExecutionTime`](crate::ExecutionTime) or
+    /// [`MemoryUsage`](crate::MemoryUsage).
+    #[error("invalid quantity: {0}")]
+    InvalidQuantity(String),
+
+    /// Indicates that a source didn't compile locally. Contains the output of
+    /// the compiler.
+    #[error("local compilation failed:\n{0}")]
+    LocalCompilationFailed(String),
+
+    /// Indicates that <https://jutge.org> rejected the given credentials.
+    #[error("login failed: wrong email or password")]
+    LoginFailed,
+
+    /// Indicates that the requested page is only available to authenticated
+    /// users, and the [`Client`](crate::Client) isn't logged in.
+    #[error("this operation requires being logged in")]
+    NotAuthenticated,
+
+    /// Indicates that the current password given to
+    /// [`Session::change_password()`](crate::Session::change_password) is wrong.
+    #[error("wrong current password")]
+    WrongPassword,
+
+    /// Indicates that <https://jutge.org> rejected a new password, e.g.
+    /// because it doesn't follow the password policy. Contains the reason
+    /// given by the site.
+    #[error("password rejected: {0}")]
+    PasswordRejected(String),
+
+    /// Indicates that <https://jutge.org> rejected the changes given to
+    /// [`Session::update_profile()`](crate::Session::update_profile). Contains
+    /// the reason given by the site.
+    #[error("profile update rejected: {0}")]
+    ProfileRejected(String),
+
+    /// Indicates that <https://jutge.org> rejected the image given to
+    /// [`Session::set_avatar()`](crate::Session::set_avatar), e.g. because of
+    /// its format or its size. Contains the reason given by the site.
+    #[error("avatar rejected: {0}")]
+    AvatarRejected(String),
+
+    /// Indicates that a [`SubmissionRequest`](crate::SubmissionRequest) was
+    /// sent without something it needs, such as its source. Contains what's
+    /// missing.
+    #[error("incomplete submission: {0}")]
+    IncompleteSubmission(String),
+
+    /// Indicates that <https://jutge.org> didn't accept a submission.
+    /// Contains the reason given by the site.
+    #[error("submission rejected: {0}")]
+    SubmissionRejected(String),
+
+    /// Indicates that <https://jutge.org> didn't accept a submission because
+    /// the user made too many of them lately. Submitting again before the
+    /// wait is over only extends the block.
+    #[error("too many submissions, wait before submitting again")]
+    SubmissionQuotaExceeded {
+        /// How long the site asked to wait before submitting again, if it
+        /// said so.
+        wait: Option<std::time::Duration>,
+    },
+
+    /// Indicates that <https://jutge.org> has blocked the account of the
+    /// user, so it can't log in nor submit.
+    #[error("the account is blocked")]
+    AccountBlocked,
+
+    /// Indicates that an operation is only available to instructors, and the
+    /// user of the [`Session`](crate::Session) isn't one (or can't manage the
+    /// given problem).
+    #[error("this operation requires being an instructor")]
+    NotAnInstructor,
+
+    /// Indicates that <https://jutge.org> didn't accept the package of a
+    /// problem. Contains the reason given by the site.
+    #[error("problem package rejected: {0}")]
+    PackageRejected(String),
+
+    /// Indicates that <https://jutge.org> didn't accept a change to an exam.
+    /// Contains the reason given by the site.
+    #[error("exam change rejected: {0}")]
+    ExamRejected(String),
+
+    /// Indicates that <https://jutge.org> kept throttling the requests of the
+    /// [`Client`](crate::Client), or asked to wait longer than allowed by
+    /// [`ClientBuilder::max_retry_after()`](crate::ClientBuilder::max_retry_after).
+    /// Contains how long the site asked to wait, if it said so.
+    #[error("too many requests, the server is throttling the client")]
+    Throttled(Option<std::time::Duration>),
+
+    /// Indicates that an operation needs the network, but the
+    /// [`Client`](crate::Client) is in
+    /// [offline mode](crate::ClientBuilder::offline) and the page isn't in its
+    /// disk cache.
+    #[error("not available offline")]
+    Offline,
+
+    /// Indicates that the proxy given to
+    /// [`ClientBuilder::proxy()`](crate::ClientBuilder::proxy) isn't valid.
+    #[error("invalid proxy: {0}")]
+    InvalidProxy(String),
+
+    /// Indicates that a response from <https://jutge.org> was bigger than
+    /// allowed by [`ClientBuilder::max_page_size()`](crate::ClientBuilder::max_page_size)
+    /// or [`ClientBuilder::max_download_size()`](crate::ClientBuilder::max_download_size),
+    /// so it was aborted. Contains the limit, in bytes.
+    #[error("response larger than {0} bytes")]
+    ResponseTooLarge(u64),
+
+    /// Indicates that a file downloaded from <https://jutge.org> was
+    /// truncated or corrupt, even after retrying the download. Contains what
+    /// was wrong with it.
+    #[error("corrupt download: {0}")]
+    CorruptDownload(String),
+
+    /// Indicates that a saved session couldn't be read or written.
+    #[error("invalid session: {0}")]
+    InvalidSession(String),
+
+    /// Indicates that a workspace [`Manifest`](crate::Manifest) couldn't be
+    /// parsed.
+    #[error("invalid manifest: {0}")]
+    InvalidManifest(String),
+
+    /// Indicates that a problem [`Package`](crate::Package) is malformed, or
+    /// that it isn't ready to be uploaded. Contains what's wrong with it.
+    #[error("invalid package: {0}")]
+    InvalidPackage(String),
+
+    /// Indicates that a record of a [`StateStore`](crate::StateStore), or
+    /// the file a store keeps its records in, is malformed. Contains what's
+    /// wrong with it.
+    #[error("invalid state: {0}")]
+    InvalidState(String),
+
+    /// Indicates that a page from <https://jutge.org> didn't have the
+    /// expected structure, so it couldn't be parsed.
+    #[error("unexpected layout in {page}: {hint}")]
+    UnexpectedPageLayout {
+        /// The path of the page in the site, such as `/problems/P68688_en`.
+        page: String,
+
+        /// What the scraper was looking for and couldn't find, such as
+        /// `no statement found`.
+        hint: String,
+    },
+}
+
+#[cfg(feature = "web-client")]
+impl From<ureq::Error> for Error {
+    fn from(err: ureq::Error) -> Self {
+        Self::UreqError(Box::new(err))
+    }
 }
@@ -0,0 +1,315 @@
+use crate::problem_id_types::Unlocalized;
+use crate::sans_io::{FetchExamSubmissions, Operation, Request, Response};
+use crate::scrape::{selector, text};
+use crate::{Error, ProblemId, Result, Timestamp, Verdict};
+
+/// A submission made during an exam, as seen by its instructors.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExamSubmission {
+    /// The student who made the submission.
+    pub student: String,
+
+    /// The problem of the exam the submission was sent to.
+    pub problem: ProblemId<Unlocalized>,
+
+    /// The verdict of the submission, or `None` if it hasn't been judged
+    /// yet.
+    pub verdict: Option<Verdict>,
+
+    /// The time the submission was made, if the site shows it.
+    pub submitted: Option<Timestamp>,
+}
+
+impl Operation for FetchExamSubmissions {
+    type Output = Vec<ExamSubmission>;
+
+    fn request(&self) -> Request {
+        Request::page(format!("/instructor/exams/{}/submissions", self.exam_id))
+    }
+
+    fn parse(&self, response: &Response<'_>) -> Result<Vec<ExamSubmission>> {
+        let html = response.html()?;
+        let table = html
+            .select(&selector("table"))
+            .next()
+            .ok_or_else(|| Error::unexpected_layout(self.request().path, "no submissions found"))?;
+
+        let mut submissions: Vec<_> = table
+            .select(&selector("tr"))
+            .filter_map(|row| {
+                let cells: Vec<_> = row.select(&selector("td")).map(text).collect();
+                let [student, problem, verdict, rest @ ..] = cells.as_slice() else {
+                    return None;
+                };
+
+                Some(ExamSubmission {
+                    student: student.clone(),
+                    problem: ProblemId::<Unlocalized>::parse_lenient(problem).ok()?,
+                    verdict: verdict.parse().ok().filter(Verdict::is_final),
+                    submitted: rest
+                        .iter()
+                        .find_map(|cell| Timestamp::find_in_listing(cell)),
+                })
+            })
+            .collect();
+
+        // The site lists the newest submissions first.
+        submissions.sort_by_key(|submission| submission.submitted);
+        Ok(submissions)
+    }
+}
+
+#[cfg(feature = "courses")]
+mod admin {
+    use std::collections::HashMap;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::ExamSubmission;
+    use crate::client::instructor_only;
+    use crate::form::Form;
+    use crate::problem_id_types::Unlocalized;
+    use crate::sans_io::FetchExamSubmissions;
+    use crate::scrape::error_alert;
+    use crate::{Error, ExamWindow, ProblemId, Result, Session, Timestamp};
+
+    impl Session {
+        /// Creates an exam in a course, returning its id.
+        ///
+        /// The exam starts without problems, students nor time window: set them
+        /// with [`Session::add_exam_problem()`],
+        /// [`Session::register_exam_students()`] and
+        /// [`Session::set_exam_window()`].
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAnInstructor`] if the user of the session isn't an
+        /// instructor of the course, or [`Error::ExamRejected`] if the site
+        /// doesn't create the exam.
+        pub fn create_exam(&self, course_id: &str, title: &str) -> Result<String> {
+            let client = self.client();
+            let page = format!("/instructor/courses/{course_id}/exams/new");
+            let mut form = self.exam_form(&page, "title")?;
+            form.set("title", title);
+
+            let response = client.submit_form(&form).map_err(instructor_only)?;
+            let prefix = client.url("/instructor/exams/");
+            if let Some(id) = response
+                .get_url()
+                .strip_prefix(&prefix)
+                .map(|rest| rest.trim_end_matches('/'))
+                .filter(|id| !id.is_empty() && !id.contains('/'))
+            {
+                return Ok(id.into());
+            }
+
+            match error_alert(&client.read_authenticated_html(response)?) {
+                Some(reason) => Err(Error::ExamRejected(reason)),
+                None => Err(Error::unexpected_layout(
+                    page,
+                    "creating the exam didn't lead to its page",
+                )),
+            }
+        }
+
+        /// Adds a problem to an exam.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAnInstructor`] if the user of the session can't
+        /// manage the exam, or [`Error::ExamRejected`] if the site doesn't add
+        /// the problem (e.g. because it doesn't exist).
+        pub fn add_exam_problem(
+            &self,
+            exam_id: &str,
+            problem: &ProblemId<Unlocalized>,
+        ) -> Result<()> {
+            self.update_exam(
+                exam_id,
+                "problems",
+                "problem",
+                &[("problem", problem.as_str())],
+            )
+        }
+
+        /// Sets the time window in which an exam can be taken.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAnInstructor`] if the user of the session can't
+        /// manage the exam, or [`Error::ExamRejected`] if the site doesn't
+        /// accept the window.
+        pub fn set_exam_window(&self, exam_id: &str, window: &ExamWindow) -> Result<()> {
+            self.update_exam(
+                exam_id,
+                "edit",
+                "start",
+                &[
+                    ("start", &form_time(window.start)),
+                    ("end", &form_time(window.end)),
+                ],
+            )
+        }
+
+        /// Sets the password students need to start an exam, or removes it if
+        /// `password` is `None`.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAnInstructor`] if the user of the session can't
+        /// manage the exam, or [`Error::ExamRejected`] if the site doesn't
+        /// accept the password.
+        pub fn set_exam_password(&self, exam_id: &str, password: Option<&str>) -> Result<()> {
+            self.update_exam(
+                exam_id,
+                "edit",
+                "password",
+                &[("password", password.unwrap_or_default())],
+            )
+        }
+
+        /// Registers students in an exam, given their emails.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAnInstructor`] if the user of the session can't
+        /// manage the exam, or [`Error::ExamRejected`] if the site doesn't
+        /// register the students (e.g. because an email is unknown).
+        pub fn register_exam_students(&self, exam_id: &str, emails: &[&str]) -> Result<()> {
+            self.update_exam(
+                exam_id,
+                "students",
+                "emails",
+                &[("emails", &emails.join("\n"))],
+            )
+        }
+
+        /// Fetches the submissions made so far in an exam, from the oldest to
+        /// the newest.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAnInstructor`] if the user of the session can't
+        /// manage the exam, or [`Error::UnexpectedPageLayout`] if the
+        /// submissions can't be found in the page.
+        pub fn exam_submissions(&self, exam_id: &str) -> Result<Vec<ExamSubmission>> {
+            self.client()
+                .execute(&FetchExamSubmissions {
+                    exam_id: exam_id.into(),
+                })
+                .map_err(instructor_only)
+        }
+
+        /// Follows the submissions of an exam while it runs.
+        ///
+        /// The returned iterator polls the submissions of the exam, and yields
+        /// each of them when it's made, and again when its verdict changes. It
+        /// never ends by itself, except after the first error.
+        #[must_use]
+        pub fn monitor_exam(&self, exam_id: &str) -> ExamMonitor<'_> {
+            ExamMonitor {
+                session: self,
+                exam_id: exam_id.into(),
+                interval: Duration::from_secs(5),
+                seen: HashMap::new(),
+                ready: Vec::new(),
+                polled: false,
+                done: false,
+            }
+        }
+
+        /// Fetches the form with the field `field` in the page `page` of an
+        /// exam, fills in `values` and submits it.
+        fn update_exam(
+            &self,
+            exam_id: &str,
+            page: &str,
+            field: &str,
+            values: &[(&str, &str)],
+        ) -> Result<()> {
+            let client = self.client();
+            let mut form = self.exam_form(&format!("/instructor/exams/{exam_id}/{page}"), field)?;
+            for (name, value) in values {
+                form.set(name, value);
+            }
+
+            let response = client.submit_form(&form).map_err(instructor_only)?;
+            match error_alert(&client.read_authenticated_html(response)?) {
+                None => Ok(()),
+                Some(reason) => Err(Error::ExamRejected(reason)),
+            }
+        }
+
+        /// Fetches a form of the instructor pages of an exam.
+        fn exam_form(&self, page: &str, field: &str) -> Result<Form> {
+            self.client()
+                .fetch_form(page, field)
+                .map_err(instructor_only)
+        }
+    }
+
+    /// An iterator over the new submissions of an exam.
+    ///
+    /// This is created by [`Session::monitor_exam()`].
+    #[derive(Debug)]
+    pub struct ExamMonitor<'a> {
+        session: &'a Session,
+        exam_id: String,
+        interval: Duration,
+        /// How many times each submission was listed in the last poll, as
+        /// the site doesn't tell apart identical submissions.
+        seen: HashMap<ExamSubmission, usize>,
+        ready: Vec<ExamSubmission>,
+        polled: bool,
+        done: bool,
+    }
+
+    impl ExamMonitor<'_> {
+        /// Sets the time to wait between polls. Defaults to 5 seconds.
+        #[must_use]
+        pub fn poll_interval(mut self, interval: Duration) -> Self {
+            self.interval = interval;
+            self
+        }
+    }
+
+    impl Iterator for ExamMonitor<'_> {
+        type Item = Result<ExamSubmission>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            while self.ready.is_empty() && !self.done {
+                if self.polled {
+                    thread::sleep(self.interval);
+                }
+                self.polled = true;
+
+                match self.session.exam_submissions(&self.exam_id) {
+                    Ok(submissions) => {
+                        // Only the newest copies of a submission beyond the
+                        // ones listed before are new.
+                        let mut counts = HashMap::new();
+                        for submission in submissions {
+                            let count = counts.entry(submission.clone()).or_insert(0);
+                            *count += 1;
+                            if *count > self.seen.get(&submission).copied().unwrap_or(0) {
+                                self.ready.push(submission);
+                            }
+                        }
+                        self.seen = counts;
+                        // Yielded from the end, so the oldest goes first.
+                        self.ready.reverse();
+                    }
+                    Err(err) => {
+                        self.done = true;
+                        return Some(Err(err));
+                    }
+                }
+            }
+
+            self.ready.pop().map(Ok)
+        }
+    }
+
+    /// Formats a time as expected by the forms of the site, in its timezone.
+    fn form_time(time: Timestamp) -> String {
+        let (year, month, day, hour, minute, _) = time.to_madrid();
+        format!("{year:04}-{month:02}-{day:02} {hour:02}:{minute:02}")
+    }
+}
+
+#[cfg(feature = "courses")]
+pub use admin::ExamMonitor;
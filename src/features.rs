@@ -0,0 +1,130 @@
+//! The optional capabilities of the crate, chosen with its Cargo features.
+
+use std::fmt::Display;
+
+/// The capabilities a build of the crate has, as returned by [`features()`].
+///
+/// Everything is enabled by default. Programs that only need some parts of
+/// the crate can disable the default features and pick the ones they use:
+///
+/// | Feature       | Capability                                                        |
+/// |---------------|-------------------------------------------------------------------|
+/// | (none)        | Ids, verdicts, timestamps and the other types of the site.        |
+/// | `web-client`  | Fetching and parsing the public pages of the site.                |
+/// | `client-auth` | Logging in, submitting and everything else done as a user.        |
+/// | `courses`     | The courses, lists and exams of a user, and the watchers of them. |
+/// | `runner`      | Running, checking and judging solutions locally.                  |
+/// | `archive`     | Reading and writing the zip archives of problem packages.         |
+/// | `cache`       | The disk cache and the offline mode of the `Client`.              |
+/// | `cli`         | The `jutge` command line tool.                                    |
+///
+/// For example, a tool that only parses pages it already has can depend on
+/// `jutge` with `default-features = false, features = ["web-client"]`, and
+/// use [`parse_problem_info()`](crate::parse_problem_info) and the rest of
+/// the parsers.
+///
+/// Each field tells whether the Cargo feature with the same name (with
+/// hyphens instead of underscores) is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(clippy::struct_excessive_bools)]
+#[non_exhaustive]
+pub struct Features {
+    /// Fetching and parsing the public pages of the site.
+    pub web_client: bool,
+
+    /// Logging in, and everything done as a logged-in user.
+    pub client_auth: bool,
+
+    /// The courses, lists and exams of the logged-in user.
+    pub courses: bool,
+
+    /// Running, checking and judging solutions locally.
+    pub runner: bool,
+
+    /// Reading and writing zip archives.
+    pub archive: bool,
+
+    /// The disk cache and the offline mode of the `Client`.
+    pub cache: bool,
+
+    /// The `jutge` command line tool.
+    pub cli: bool,
+
+    /// Connecting through SOCKS proxies.
+    pub socks_proxy: bool,
+
+    /// Serializing the entities of the site to JSON.
+    pub serde: bool,
+
+    /// Conversions from and to the types of the `chrono` crate.
+    pub chrono: bool,
+
+    /// Conversions from and to the types of the `time` crate.
+    pub time: bool,
+
+    /// State stores kept in `sled` databases.
+    pub sled: bool,
+
+    /// State stores kept in `SQLite` databases.
+    pub sqlite: bool,
+}
+
+impl Features {
+    /// Gets the names of the enabled features, such as `web-client`.
+    #[must_use]
+    pub fn names(&self) -> Vec<&'static str> {
+        [
+            (self.web_client, "web-client"),
+            (self.client_auth, "client-auth"),
+            (self.courses, "courses"),
+            (self.runner, "runner"),
+            (self.archive, "archive"),
+            (self.cache, "cache"),
+            (self.cli, "cli"),
+            (self.socks_proxy, "socks-proxy"),
+            (self.serde, "serde"),
+            (self.chrono, "chrono"),
+            (self.time, "time"),
+            (self.sled, "sled"),
+            (self.sqlite, "sqlite"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, name)| enabled.then_some(name))
+        .collect()
+    }
+}
+
+impl Display for Features {
+    /// Formats the names of the enabled features, separated by commas.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.names().join(", "))
+    }
+}
+
+/// Gets the features this build of the crate was compiled with.
+///
+/// # Example
+/// ```
+/// let features = jutge::features();
+/// if !features.client_auth {
+///     eprintln!("This build of jutge can't log in");
+/// }
+/// ```
+#[must_use]
+pub const fn features() -> Features {
+    Features {
+        web_client: cfg!(feature = "web-client"),
+        client_auth: cfg!(feature = "client-auth"),
+        courses: cfg!(feature = "courses"),
+        runner: cfg!(feature = "runner"),
+        archive: cfg!(feature = "archive"),
+        cache: cfg!(feature = "cache"),
+        cli: cfg!(feature = "cli"),
+        socks_proxy: cfg!(feature = "socks-proxy"),
+        serde: cfg!(feature = "serde"),
+        chrono: cfg!(feature = "chrono"),
+        time: cfg!(feature = "time"),
+        sled: cfg!(feature = "sled"),
+        sqlite: cfg!(feature = "sqlite"),
+    }
+}
@@ -0,0 +1,1157 @@
+//! Test support: a corpus of anonymized <https://jutge.org> pages and golden
+//! tests for the scrapers of the crate.
+//!
+//! The scrapers depend on the layout of the site, which can change at any
+//! time. This module bundles a copy of the pages they're expected to parse,
+//! a [`FixtureServer`] that serves them locally, and the [`GOLDEN_TESTS`]
+//! that check what the crate parses from each of them. Downstream crates can
+//! run the same tests (e.g. against the [`Client`] they configure), or serve
+//! their own fixtures.
+//!
+//! ```no_run
+//! use jutge::fixtures::{self, FixtureServer};
+//!
+//! let server = FixtureServer::start()?;
+//! assert_eq!(fixtures::check_all(&server.client()), Ok(()));
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! This module is only available with the `fixtures` feature.
+
+use std::fmt::Debug;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::precheck::ScratchDir;
+use crate::problem_id_types::{Localized, Unlocalized};
+use crate::{
+    Activity, Announcement, Attachment, Challenge, Change, Client, Compiler, CourseProgress,
+    CourseState, Error, Event, ExamSubmission, ExecutionTime, FeaturedProblem, HistoryEntry, Home,
+    ListState, MathMode, MemoryUsage, Mirror, MirrorOptions, News, ProblemId, ProblemInfo,
+    ProblemLanguage, ProfileUpdate, ProgressReport, PublicProblem, PublicProfile, Route, Score,
+    StatementNode, StudentProgress, Submission, SubmissionId, SubmissionStatus, TestCase,
+    Timestamp, UserProblemStatus, Verdict, WatchConfig, WatchState, Watcher,
+};
+
+/// A page served by a [`FixtureServer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fixture {
+    /// The path of the page in the site, including the query, such as
+    /// `/problems/public?page=1`.
+    pub path: &'static str,
+
+    /// The `Content-Type` of the page.
+    pub content_type: &'static str,
+
+    /// The contents of the page.
+    pub body: &'static [u8],
+}
+
+/// The pages bundled with the crate.
+pub const FIXTURES: &[Fixture] = &[
+    Fixture {
+        path: "/",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/home.html"),
+    },
+    Fixture {
+        path: "/challenges",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/challenges.html"),
+    },
+    Fixture {
+        path: "/courses/PRO1",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/course.html"),
+    },
+    Fixture {
+        path: "/courses/PRO1/L1",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/course_list.html"),
+    },
+    Fixture {
+        path: "/courses/PRO1/L2",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/course_list.html"),
+    },
+    Fixture {
+        path: "/courses/PRO1/L3",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/course_list.html"),
+    },
+    Fixture {
+        path: "/courses/PRO1/progress",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/course_progress.html"),
+    },
+    Fixture {
+        path: "/dashboard",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/dashboard.html"),
+    },
+    Fixture {
+        path: "/problems/data.csv",
+        content_type: "text/csv",
+        body: include_bytes!("../fixtures/data.csv"),
+    },
+    Fixture {
+        path: "/problems/P068688_en",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/problem.html"),
+    },
+    Fixture {
+        path: "/problems/P068688_en/zip",
+        content_type: "application/zip",
+        body: include_bytes!("../fixtures/samples.zip"),
+    },
+    Fixture {
+        path: "/problems/P068688_en/submissions",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/submissions.html"),
+    },
+    Fixture {
+        path: "/problems/P068688_en/submissions/S001",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/submission.html"),
+    },
+    Fixture {
+        path: "/problems/P068688_en/submissions/S003",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/submission_notes.html"),
+    },
+    Fixture {
+        path: "/problems/P068688_en/submissions/S002",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/submission_latest.html"),
+    },
+    Fixture {
+        path: "/instructor/exams/E1/submissions",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/exam_submissions.html"),
+    },
+    Fixture {
+        path: "/problems/P068688_en/submissions/S002/source",
+        content_type: "text/plain; charset=utf-8",
+        body: include_bytes!("../fixtures/submission_source.cc"),
+    },
+    // What the site shows in place of a page once the session has expired.
+    Fixture {
+        path: "/profile/edit",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/home.html"),
+    },
+    Fixture {
+        path: "/submissions",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/submission_history.html"),
+    },
+    Fixture {
+        path: "/users?search=alex",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/user_search.html"),
+    },
+    Fixture {
+        path: "/users/alexd",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/user_profile.html"),
+    },
+    Fixture {
+        path: "/problems/public?page=1",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/public_problems.html"),
+    },
+    Fixture {
+        path: "/problems/public?page=2",
+        content_type: "text/html; charset=utf-8",
+        body: include_bytes!("../fixtures/public_problems_end.html"),
+    },
+];
+
+/// A local HTTP server that serves a set of [`Fixture`]s, answering with a 404
+/// status to any other path.
+///
+/// The server runs in a background thread, and stops when it's dropped.
+#[derive(Debug)]
+pub struct FixtureServer {
+    addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FixtureServer {
+    /// Starts a server with the pages bundled with the crate.
+    ///
+    /// # Errors
+    /// Returns an error if no local port can be bound.
+    pub fn start() -> io::Result<Self> {
+        Self::serve(FIXTURES.to_vec())
+    }
+
+    /// Starts a server with the given pages.
+    ///
+    /// # Errors
+    /// Returns an error if no local port can be bound.
+    pub fn serve(fixtures: Vec<Fixture>) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let stop = Arc::clone(&stop);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        // A broken connection only affects its own request.
+                        let _ = respond(stream, &fixtures);
+                    }
+                }
+            })
+        };
+
+        Ok(Self {
+            addr,
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    /// Gets the base URL of the server, such as `http://127.0.0.1:1234`.
+    #[must_use]
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Creates a [`Client`] that uses the server as the site.
+    #[must_use]
+    pub fn client(&self) -> Client {
+        Client::builder().base_url(&self.url()).build()
+    }
+}
+
+impl Drop for FixtureServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // Wakes the server up, so it sees that it must stop.
+        let _ = TcpStream::connect(self.addr);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Answers a single request with the matching fixture.
+fn respond(stream: TcpStream, fixtures: &[Fixture]) -> io::Result<()> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim_end().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+
+    let mut stream = reader.into_inner();
+    let Some(fixture) = fixtures.iter().find(|fixture| fixture.path == path) else {
+        return stream.write_all(
+            b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        );
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        fixture.content_type,
+        fixture.body.len(),
+    )?;
+    if method != "HEAD" {
+        stream.write_all(fixture.body)?;
+    }
+    stream.flush()
+}
+
+/// A golden test: a check of what the crate parses from one of the
+/// [`FIXTURES`].
+#[derive(Debug, Clone, Copy)]
+pub struct GoldenTest {
+    /// The name of the test.
+    pub name: &'static str,
+
+    check: fn(&Client) -> Result<(), String>,
+}
+
+impl GoldenTest {
+    /// Runs the test with a `Client` that uses a [`FixtureServer`] serving
+    /// the bundled pages as the site.
+    ///
+    /// # Errors
+    /// Returns a description of the mismatch if the crate doesn't parse the
+    /// expected values, or of the error if it fails to parse them.
+    pub fn run(&self, client: &Client) -> Result<(), String> {
+        (self.check)(client)
+    }
+}
+
+/// The golden tests of the bundled pages.
+pub const GOLDEN_TESTS: &[GoldenTest] = &[
+    GoldenTest {
+        name: "statement",
+        check: |client| {
+            let statement = client
+                .statement(&problem())
+                .map_err(|err| err.to_string())?;
+            expect(
+                "statement nodes",
+                &statement.nodes(MathMode::Tex),
+                &expected_statement_nodes(),
+            )?;
+
+            let sanitized = statement.sanitized_html();
+            if sanitized.contains("<script") || sanitized.contains("onclick") {
+                return Err(format!("unsafe sanitized statement: {sanitized}"));
+            }
+            if !sanitized.contains(&format!("src=\"{}/problems/figure.png\"", client.url(""))) {
+                return Err(format!("image not made absolute: {sanitized}"));
+            }
+            Ok(())
+        },
+    },
+    GoldenTest {
+        name: "attachments",
+        check: |client| {
+            let statement = client
+                .statement(&problem())
+                .map_err(|err| err.to_string())?;
+            let attachments = statement.attachments();
+            expect("attachments", &attachments, &expected_attachments())?;
+
+            let data = client
+                .download_attachment(&attachments[1])
+                .map_err(|err| err.to_string())?;
+            expect(
+                "attachment",
+                &data.as_slice(),
+                &b"a,b\n1,2\n3,4\n".as_slice(),
+            )
+        },
+    },
+    GoldenTest {
+        name: "problem_info",
+        check: |client| {
+            let info = client
+                .problem_info(&problem())
+                .map_err(|err| err.to_string())?;
+            expect("problem info", &info, &expected_problem_info())
+        },
+    },
+    GoldenTest {
+        name: "parse_only",
+        check: |_| {
+            let page = |file: &str| {
+                FIXTURES
+                    .iter()
+                    .find(|fixture| fixture.path == file)
+                    .map(|fixture| String::from_utf8_lossy(fixture.body).into_owned())
+                    .ok_or_else(|| format!("missing fixture {file}"))
+            };
+
+            let info = crate::parse_problem_info(&problem(), &page("/problems/P068688_en")?)
+                .map_err(|err| err.to_string())?;
+            expect("problem info", &info, &expected_problem_info())?;
+
+            let problems = crate::parse_public_problems(1, &page("/problems/public?page=1")?)
+                .map_err(|err| err.to_string())?;
+            expect("public problems", &problems, &expected_public_problems())
+        },
+    },
+    GoldenTest {
+        name: "home",
+        check: |client| {
+            let home = client.home().map_err(|err| err.to_string())?;
+            expect("home", &home, &expected_home())
+        },
+    },
+    GoldenTest {
+        name: "challenges",
+        check: |client| {
+            let challenges = client.current_challenges().map_err(|err| err.to_string())?;
+            let challenge = |name: &str, id: &str, deadline| Challenge {
+                name: name.into(),
+                problem: id.parse().expect("Fixture problem id should be valid"),
+                deadline,
+            };
+            let expected = vec![
+                challenge(
+                    "Problem of the day",
+                    "P068688",
+                    Timestamp::from_madrid(2099, 1, 1, 0, 0, 0),
+                ),
+                challenge("Problem of the week", "X012345", None),
+            ];
+            expect("challenges", &challenges, &expected)
+        },
+    },
+    GoldenTest {
+        name: "samples",
+        check: |client| {
+            let samples = client.samples(&problem()).map_err(|err| err.to_string())?;
+            expect("samples", &samples, &expected_samples())
+        },
+    },
+    GoldenTest {
+        name: "problem_exists",
+        check: |client| {
+            let exists = client
+                .problem_exists(&problem())
+                .map_err(|err| err.to_string())?;
+            expect("existence of the problem", &exists, &true)
+        },
+    },
+    GoldenTest {
+        name: "submission",
+        check: |client| {
+            let submission = client
+                .resume()
+                .and_then(|session| session.submission(&SubmissionId::new(problem(), 1)))
+                .map_err(|err| err.to_string())?;
+            expect("submission", &submission, &expected_submission())
+        },
+    },
+    GoldenTest {
+        name: "submission_notes",
+        check: |client| {
+            let submission = client
+                .resume()
+                .and_then(|session| session.submission(&SubmissionId::new(problem(), 3)))
+                .map_err(|err| err.to_string())?;
+            let expected = Submission {
+                id: SubmissionId::new(problem(), 3),
+                status: SubmissionStatus::Judged(Verdict::WrongAnswer),
+                score: Some(Score {
+                    awarded: 50.0,
+                    total: 100.0,
+                }),
+                feedback: Some("Passes 3/4 of the public test cases.".into()),
+                submitted: Timestamp::from_madrid(2024, 4, 2, 10, 15, 0),
+                compiler: None,
+                time: None,
+                memory: None,
+            };
+            expect("submission", &submission, &expected)
+        },
+    },
+    GoldenTest {
+        name: "submissions",
+        check: |client| {
+            let submissions = client
+                .resume()
+                .and_then(|session| session.submissions(&problem()))
+                .map_err(|err| err.to_string())?;
+            let expected = vec![
+                SubmissionId::new(problem(), 1),
+                SubmissionId::new(problem(), 2),
+            ];
+            expect("submissions", &submissions, &expected)
+        },
+    },
+    GoldenTest {
+        name: "already_accepted",
+        check: |client| {
+            let accepted = client
+                .resume()
+                .and_then(|session| session.already_accepted(&problem()))
+                .map_err(|err| err.to_string())?;
+            expect("already accepted", &accepted, &true)
+        },
+    },
+    GoldenTest {
+        name: "duplicate_of_latest",
+        check: |client| {
+            let session = client.resume().map_err(|err| err.to_string())?;
+            let duplicate = |source, compiler| {
+                session
+                    .duplicate_of_latest(&problem(), source, &compiler)
+                    .map_err(|err| err.to_string())
+            };
+
+            // Only line endings and trailing whitespace differ.
+            let same =
+                "#include <iostream>\nusing namespace std;\n\nint main() {\n    int x, y;  \n    \
+                        cin >> x >> y;\n    cout << x + y << endl;\n}\n\n";
+            expect(
+                "duplicate",
+                &duplicate(same, Compiler::Gxx17)?,
+                &Some(SubmissionId::new(problem(), 2)),
+            )?;
+            expect("other compiler", &duplicate(same, Compiler::Gxx11)?, &None)?;
+            expect(
+                "other source",
+                &duplicate("int main() {}\n", Compiler::Gxx17)?,
+                &None,
+            )
+        },
+    },
+    GoldenTest {
+        name: "expired_session",
+        check: |client| {
+            let update = client
+                .resume()
+                .and_then(|session| session.update_profile(&ProfileUpdate::default()));
+            expect(
+                "rejection of an expired session",
+                &matches!(update, Err(Error::NotAuthenticated)),
+                &true,
+            )
+        },
+    },
+    GoldenTest {
+        name: "announcements",
+        check: |client| {
+            let announcements = client
+                .resume()
+                .and_then(|session| session.announcements())
+                .map_err(|err| err.to_string())?;
+            let expected = vec![Announcement {
+                title: Some("Exam on Friday".into()),
+                body: "The lab exam starts at 10:00 in room A5.".into(),
+            }];
+            expect("announcements", &announcements, &expected)
+        },
+    },
+    GoldenTest {
+        name: "exam_monitor",
+        check: |client| {
+            let session = client.resume().map_err(|err| err.to_string())?;
+            let problem =
+                ProblemId::<Unlocalized>::parse_lenient("P68688").map_err(|err| err.to_string())?;
+            let submission = |student: &str, verdict, minute| ExamSubmission {
+                student: student.into(),
+                problem: problem.clone(),
+                verdict,
+                submitted: Timestamp::from_madrid(2024, 3, 5, 10, minute, 0),
+            };
+
+            // Both identical submissions are new.
+            let submissions = session
+                .monitor_exam("E1")
+                .take(3)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| err.to_string())?;
+            let wrong_answer = submission("Alex Doe", Some(Verdict::WrongAnswer), 20);
+            expect(
+                "submissions",
+                &submissions,
+                &vec![
+                    submission("Kim Poe", None, 15),
+                    wrong_answer.clone(),
+                    wrong_answer,
+                ],
+            )
+        },
+    },
+    GoldenTest {
+        name: "progress_report",
+        check: |client| {
+            let session = client.resume().map_err(|err| err.to_string())?;
+            let history = session
+                .submission_history()
+                .map_err(|err| err.to_string())?;
+            expect("submission history entries", &history.len(), &5)?;
+            expect(
+                "latest submission",
+                &history[0],
+                &HistoryEntry {
+                    problem: "X012345".parse().map_err(|_| "invalid id")?,
+                    verdict: Verdict::Accepted,
+                    submitted: Timestamp::from_madrid(2024, 3, 12, 18, 5, 0),
+                    category: Some("Loops".into()),
+                },
+            )?;
+
+            let courses = session.enrolled_courses().map_err(|err| err.to_string())?;
+            let names: Vec<_> = courses
+                .iter()
+                .map(|c| (c.course_id.as_str(), c.problems.len()))
+                .collect();
+            expect("enrolled courses", &names, &vec![("PRO1", 3)])?;
+
+            let generated = Timestamp::from_unix(0).ok_or("invalid timestamp")?;
+            let mut csv = Vec::new();
+            ProgressReport::new(&history, &courses, generated)
+                .to_csv(&mut csv)
+                .map_err(|err| err.to_string())?;
+            expect(
+                "progress report CSV",
+                &String::from_utf8_lossy(&csv).into_owned(),
+                &"group,key,solved,attempted,total\r\n\
+                  week,2024-03-04,1,2,\r\n\
+                  week,2024-03-11,1,1,\r\n\
+                  category,Introduction,1,1,\r\n\
+                  category,Loops,1,1,\r\n\
+                  course,PRO1,2,3,3\r\n"
+                    .to_owned(),
+            )
+        },
+    },
+    GoldenTest {
+        name: "raw_page",
+        check: |client| {
+            let raw = |route| client.get_raw(&route).map_err(|err| err.to_string());
+
+            let dashboard = raw(Route::Dashboard)?;
+            expect("status", &dashboard.status, &200)?;
+            expect(
+                "content type",
+                &dashboard.header("content-type"),
+                &Some("text/html; charset=utf-8"),
+            )?;
+            expect(
+                "body",
+                &dashboard.text().contains("<h1>Dashboard</h1>"),
+                &true,
+            )?;
+
+            let missing = raw(Route::Other("/missing".into()))?;
+            expect("status", &missing.status, &404)
+        },
+    },
+    GoldenTest {
+        name: "users",
+        check: |client| {
+            let users = client.find_user("alex").map_err(|err| err.to_string())?;
+            let usernames: Vec<_> = users.iter().map(|user| user.username.as_str()).collect();
+            expect("found users", &usernames, &vec!["alexd", "alexandra"])?;
+
+            let profile = client
+                .user_profile("alexd")
+                .map_err(|err| err.to_string())?;
+            let expected = PublicProfile {
+                username: "alexd".into(),
+                name: "Alex Doe".into(),
+                institution: Some("Universitat Politècnica de Catalunya".into()),
+                country: Some("Spain".into()),
+                webpage: None,
+                description: None,
+                accepted: Some(42),
+            };
+            expect("profile", &profile, &Some(expected))?;
+
+            let missing = client
+                .user_profile("nobody")
+                .map_err(|err| err.to_string())?;
+            expect("missing profile", &missing, &None)
+        },
+    },
+    GoldenTest {
+        name: "course_lists",
+        check: |client| {
+            let lists = client
+                .resume()
+                .and_then(|session| session.course_lists("PRO1"))
+                .map_err(|err| err.to_string())?;
+            expect(
+                "course lists",
+                &lists,
+                &vec!["L1".into(), "L2".into(), "L3".into()],
+            )
+        },
+    },
+    GoldenTest {
+        name: "notifier",
+        check: |client| {
+            let session = client.resume().map_err(|err| err.to_string())?;
+            let id = SubmissionId::new(problem(), 1);
+            let mut seen = Vec::new();
+            let mut notifier = session
+                .notifier()
+                .watch_submission(&id)
+                .on_event(|event| seen.push(event.to_json("https://jutge.org")));
+            let events = notifier.poll().map_err(|err| err.to_string())?;
+            expect(
+                "events",
+                &events,
+                &vec![Event::VerdictFinalized {
+                    submission: id,
+                    verdict: Verdict::Incomplete,
+                }],
+            )?;
+            expect("idle", &notifier.is_idle(), &true)?;
+            drop(notifier);
+            expect(
+                "reported events",
+                &seen,
+                &vec![
+                    "{\"event\":\"verdict_finalized\",\"submission\":\"P068688_en/S001\",\
+                     \"verdict\":\"IC\"}"
+                        .to_owned(),
+                ],
+            )
+        },
+    },
+    GoldenTest {
+        name: "notifier_failed_fetch",
+        check: |client| {
+            let session = client.resume().map_err(|err| err.to_string())?;
+            let id = SubmissionId::new(problem(), 1);
+            let mut seen = Vec::new();
+            let mut notifier = session
+                .notifier()
+                .watch_submission(&id)
+                .watch_submission(&SubmissionId::new(problem(), 9))
+                .on_event(|event| seen.push(event.clone()));
+            expect("failed poll", &notifier.poll().is_err(), &true)?;
+            expect("idle", &notifier.is_idle(), &false)?;
+            expect("failed poll again", &notifier.poll().is_err(), &true)?;
+            drop(notifier);
+            expect(
+                "reported events",
+                &seen,
+                &vec![Event::VerdictFinalized {
+                    submission: id,
+                    verdict: Verdict::Incomplete,
+                }],
+            )
+        },
+    },
+    GoldenTest {
+        name: "list_problems",
+        check: |client| {
+            let entries = client
+                .resume()
+                .and_then(|session| session.list_problems("PRO1", "L1"))
+                .map_err(|err| err.to_string())?;
+            let entries: Vec<_> = entries
+                .iter()
+                .map(|entry| (entry.problem.as_str(), entry.title.as_str(), entry.status))
+                .collect();
+            expect(
+                "list problems",
+                &entries,
+                &vec![
+                    ("P068688", "Hello world!", UserProblemStatus::Accepted),
+                    ("X012345", "Sum of digits", UserProblemStatus::Rejected),
+                    ("P001006", "Maximum", UserProblemStatus::Attempted),
+                    ("P002345", "Minimum", UserProblemStatus::NotTried),
+                ],
+            )
+        },
+    },
+    GoldenTest {
+        name: "watcher",
+        check: |client| {
+            let session = client.resume().map_err(|err| err.to_string())?;
+            let mut course = CourseState::default();
+            course.lists.insert("L0".into(), ListState::default());
+            course.lists.insert("L1".into(), ListState::default());
+            let mut state = WatchState::default();
+            state.courses.insert("PRO1".into(), course);
+
+            let watcher = Watcher::start(
+                session,
+                WatchConfig::new()
+                    .course("PRO1")
+                    .poll_interval(Duration::from_hours(1))
+                    .initial_state(state),
+            );
+            let changes: Vec<_> = watcher
+                .events()
+                .iter()
+                .take(5)
+                .collect::<Result<_, _>>()
+                .map_err(|err| err.to_string())?;
+
+            // The fixture lists all share the same page.
+            let synced = ListState {
+                deadline: Timestamp::from_madrid(2024, 3, 8, 23, 59, 0),
+                score: Some(Score {
+                    awarded: 7.0,
+                    total: 10.0,
+                }),
+            };
+            let expected = vec![
+                Change::DeadlineChanged {
+                    course_id: "PRO1".into(),
+                    list_id: "L1".into(),
+                    old: None,
+                    new: synced.deadline,
+                },
+                Change::ScoreChanged {
+                    course_id: "PRO1".into(),
+                    list_id: "L1".into(),
+                    old: None,
+                    new: synced.score,
+                },
+                Change::ListAdded {
+                    course_id: "PRO1".into(),
+                    list_id: "L2".into(),
+                    state: synced,
+                },
+                Change::ListAdded {
+                    course_id: "PRO1".into(),
+                    list_id: "L3".into(),
+                    state: synced,
+                },
+                Change::ListRemoved {
+                    course_id: "PRO1".into(),
+                    list_id: "L0".into(),
+                },
+            ];
+            expect("changes", &changes, &expected)?;
+
+            let lists: Vec<_> = watcher.state().courses["PRO1"]
+                .lists
+                .keys()
+                .cloned()
+                .collect();
+            expect(
+                "synced lists",
+                &lists,
+                &vec!["L1".into(), "L2".into(), "L3".into()],
+            )
+        },
+    },
+    GoldenTest {
+        name: "course_progress",
+        check: |client| {
+            let progress = client
+                .resume()
+                .and_then(|session| session.course_progress("PRO1"))
+                .map_err(|err| err.to_string())?;
+            expect("course progress", &progress, &expected_course_progress())?;
+
+            let mut csv = Vec::new();
+            progress.to_csv(&mut csv).map_err(|err| err.to_string())?;
+            expect(
+                "course progress CSV",
+                &String::from_utf8_lossy(&csv).into_owned(),
+                &"name,email,P068688,X012345,accepted,total\r\n\
+                  Alex Doe,alex@example.com,AC,WA,1,2\r\n\
+                  \"Sam Roe, Jr.\",,AC,AC,2,2\r\n\
+                  Kim Poe,kim@example.com,,,0,2\r\n"
+                    .to_owned(),
+            )
+        },
+    },
+    GoldenTest {
+        name: "public_problems",
+        check: |client| {
+            let problems = client
+                .all_public_problems()
+                .collect::<crate::Result<Vec<_>>>()
+                .map_err(|err| err.to_string())?;
+            expect("public problems", &problems, &expected_public_problems())
+        },
+    },
+    GoldenTest {
+        name: "public_problems_pipeline",
+        check: |client| {
+            let mut problems = Vec::new();
+            client
+                .for_each_public_problem(
+                    2,
+                    |_, problem| Ok(problem.title.clone()),
+                    |problem, title| {
+                        problems.push((problem.id, title.ok()));
+                        ControlFlow::Continue(())
+                    },
+                )
+                .map_err(|err| err.to_string())?;
+            problems.sort_by_key(|(id, _)| id.to_string());
+
+            let mut expected: Vec<_> = expected_public_problems()
+                .into_iter()
+                .map(|problem| (problem.id, Some(problem.title)))
+                .collect();
+            expected.sort_by_key(|(id, _)| id.to_string());
+            expect("public problems", &problems, &expected)
+        },
+    },
+    GoldenTest {
+        name: "mirror",
+        check: |client| {
+            let dir = ScratchDir::new().map_err(|err| err.to_string())?;
+            let options = MirrorOptions::new(dir.path()).languages(&[ProblemLanguage::English]);
+
+            let mirror =
+                Mirror::public_problems(client, &options).map_err(|err| err.to_string())?;
+            expect("mirror completeness", &mirror.is_complete(), &true)?;
+            expect(
+                "updated problems",
+                &mirror.updated(),
+                &[problem()].as_slice(),
+            )?;
+
+            let read = |path: &str| {
+                std::fs::read_to_string(dir.path().join(path)).map_err(|err| err.to_string())
+            };
+            expect(
+                "mirror index",
+                &read(Mirror::INDEX_FILE)?,
+                &"P068688\tSum of two numbers\ten\n".to_owned(),
+            )?;
+            let samples = expected_samples();
+            expect(
+                "mirrored sample",
+                &read("P068688_en/sample-1.inp")?,
+                &samples[0].input,
+            )?;
+
+            let again = Mirror::public_problems(client, &options).map_err(|err| err.to_string())?;
+            expect("updated problems", &again.updated(), &[].as_slice())
+        },
+    },
+];
+
+/// Runs all the [`GOLDEN_TESTS`] with a `Client` that uses a
+/// [`FixtureServer`] serving the bundled pages as the site.
+///
+/// # Errors
+/// Returns the failures of all the tests that didn't pass, one per line.
+pub fn check_all(client: &Client) -> Result<(), String> {
+    let failures: Vec<_> = GOLDEN_TESTS
+        .iter()
+        .filter_map(|test| {
+            test.run(client)
+                .err()
+                .map(|err| format!("{}: {err}", test.name))
+        })
+        .collect();
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("\n"))
+    }
+}
+
+/// Gets the problem of the bundled pages.
+///
+/// # Panics
+/// Never panics, as the id is valid.
+#[must_use]
+pub fn problem() -> ProblemId<Localized> {
+    "P068688_en"
+        .parse()
+        .expect("Fixture problem id should be valid")
+}
+
+/// Gets the nodes of the bundled statement, as returned by
+/// [`Statement::nodes()`](crate::Statement::nodes) with [`MathMode::Tex`].
+#[must_use]
+pub fn expected_statement_nodes() -> Vec<StatementNode> {
+    let text = |text: &str| StatementNode::Text(text.into());
+    let math = |tex: &str| StatementNode::InlineMath(tex.into());
+
+    vec![
+        text("Write a program that reads two numbers "),
+        math("a"),
+        text(" and "),
+        math("b"),
+        text(" and prints "),
+        math("a + b"),
+        text("."),
+        StatementNode::Break,
+        text("The answer must satisfy "),
+        StatementNode::BlockMath("0 \\le a + b \\le 10^9".into()),
+        StatementNode::Break,
+        text("Input"),
+        StatementNode::Break,
+        text("The input consists of two integers separated by a space."),
+        StatementNode::Break,
+        text("Catalan version"),
+        StatementNode::Break,
+        text("Data set"),
+    ]
+}
+
+/// Gets the files attached to the statement of the bundled problem.
+#[must_use]
+pub fn expected_attachments() -> Vec<Attachment> {
+    let attachment = |name: &str, mime_type: &str| Attachment {
+        name: name.into(),
+        path: format!("/problems/{name}"),
+        mime_type: mime_type.into(),
+    };
+
+    vec![
+        attachment("figure.png", "image/png"),
+        attachment("data.csv", "text/csv"),
+        attachment("tune.ogg", "audio/ogg"),
+    ]
+}
+
+/// Gets the general information about the bundled problem.
+#[must_use]
+pub fn expected_problem_info() -> ProblemInfo {
+    ProblemInfo {
+        id: problem(),
+        title: "Sum of two numbers".into(),
+        limits: Some("Time limit: 1 second. Memory limit: 256 MiB.".into()),
+    }
+}
+
+/// Gets the contents of the bundled landing page.
+///
+/// # Panics
+/// Never panics, as the ids are valid.
+#[must_use]
+pub fn expected_home() -> Home {
+    let id = |id: &str| -> ProblemId<Unlocalized> {
+        id.parse().expect("Fixture problem id should be valid")
+    };
+
+    Home {
+        featured: vec![
+            FeaturedProblem {
+                id: id("P068688"),
+                title: "Sum of two numbers".into(),
+            },
+            FeaturedProblem {
+                id: id("X012345"),
+                title: "Suma de matrices".into(),
+            },
+        ],
+        news: vec![News {
+            date: Timestamp::from_madrid(2024, 2, 1, 0, 0, 0),
+            title: "New compilers".into(),
+            body: "Rust and Go are now available.".into(),
+        }],
+        activity: vec![
+            Activity {
+                user: "alex".into(),
+                problem: id("P068688"),
+                verdict: Some(Verdict::Accepted),
+                time: Timestamp::from_madrid(2024, 2, 2, 10, 15, 0),
+            },
+            Activity {
+                user: "sam".into(),
+                problem: id("X012345"),
+                verdict: None,
+                time: Timestamp::from_madrid(2024, 2, 2, 10, 14, 30),
+            },
+        ],
+    }
+}
+
+/// Gets the sample test cases of the bundled problem.
+#[must_use]
+pub fn expected_samples() -> Vec<TestCase> {
+    vec![
+        TestCase {
+            name: "sample-1".into(),
+            input: "2 3\n".into(),
+            expected: "5\n".into(),
+        },
+        TestCase {
+            name: "sample-2".into(),
+            input: "-1 1\n".into(),
+            expected: "0\n".into(),
+        },
+    ]
+}
+
+/// Gets the bundled submission.
+#[must_use]
+pub fn expected_submission() -> Submission {
+    Submission {
+        id: SubmissionId::new(problem(), 1),
+        status: SubmissionStatus::Judged(Verdict::Incomplete),
+        score: Some(Score {
+            awarded: 75.0,
+            total: 100.0,
+        }),
+        feedback: Some("Wrong answer on the large test cases.".into()),
+        // The clocks went forward at 02:00 that day.
+        submitted: Timestamp::from_madrid(2024, 3, 31, 3, 30, 0),
+        compiler: Some(Compiler::Gxx17),
+        time: Some(ExecutionTime::new(Duration::from_millis(123))),
+        memory: Some(MemoryUsage::new(3_200_000)),
+    }
+}
+
+/// Gets the bundled progress of the students of the `PRO1` course.
+///
+/// # Panics
+/// Never panics, as the ids are valid.
+#[must_use]
+pub fn expected_course_progress() -> CourseProgress {
+    let student =
+        |name: &str, email: Option<&str>, verdicts: Vec<Option<Verdict>>| StudentProgress {
+            name: name.into(),
+            email: email.map(Into::into),
+            verdicts,
+        };
+
+    CourseProgress {
+        course_id: "PRO1".into(),
+        problems: vec![
+            "P068688"
+                .parse()
+                .expect("Fixture problem id should be valid"),
+            "X012345"
+                .parse()
+                .expect("Fixture problem id should be valid"),
+        ],
+        students: vec![
+            student(
+                "Alex Doe",
+                Some("alex@example.com"),
+                vec![Some(Verdict::Accepted), Some(Verdict::WrongAnswer)],
+            ),
+            student(
+                "Sam Roe, Jr.",
+                None,
+                vec![Some(Verdict::Accepted), Some(Verdict::Accepted)],
+            ),
+            student("Kim Poe", Some("kim@example.com"), vec![None, None]),
+        ],
+    }
+}
+
+/// Gets the problems listed in the bundled public problem index.
+///
+/// # Panics
+/// Never panics, as the ids are valid.
+#[must_use]
+pub fn expected_public_problems() -> Vec<PublicProblem> {
+    vec![
+        PublicProblem {
+            id: "P068688"
+                .parse()
+                .expect("Fixture problem id should be valid"),
+            title: "Sum of two numbers".into(),
+            languages: vec![ProblemLanguage::English, ProblemLanguage::Catalan],
+        },
+        PublicProblem {
+            id: "X012345"
+                .parse()
+                .expect("Fixture problem id should be valid"),
+            title: "Suma de matrices".into(),
+            languages: vec![ProblemLanguage::Spanish],
+        },
+    ]
+}
+
+/// Compares a parsed value with the expected one.
+fn expect<T: PartialEq + Debug>(what: &str, actual: &T, expected: &T) -> Result<(), String> {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "unexpected {what}: expected {expected:?}, got {actual:?}"
+        ))
+    }
+}
@@ -0,0 +1,292 @@
+//! Handling of the HTML forms of <https://jutge.org>.
+//!
+//! Most forms in the site embed hidden fields (such as CSRF tokens) that must
+//! be sent back when submitting them. A [`Form`] is scraped from the page
+//! that contains it, so it carries all these fields, and callers only need
+//! to fill in the fields they care about.
+
+use scraper::{ElementRef, Html};
+use ureq::Response;
+
+use crate::multipart::Multipart;
+use crate::scrape::{selector, text};
+use crate::{Client, Error, Result};
+
+/// A form scraped from a page of the site.
+#[derive(Debug, Clone)]
+pub(crate) struct Form {
+    action: String,
+    multipart: bool,
+    fields: Vec<(String, String)>,
+}
+
+impl Form {
+    /// Scrapes the first form in `html` that has a field named `field`.
+    ///
+    /// `page` is the path of the page the form comes from, used to resolve
+    /// relative actions, and `site` is the base URL of the site, such as
+    /// `https://jutge.org`. Forms sent to other sites are rejected.
+    pub(crate) fn containing(html: &Html, page: &str, field: &str, site: &str) -> Result<Self> {
+        let field_selector = selector(&format!("[name=\"{field}\"]"));
+
+        let form = html
+            .select(&selector("form"))
+            .find(|form| form.select(&field_selector).next().is_some())
+            .ok_or_else(|| {
+                Error::unexpected_layout(page, format!("no form with a `{field}` field"))
+            })?;
+
+        Self::from_element(form, page, site)
+    }
+
+    fn from_element(form: ElementRef<'_>, page: &str, site: &str) -> Result<Self> {
+        let attrs = form.value();
+        let action = resolve_action(attrs.attr("action").unwrap_or_default(), page, site)?;
+        let multipart = attrs
+            .attr("enctype")
+            .is_some_and(|enctype| enctype.eq_ignore_ascii_case("multipart/form-data"));
+
+        let mut fields = Vec::new();
+        for element in form.select(&selector("input[name], textarea[name], select[name]")) {
+            let attrs = element.value();
+            let name = attrs.attr("name").unwrap_or_default().to_owned();
+
+            let value = match attrs.name() {
+                // Unlike other text, whitespace in a textarea is part of its
+                // value.
+                "textarea" => Some(element.text().collect()),
+                "select" => select_value(element),
+                _ => match attrs.attr("type").unwrap_or("text") {
+                    "submit" | "button" | "image" | "reset" | "file" => None,
+                    "checkbox" | "radio" if attrs.attr("checked").is_none() => None,
+                    _ => Some(attrs.attr("value").unwrap_or_default().into()),
+                },
+            };
+
+            if let Some(value) = value {
+                fields.push((name, value));
+            }
+        }
+
+        Ok(Self {
+            action,
+            multipart,
+            fields,
+        })
+    }
+
+    /// Sets the value of a field, adding it if it isn't already in the form.
+    pub(crate) fn set(&mut self, name: &str, value: &str) -> &mut Self {
+        match self.fields.iter_mut().find(|(field, _)| field == name) {
+            Some((_, old)) => value.clone_into(old),
+            None => self.fields.push((name.into(), value.into())),
+        }
+        self
+    }
+
+    fn encoded_fields(&self) -> Vec<(&str, &str)> {
+        self.fields
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect()
+    }
+}
+
+/// A file to upload with a [`Form`].
+pub(crate) struct FormFile<'a> {
+    pub(crate) field: &'a str,
+    pub(crate) filename: &'a str,
+    pub(crate) content_type: &'a str,
+    pub(crate) data: &'a [u8],
+}
+
+impl Client {
+    /// Fetches the page at `page` and scrapes the form in it that has a
+    /// field named `field`.
+    ///
+    /// Fails with [`Error::NotAuthenticated`] if the site shows the login
+    /// page instead.
+    pub(crate) fn fetch_form(&self, page: &str, field: &str) -> Result<Form> {
+        Form::containing(
+            &self.get_authenticated_page(page)?,
+            page,
+            field,
+            &self.url(""),
+        )
+    }
+
+    /// Submits a form, including all its hidden fields.
+    pub(crate) fn submit_form(&self, form: &Form) -> Result<Response> {
+        self.submit_form_with_files(form, &[])
+    }
+
+    /// Submits a form along with some files.
+    pub(crate) fn submit_form_with_files(
+        &self,
+        form: &Form,
+        files: &[FormFile<'_>],
+    ) -> Result<Response> {
+        if !form.multipart && files.is_empty() {
+            return self.post_form(&form.action, &form.encoded_fields());
+        }
+
+        let mut multipart = Multipart::new();
+        for (name, value) in &form.fields {
+            multipart = multipart.text(name, value);
+        }
+        for file in files {
+            multipart = multipart.file(file.field, file.filename, file.content_type, file.data);
+        }
+
+        self.post_multipart(&form.action, multipart)
+    }
+}
+
+/// Gets the value a `<select>` is sent with: its selected option or, if
+/// none is, its first one (unless several can be selected).
+fn select_value(select: ElementRef<'_>) -> Option<String> {
+    let option = match select.select(&selector("option[selected]")).next() {
+        Some(option) => option,
+        None if select.value().attr("multiple").is_some() => return None,
+        None => select.select(&selector("option")).next()?,
+    };
+
+    Some(
+        option
+            .value()
+            .attr("value")
+            .map_or_else(|| text(option), Into::into),
+    )
+}
+
+/// Resolves the action of a form to a path in `site`.
+///
+/// Fails with [`Error::UnexpectedPageLayout`] if the form is sent to another
+/// site.
+fn resolve_action(action: &str, page: &str, site: &str) -> Result<String> {
+    let foreign = || Error::unexpected_layout(page, format!("form sent to another site: {action}"));
+
+    let absolute = match action.split_once("://") {
+        Some((scheme, rest))
+            if scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https") =>
+        {
+            Some(rest)
+        }
+        Some(_) => return Err(foreign()),
+        None => action.strip_prefix("//"),
+    };
+    let Some(absolute) = absolute else {
+        if action.is_empty() {
+            return Ok(page.into());
+        } else if action.starts_with('/') {
+            return Ok(action.into());
+        } else if action.starts_with('?') {
+            let page = page.split('?').next().unwrap_or_default();
+            return Ok(format!("{page}{action}"));
+        } else if action
+            .split(['/', '?', '#'])
+            .next()
+            .is_some_and(|head| head.contains(':'))
+        {
+            // Another scheme, such as `javascript:`.
+            return Err(foreign());
+        }
+        let dir = page.rfind('/').map_or("", |i| &page[..i]);
+        return Ok(format!("{dir}/{action}"));
+    };
+
+    let site = site
+        .split_once("://")
+        .map_or(site, |(_, rest)| rest)
+        .trim_end_matches('/');
+    match absolute.strip_prefix(site) {
+        Some("") => Ok("/".into()),
+        Some(path) if path.starts_with('/') => Ok(path.into()),
+        Some(query) if query.starts_with('?') => Ok(format!("/{query}")),
+        _ => Err(foreign()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use scraper::Html;
+
+    use super::{resolve_action, Form};
+
+    const SITE: &str = "https://jutge.org";
+
+    fn form(html: &str) -> Form {
+        Form::containing(&Html::parse_document(html), "/profile/edit", "name", SITE).unwrap()
+    }
+
+    fn field<'a>(form: &'a Form, name: &str) -> Option<&'a str> {
+        form.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[test]
+    fn select_defaults() {
+        let form = form(
+            r#"<form action="/profile">
+                <input name="name" value="Ada">
+                <select name="country">
+                    <option value="AD">Andorra
+                    <option value="ES" selected>Spain
+                </select>
+                <select name="language"><option value="ca">Català<option>English</select>
+                <select name="year"><option>2024<option>2025</select>
+                <select name="tags" multiple><option value="a">A</select>
+            </form>"#,
+        );
+
+        assert_eq!(field(&form, "country"), Some("ES"));
+        assert_eq!(field(&form, "language"), Some("ca"));
+        assert_eq!(field(&form, "year"), Some("2024"));
+        assert_eq!(field(&form, "tags"), None);
+    }
+
+    #[test]
+    fn textarea_values() {
+        let form = form(
+            "<form action=\"/profile\">\n\
+                <input name=\"name\" value=\"Ada\">\n\
+                <textarea name=\"bio\">\n  Line one\n\n\tLine  two &amp; more\n</textarea>\n\
+                <textarea name=\"empty\"></textarea>\n\
+            </form>",
+        );
+
+        assert_eq!(
+            field(&form, "bio"),
+            Some("  Line one\n\n\tLine  two & more\n")
+        );
+        assert_eq!(field(&form, "empty"), Some(""));
+    }
+
+    #[test]
+    fn actions_in_the_site() {
+        let resolve = |action| resolve_action(action, "/profile/edit?tab=1", SITE).unwrap();
+
+        assert_eq!(resolve(""), "/profile/edit?tab=1");
+        assert_eq!(resolve("/profile"), "/profile");
+        assert_eq!(resolve("save"), "/profile/save");
+        assert_eq!(resolve("?tab=2"), "/profile/edit?tab=2");
+        assert_eq!(resolve("https://jutge.org/profile"), "/profile");
+        assert_eq!(resolve("HTTPS://jutge.org"), "/");
+        assert_eq!(resolve("//jutge.org/profile"), "/profile");
+        assert_eq!(resolve("http://jutge.org?tab=2"), "/?tab=2");
+    }
+
+    #[test]
+    fn actions_in_other_sites() {
+        let resolve = |action| resolve_action(action, "/profile/edit", SITE);
+
+        assert!(resolve("https://evil.example/profile").is_err());
+        assert!(resolve("https://jutge.org.evil.example/profile").is_err());
+        assert!(resolve("https://jutge.org@evil.example/profile").is_err());
+        assert!(resolve("//evil.example/profile").is_err());
+        assert!(resolve("javascript:alert(1)").is_err());
+        assert!(resolve("ftp://jutge.org/profile").is_err());
+    }
+}
@@ -0,0 +1,172 @@
+use scraper::{ElementRef, Html};
+
+use crate::problem_id_types::Unlocalized;
+use crate::sans_io::{FetchHome, Operation, Request, Response};
+use crate::scrape::{selector, text, text_of};
+use crate::{Client, Partial, ProblemId, Result, Timestamp, Verdict};
+
+/// What's new in <https://jutge.org>, as shown in its landing page.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Home {
+    /// The problems featured by the site.
+    pub featured: Vec<FeaturedProblem>,
+
+    /// The news of the site, from the newest to the oldest.
+    pub news: Vec<News>,
+
+    /// The latest submissions made to the site, from the newest to the
+    /// oldest.
+    pub activity: Vec<Activity>,
+}
+
+/// A problem featured in the landing page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeaturedProblem {
+    /// The id of the problem.
+    pub id: ProblemId<Unlocalized>,
+
+    /// The title of the problem.
+    pub title: String,
+}
+
+/// A piece of news from the landing page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct News {
+    /// The time the news was published, if the site shows it.
+    pub date: Option<Timestamp>,
+
+    /// The heading of the news.
+    pub title: String,
+
+    /// The text of the news, without its heading and date.
+    pub body: String,
+}
+
+/// A recent submission, as listed in the landing page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Activity {
+    /// The user who made the submission.
+    pub user: String,
+
+    /// The problem the submission was sent to.
+    pub problem: ProblemId<Unlocalized>,
+
+    /// The verdict of the submission, if it's been judged.
+    pub verdict: Option<Verdict>,
+
+    /// The time the submission was made, if the site shows it.
+    pub time: Option<Timestamp>,
+}
+
+impl Client {
+    /// Fetches the featured problems, the news and the recent activity shown
+    /// in the landing page of the site.
+    ///
+    /// Sections missing from the page are returned empty.
+    ///
+    /// # Errors
+    /// Network errors are returned as [`Error::UreqError`](crate::Error::UreqError).
+    pub fn home(&self) -> Result<Home> {
+        self.execute(&FetchHome)
+    }
+}
+
+impl Operation for FetchHome {
+    type Output = Home;
+
+    fn request(&self) -> Request {
+        Request::page("/")
+    }
+
+    fn parse(&self, response: &Response<'_>) -> Result<Home> {
+        Ok(self.parse_partial(response)?.value)
+    }
+
+    /// Sections missing from the page are left empty, and reported as
+    /// failures even though [`Operation::parse()`] doesn't fail on them.
+    fn parse_partial(&self, response: &Response<'_>) -> Result<Partial<Home>> {
+        // The landing page includes the login form when logged out, so it
+        // can't be parsed with `Response::html()`.
+        let html = Html::parse_document(&String::from_utf8_lossy(response.body));
+
+        let mut home = Partial::complete(Home {
+            featured: parse_featured(&html),
+            news: parse_news(&html),
+            activity: parse_activity(&html),
+        });
+        for (field, section) in [
+            ("featured", "#featured, .featured"),
+            ("news", "#news, .news"),
+            ("activity", "#activity, .activity"),
+        ] {
+            if html.select(&selector(section)).next().is_none() {
+                home.fail(field, format!("no {field} section found"));
+            }
+        }
+        Ok(home)
+    }
+}
+
+fn parse_featured(html: &Html) -> Vec<FeaturedProblem> {
+    html.select(&selector("#featured a[href], .featured a[href]"))
+        .filter_map(|link| {
+            let id = ProblemId::<Unlocalized>::parse_lenient(link.value().attr("href")?).ok()?;
+            let title = text(link);
+            // Links show the title, sometimes after the id.
+            let title = match title.split_once('·') {
+                Some((_, title)) => title.trim().to_owned(),
+                None => title,
+            };
+
+            Some(FeaturedProblem { id, title })
+        })
+        .collect()
+}
+
+fn parse_news(html: &Html) -> Vec<News> {
+    html.select(&selector(
+        "#news article, .news article, #news li, .news li",
+    ))
+    .filter_map(|item| {
+        let title = text_of(item, "h3, h4, h5, strong")?;
+        let date = text_of(item, "time, .date");
+        let body = strip_parts(item, &[&title, date.as_deref().unwrap_or_default()]);
+
+        Some(News {
+            date: date
+                .as_deref()
+                .and_then(Timestamp::find_in)
+                .or_else(|| Timestamp::find_in(&text(item))),
+            title,
+            body,
+        })
+    })
+    .collect()
+}
+
+fn parse_activity(html: &Html) -> Vec<Activity> {
+    html.select(&selector("#activity tr, .activity tr"))
+        .filter_map(|row| {
+            let cells: Vec<_> = row.select(&selector("td")).map(text).collect();
+            let [user, problem, verdict, rest @ ..] = cells.as_slice() else {
+                return None;
+            };
+
+            Some(Activity {
+                user: user.clone(),
+                problem: ProblemId::<Unlocalized>::parse_lenient(problem).ok()?,
+                verdict: verdict.parse().ok().filter(Verdict::is_final),
+                time: rest.iter().find_map(|cell| Timestamp::find_in(cell)),
+            })
+        })
+        .collect()
+}
+
+/// Gets the text of an element, without the given parts (e.g. its heading).
+fn strip_parts(element: ElementRef<'_>, parts: &[&str]) -> String {
+    let mut body = text(element);
+    for part in parts.iter().filter(|part| !part.is_empty()) {
+        body = body.replacen(part, "", 1);
+    }
+    body.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,58 @@
+//! Verification of the files downloaded by a [`Client`](crate::Client).
+
+use std::io::{self, Cursor};
+
+use zip::ZipArchive;
+
+/// Checks that a downloaded file is complete, returning what's wrong with it
+/// otherwise.
+///
+/// The length of the file is compared with the one announced by the server,
+/// if any. Then, the format of the file is detected from its contents:
+/// zip archives must have a readable central directory and entries that
+/// match their checksums, and PDF documents must end with an end-of-file
+/// marker. Other files are only checked by length.
+pub(crate) fn verify(contents: &[u8], expected_length: Option<u64>) -> Result<(), String> {
+    if let Some(expected) = expected_length {
+        if contents.len() as u64 != expected {
+            return Err(format!(
+                "got {} bytes, but the server announced {expected}",
+                contents.len()
+            ));
+        }
+    }
+
+    if contents.starts_with(b"PK\x03\x04") {
+        verify_zip(contents)
+    } else if contents.starts_with(b"%PDF-") {
+        verify_pdf(contents)
+    } else {
+        Ok(())
+    }
+}
+
+fn verify_zip(contents: &[u8]) -> Result<(), String> {
+    let mut archive =
+        ZipArchive::new(Cursor::new(contents)).map_err(|err| format!("broken zip: {err}"))?;
+
+    // The checksum of an entry is checked once it has been read entirely.
+    for i in 0..archive.len() {
+        let mut entry = archive
+            .by_index(i)
+            .map_err(|err| format!("broken zip entry: {err}"))?;
+        io::copy(&mut entry, &mut io::sink())
+            .map_err(|err| format!("broken zip entry {i}: {err}"))?;
+    }
+
+    Ok(())
+}
+
+fn verify_pdf(contents: &[u8]) -> Result<(), String> {
+    let tail = &contents[contents.len().saturating_sub(1024)..];
+
+    if tail.windows(5).any(|window| window == b"%%EOF") {
+        Ok(())
+    } else {
+        Err("truncated PDF: no end-of-file marker".into())
+    }
+}
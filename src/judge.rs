@@ -0,0 +1,236 @@
+//! A small local judge, for setters who have all the test cases of a problem.
+
+use std::fmt::Display;
+use std::time::Duration;
+
+use crate::{Result, Runner, TestCase, TestOutcome, Verdict};
+
+/// Judges solutions locally against a full set of test cases, like the judge
+/// of <https://jutge.org> would.
+///
+/// The test cases usually come from a [`Package`](crate::Package). A
+/// reference solution can be given with [`LocalJudge::with_reference()`],
+/// to check that the test cases are consistent and to compare the time of
+/// the candidate solutions with it.
+///
+/// The test cases are run one at a time, so the timings aren't skewed by the
+/// solutions competing for the CPU.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalJudge {
+    cases: Vec<TestCase>,
+    time_limit: Option<Duration>,
+    reference: Option<JudgeReport>,
+}
+
+impl LocalJudge {
+    /// Creates a `LocalJudge` for the given test cases, without a time limit
+    /// nor a reference solution.
+    #[must_use]
+    pub fn new(cases: impl IntoIterator<Item = TestCase>) -> Self {
+        Self {
+            cases: cases.into_iter().collect(),
+            time_limit: None,
+            reference: None,
+        }
+    }
+
+    /// Sets the maximum (wall clock) time a solution can take in each test
+    /// case, replacing the one of its [`Runner`].
+    #[must_use]
+    pub fn time_limit(mut self, limit: Duration) -> Self {
+        self.time_limit = Some(limit);
+        self
+    }
+
+    /// Runs the reference solution of the problem against all the test
+    /// cases, so the reports of [`LocalJudge::judge()`] compare the times of
+    /// the candidates with its ones.
+    ///
+    /// The reference is expected to pass all the test cases: check its
+    /// report, available with [`LocalJudge::reference()`], to find the ones
+    /// where it doesn't (or where it's too close to the time limit).
+    ///
+    /// # Errors
+    /// Returns [`Error::IoError`](crate::Error::IoError) if the reference
+    /// solution can't be executed.
+    pub fn with_reference(mut self, reference: &Runner) -> Result<Self> {
+        self.reference = Some(self.run(reference, None)?);
+        Ok(self)
+    }
+
+    /// Gets the report of the reference solution, if there's one.
+    #[must_use]
+    pub const fn reference(&self) -> Option<&JudgeReport> {
+        self.reference.as_ref()
+    }
+
+    /// Judges a candidate solution against all the test cases.
+    ///
+    /// # Errors
+    /// Returns [`Error::IoError`](crate::Error::IoError) if the solution
+    /// can't be executed.
+    pub fn judge(&self, candidate: &Runner) -> Result<JudgeReport> {
+        self.run(candidate, self.reference.as_ref())
+    }
+
+    fn run(&self, runner: &Runner, reference: Option<&JudgeReport>) -> Result<JudgeReport> {
+        let runner = match self.time_limit {
+            Some(limit) => runner.clone().time_limit(limit),
+            None => runner.clone(),
+        };
+
+        let cases = self
+            .cases
+            .iter()
+            .enumerate()
+            .map(|(i, case)| {
+                let (outcome, time) = runner.run_timed(case)?;
+                Ok(CaseReport {
+                    name: case.name.clone(),
+                    outcome,
+                    time,
+                    reference_time: reference.and_then(|r| r.cases.get(i)).map(|r| r.time),
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(JudgeReport {
+            cases,
+            time_limit: self.time_limit,
+        })
+    }
+}
+
+/// The result of judging a solution with a [`LocalJudge`].
+///
+/// Its [`Display`] implementation renders it as a table, with a line per
+/// test case and a summary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JudgeReport {
+    /// The results of the test cases, in the order they were given to the
+    /// judge.
+    pub cases: Vec<CaseReport>,
+
+    /// The time limit of each test case, if there was one.
+    pub time_limit: Option<Duration>,
+}
+
+/// The result of running a solution against a test case, in a
+/// [`JudgeReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseReport {
+    /// The name of the test case.
+    pub name: String,
+
+    /// The outcome of the test case.
+    pub outcome: TestOutcome,
+
+    /// The time the solution took in the test case.
+    pub time: Duration,
+
+    /// The time the reference solution took in the test case, if there's
+    /// one.
+    pub reference_time: Option<Duration>,
+}
+
+impl CaseReport {
+    /// Gets how many times slower than the reference solution the solution
+    /// was, if there's a reference.
+    #[must_use]
+    pub fn slowdown(&self) -> Option<f64> {
+        let reference = self.reference_time.filter(|time| !time.is_zero())?;
+        Some(self.time.as_secs_f64() / reference.as_secs_f64())
+    }
+}
+
+impl JudgeReport {
+    /// Gets the verdict the judge would give to the solution: the one of its
+    /// worst test case, or [`Verdict::Accepted`] if it passed all of them.
+    #[must_use]
+    pub fn verdict(&self) -> Verdict {
+        self.cases.iter().map(|case| case.outcome.verdict()).fold(
+            Verdict::Accepted,
+            |worst, verdict| {
+                if verdict.worse_than(&worst) {
+                    verdict
+                } else {
+                    worst
+                }
+            },
+        )
+    }
+
+    /// Counts the test cases the solution passed.
+    #[must_use]
+    pub fn passed(&self) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| case.outcome.is_passed())
+            .count()
+    }
+
+    /// Gets the longest time the solution took in a test case.
+    #[must_use]
+    pub fn max_time(&self) -> Duration {
+        self.cases
+            .iter()
+            .map(|case| case.time)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Gets the largest slowdown of the solution with respect to the
+    /// reference (see [`CaseReport::slowdown()`]), if there's a reference.
+    #[must_use]
+    pub fn max_slowdown(&self) -> Option<f64> {
+        self.cases
+            .iter()
+            .filter_map(CaseReport::slowdown)
+            .reduce(f64::max)
+    }
+}
+
+impl Display for JudgeReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self
+            .cases
+            .iter()
+            .map(|case| case.name.len())
+            .max()
+            .unwrap_or_default();
+
+        for case in &self.cases {
+            let outcome = match case.outcome {
+                TestOutcome::TimeLimitExceeded => "TLE".to_owned(),
+                _ => case.outcome.verdict().to_string(),
+            };
+            write!(
+                f,
+                "{:width$}  {outcome:>3}  {:>8.3}s",
+                case.name,
+                case.time.as_secs_f64()
+            )?;
+            if let (Some(reference), Some(slowdown)) = (case.reference_time, case.slowdown()) {
+                write!(
+                    f,
+                    "  (reference {:.3}s, x{slowdown:.2})",
+                    reference.as_secs_f64()
+                )?;
+            }
+            writeln!(f)?;
+        }
+
+        write!(
+            f,
+            "{}: {}/{} passed, max time {:.3}s",
+            self.verdict(),
+            self.passed(),
+            self.cases.len(),
+            self.max_time().as_secs_f64()
+        )?;
+        if let Some(limit) = self.time_limit {
+            write!(f, " of {:.3}s", limit.as_secs_f64())?;
+        }
+        Ok(())
+    }
+}
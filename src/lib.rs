@@ -1,5 +1,14 @@
 //! A library crate for easy and idiomatic interaction with
 //! <https://jutge.org>
+//!
+//! Everything in the crate is available from its root, and also grouped by
+//! topic in [`common`], [`problems`], [`submissions`], [`courses`],
+//! [`users`], [`local`] and [`site`]. The types most programs use can be
+//! imported at once from the [`prelude`].
+//!
+//! Most of the crate can be left out of builds that don't need it, by
+//! disabling its default Cargo features: see [`Features`] for the list, and
+//! [`features()`] to check the ones of a build.
 
 #![warn(missing_docs)]
 #![deny(unsafe_code)]
@@ -8,23 +17,114 @@
 #![allow(clippy::module_name_repetitions)]
 
 macro_rules! modules {
-    ($($mod:ident),+; $($feature:literal => $($f_mod:ident),+);*;) => {
-        $(
-            mod $mod;
-            pub use $mod::*;
-            )+
+    ($(
+        $(#[$doc:meta])*
+        pub mod $group:ident {
+            $($mod:ident),*;
+            $($feature:literal => $($f_mod:ident),+;)*
+        }
+    )+) => {
         $(
             $(
-                #[cfg(feature=$feature)]
-                mod $f_mod;
-                #[cfg(feature=$feature)]
-                pub use $f_mod::*;
-                )+
-            )*
+                mod $mod;
+                pub use $mod::*;
+                )*
+            $(
+                $(
+                    #[cfg(feature=$feature)]
+                    mod $f_mod;
+                    #[cfg(feature=$feature)]
+                    pub use $f_mod::*;
+                    )+
+                )*
+
+            $(#[$doc])*
+            pub mod $group {
+                $(
+                    pub use crate::$mod::*;
+                    )*
+                $(
+                    $(
+                        #[cfg(feature=$feature)]
+                        pub use crate::$f_mod::*;
+                        )+
+                    )*
+            }
+            )+
     };
 }
 
 modules! {
-    error, problem;
-    "web-client" => client;
+    /// Types shared by the rest of the crate: errors, timestamps, quantities,
+    /// the outcomes of batch operations and the features of the build.
+    pub mod common {
+        batch, error, features, timestamp, units;
+        "serde" => schema;
+    }
+
+    /// Problems: their ids, statements, samples and statistics, and the index
+    /// of public problems.
+    pub mod problems {
+        problem, problem_index, problem_set, stats;
+        "web-client" => challenge, mirror, problem_handle, problem_info, samples, setter, statement;
+    }
+
+    /// Submissions: their ids, statuses and verdicts, and how to submit them.
+    pub mod submissions {
+        compiler, submission, verdict;
+        "client-auth" => submit;
+    }
+
+    /// Courses: their lists of problems, deadlines, exams and the progress of
+    /// their students.
+    pub mod courses {
+        calendar, course_list, course_progress;
+        "web-client" => announcement, exam_admin;
+        "courses" => notify, watch;
+    }
+
+    /// Users: their public profiles and progress reports.
+    pub mod users {
+        report, user;
+        "client-auth" => profile;
+    }
+
+    /// Local tools: compiling, running and judging solutions, and keeping
+    /// workspaces and problem packages.
+    pub mod local {
+        diagnostic, diff, package, runner, state, workspace;
+        "runner" => judge, precheck, testgen;
+    }
+
+    /// Access to the site: the client, sessions, typed routes and the parsers
+    /// of its pages.
+    pub mod site {
+        route;
+        "web-client" => client, debug_log, home, metrics, parse, partial, politeness;
+        "client-auth" => session;
+    }
 }
+
+pub mod prelude;
+
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
+#[cfg(feature = "web-client")]
+pub mod sans_io;
+
+#[cfg(feature = "web-client")]
+mod cache;
+#[cfg(feature = "web-client")]
+mod course;
+#[cfg(feature = "client-auth")]
+mod form;
+#[cfg(feature = "web-client")]
+mod integrity;
+#[cfg(feature = "client-auth")]
+mod multipart;
+#[cfg(feature = "web-client")]
+mod rate_limit;
+#[cfg(feature = "web-client")]
+mod resolver;
+#[cfg(feature = "web-client")]
+mod scrape;
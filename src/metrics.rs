@@ -0,0 +1,205 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use ureq::{Middleware, MiddlewareNext, Request, Response};
+
+/// The upper bounds of the buckets of a [`Histogram`].
+const BUCKETS: [Duration; 10] = [
+    Duration::from_millis(10),
+    Duration::from_millis(25),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_millis(2500),
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+];
+
+/// A snapshot of the metrics of a [`Client`](crate::Client), as returned by
+/// [`Client::metrics()`](crate::Client::metrics).
+///
+/// The counters start when the `Client` is built and are shared with its
+/// sessions, so they cover all the traffic to the site.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Metrics {
+    /// The number of requests sent to each endpoint, such as
+    /// `GET /problems/{id}`. Path segments with digits, which are usually
+    /// ids, are replaced by `{id}`, and queries are left out.
+    pub requests: BTreeMap<String, u64>,
+
+    /// The number of responses received with each status code.
+    pub statuses: BTreeMap<u16, u64>,
+
+    /// The number of requests that got no response, e.g. because the
+    /// connection failed.
+    pub transport_errors: u64,
+
+    /// The number of requests retried, because the site throttled them or
+    /// a download arrived corrupt.
+    pub retries: u64,
+
+    /// The number of bytes read from the bodies of the responses, after
+    /// decompressing them.
+    pub bytes_downloaded: u64,
+
+    /// The time it took to receive the headers of each response.
+    pub latency: Histogram,
+
+    /// The time spent waiting for the rate limits of the `Client` (or for
+    /// the site to stop throttling it) before each request that had to
+    /// wait.
+    pub rate_limit_waits: Histogram,
+}
+
+impl Metrics {
+    /// Counts the requests sent to all the endpoints.
+    #[must_use]
+    pub fn total_requests(&self) -> u64 {
+        self.requests.values().sum()
+    }
+}
+
+/// A distribution of durations, counted in buckets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    counts: [u64; BUCKETS.len() + 1],
+    sum: Duration,
+}
+
+impl Histogram {
+    /// Counts the durations recorded.
+    #[must_use]
+    pub fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Adds up the durations recorded.
+    #[must_use]
+    pub const fn sum(&self) -> Duration {
+        self.sum
+    }
+
+    /// Gets the mean of the durations recorded, or `None` if there are
+    /// none.
+    #[must_use]
+    pub fn mean(&self) -> Option<Duration> {
+        let count = u32::try_from(self.count())
+            .ok()
+            .filter(|count| *count > 0)?;
+        Some(self.sum / count)
+    }
+
+    /// Gets the buckets of the histogram, as pairs of their upper bound
+    /// (inclusive) and the number of durations in them. The last bucket,
+    /// bounded by [`Duration::MAX`], holds the durations above the other
+    /// bounds.
+    pub fn buckets(&self) -> impl Iterator<Item = (Duration, u64)> + '_ {
+        BUCKETS
+            .iter()
+            .copied()
+            .chain([Duration::MAX])
+            .zip(self.counts.iter().copied())
+    }
+
+    fn record(&mut self, duration: Duration) {
+        let bucket = BUCKETS.partition_point(|bound| *bound < duration);
+        self.counts[bucket] += 1;
+        self.sum = self.sum.saturating_add(duration);
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            counts: [0; BUCKETS.len() + 1],
+            sum: Duration::ZERO,
+        }
+    }
+}
+
+/// The recorder of the metrics of a `Client`, installed as a `ureq`
+/// middleware.
+///
+/// Clones share the same counters.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MetricsRecorder {
+    metrics: Arc<Mutex<Metrics>>,
+}
+
+impl MetricsRecorder {
+    /// Takes a snapshot of the metrics recorded so far.
+    pub(crate) fn snapshot(&self) -> Metrics {
+        self.lock().clone()
+    }
+
+    /// Counts a retried request.
+    pub(crate) fn record_retry(&self) {
+        self.lock().retries += 1;
+    }
+
+    /// Counts the bytes of a response body.
+    pub(crate) fn record_bytes(&self, bytes: usize) {
+        self.lock().bytes_downloaded += bytes as u64;
+    }
+
+    /// Records a wait for a rate limit, unless it's zero.
+    pub(crate) fn record_wait(&self, wait: Duration) {
+        if !wait.is_zero() {
+            self.lock().rate_limit_waits.record(wait);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Metrics> {
+        self.metrics.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Middleware for MetricsRecorder {
+    fn handle(&self, request: Request, next: MiddlewareNext) -> Result<Response, ureq::Error> {
+        let endpoint = format!("{} {}", request.method(), endpoint(request.url()));
+        let start = Instant::now();
+
+        let result = next.handle(request);
+
+        let latency = start.elapsed();
+        let mut metrics = self.lock();
+        *metrics.requests.entry(endpoint).or_default() += 1;
+        match &result {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => {
+                *metrics.statuses.entry(response.status()).or_default() += 1;
+                metrics.latency.record(latency);
+            }
+            Err(ureq::Error::Transport(_)) => metrics.transport_errors += 1,
+        }
+
+        result
+    }
+}
+
+/// Gets the endpoint of an URL: its path, without the query and with the
+/// segments that look like ids replaced by `{id}`.
+fn endpoint(url: &str) -> String {
+    let path = url.split_once("://").map_or(url, |(_, rest)| {
+        rest.find('/').map_or("/", |start| &rest[start..])
+    });
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+
+    let segments: Vec<_> = path
+        .split('/')
+        .map(|segment| {
+            if segment.contains(|c: char| c.is_ascii_digit()) {
+                "{id}"
+            } else {
+                segment
+            }
+        })
+        .collect();
+
+    match segments.join("/") {
+        path if path.is_empty() => "/".into(),
+        path => path,
+    }
+}
@@ -0,0 +1,282 @@
+//! Local mirrors of the public problems of <https://jutge.org>.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+
+use crate::problem_id_types::Localized;
+use crate::{
+    Client, Error, Fingerprint, Manifest, ProblemId, ProblemLanguage, PublicProblem, Result,
+};
+
+/// What [`Mirror::public_problems()`] mirrors, and where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MirrorOptions {
+    dir: PathBuf,
+    languages: Vec<ProblemLanguage>,
+    concurrency: usize,
+}
+
+impl MirrorOptions {
+    /// Creates the options to mirror every public problem, in all of its
+    /// languages, to a directory.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        const DEFAULT_CONCURRENCY: usize = 2;
+
+        Self {
+            dir: dir.into(),
+            languages: Vec::new(),
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+
+    /// Only mirrors the statements in some languages. Defaults to all of
+    /// them.
+    #[must_use]
+    pub fn languages(mut self, languages: &[ProblemLanguage]) -> Self {
+        self.languages = languages.to_vec();
+        self
+    }
+
+    /// Sets how many problems are mirrored at the same time. Defaults to 2.
+    ///
+    /// This is further capped by the [`Politeness`](crate::Politeness)
+    /// policy of the `Client`.
+    #[must_use]
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Checks whether a statement language is mirrored.
+    fn includes(&self, language: ProblemLanguage) -> bool {
+        self.languages.is_empty() || self.languages.contains(&language)
+    }
+}
+
+/// A local mirror of the public problems of the site, as left by
+/// [`Mirror::public_problems()`].
+///
+/// The mirror is a directory with a subdirectory per problem and language
+/// (such as `P068688_en`), holding:
+///
+/// - `statement.html`: the [sanitized](crate::Statement::sanitized_html)
+///   HTML of the statement.
+/// - `statement.txt`: the [text](crate::Statement::text) of the statement.
+/// - `<name>.inp` and `<name>.cor`: the input and the expected output of each
+///   sample test case, such as `sample-1.inp`.
+///
+/// Next to them, the [`Manifest`] in [`Manifest::FILE_NAME`] records the
+/// [`Fingerprint`] of each mirrored problem, and the index in
+/// [`Mirror::INDEX_FILE`] has a line per problem with its unlocalized id, its
+/// title and its mirrored languages, separated by tabs. Nothing depends on
+/// when or how fast the mirror was made, so mirroring the same contents
+/// twice gives the same files.
+///
+/// # Example
+/// ```no_run
+/// use jutge::{Client, Mirror, MirrorOptions, ProblemLanguage};
+///
+/// let options = MirrorOptions::new("mirror").languages(&[ProblemLanguage::English]);
+/// let mirror = Mirror::public_problems(&Client::new(), &options)?;
+/// for (id, err) in mirror.failures() {
+///     eprintln!("Couldn't mirror {id}: {err}");
+/// }
+/// # Ok::<(), jutge::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Mirror {
+    manifest: Manifest,
+    updated: Vec<ProblemId<Localized>>,
+    failures: Vec<(ProblemId<Localized>, Error)>,
+}
+
+impl Mirror {
+    /// The name of the index file of a mirror.
+    pub const INDEX_FILE: &'static str = "index.tsv";
+
+    /// Mirrors the public problems of the site to a local directory, or
+    /// updates an existing mirror.
+    ///
+    /// The problems are walked with [`Client::for_each_public_problem()`],
+    /// so the crawl follows the [`Politeness`](crate::Politeness) policy of
+    /// the `Client`: each problem waits for the bulk delay (and the crawl
+    /// window, if any), and the concurrency is capped. The statement page and
+    /// the samples of each problem are downloaded once, and only the problems
+    /// whose [`Fingerprint`] changed since the last run are written again.
+    ///
+    /// A problem that fails doesn't stop the rest: it's reported in
+    /// [`Mirror::failures()`], and its previous copy (if any) is kept.
+    ///
+    /// # Errors
+    /// Returns [`Error::IoError`] if the manifest or the index of the mirror
+    /// can't be read or written, [`Error::InvalidManifest`] if the existing
+    /// manifest is malformed, or the first error fetching the public problem
+    /// index. In that last case, the manifest is still saved, but the index
+    /// of the mirror is left as it was.
+    pub fn public_problems(client: &Client, options: &MirrorOptions) -> Result<Self> {
+        fs::create_dir_all(&options.dir)?;
+        let manifest_path = options.dir.join(Manifest::FILE_NAME);
+
+        let previous = Manifest::load(&manifest_path)?;
+        let mut mirror = Self {
+            manifest: previous.clone(),
+            updated: Vec::new(),
+            failures: Vec::new(),
+        };
+        let mut index = Vec::new();
+        let walk = client.for_each_public_problem(
+            options.concurrency,
+            |client, problem| Ok(mirror_problem(client, options, &previous, problem)),
+            |problem, results| {
+                // The fetch never fails, as the results are per language.
+                for (id, result) in results.unwrap_or_default() {
+                    match result {
+                        Ok(fingerprint) => {
+                            if !previous.is_current(&id, fingerprint) {
+                                mirror.updated.push(id.clone());
+                            }
+                            mirror.manifest.insert(id, fingerprint);
+                        }
+                        Err(err) => mirror.failures.push((id, err)),
+                    }
+                }
+                index.push(problem);
+                ControlFlow::Continue(())
+            },
+        );
+        mirror.manifest.save(&manifest_path)?;
+        walk?;
+
+        index.sort_by(|a, b| a.id.cmp(&b.id));
+        fs::write(
+            options.dir.join(Self::INDEX_FILE),
+            mirror.index(&index, options),
+        )?;
+
+        mirror.updated.sort();
+        mirror.failures.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(mirror)
+    }
+
+    /// Gets the manifest of the mirror, with every problem it holds.
+    #[must_use]
+    pub const fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    /// Gets the problems that were added to the mirror or changed in it,
+    /// sorted by id.
+    #[must_use]
+    pub fn updated(&self) -> &[ProblemId<Localized>] {
+        &self.updated
+    }
+
+    /// Gets the problems that couldn't be mirrored, with their error,
+    /// sorted by id.
+    #[must_use]
+    pub fn failures(&self) -> &[(ProblemId<Localized>, Error)] {
+        &self.failures
+    }
+
+    /// Checks whether every problem was mirrored.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Writes the index of the mirror, with the languages of each problem
+    /// that are in the manifest.
+    fn index(&self, problems: &[PublicProblem], options: &MirrorOptions) -> String {
+        let mut index = String::new();
+        for problem in problems {
+            let languages: Vec<_> = problem
+                .languages
+                .iter()
+                .filter(|language| options.includes(**language))
+                .filter_map(|language| problem.id.localize(*language).ok())
+                .filter(|id| self.manifest.get(id).is_some())
+                .map(|id| String::from_utf8_lossy(&id.language().code()).into_owned())
+                .collect();
+            if !languages.is_empty() {
+                let _ = writeln!(
+                    index,
+                    "{}\t{}\t{}",
+                    problem.id,
+                    problem.title.replace(['\t', '\n'], " "),
+                    languages.join(",")
+                );
+            }
+        }
+        index
+    }
+}
+
+/// Mirrors the statements of a problem in the languages of the options.
+fn mirror_problem(
+    client: &Client,
+    options: &MirrorOptions,
+    previous: &Manifest,
+    problem: &PublicProblem,
+) -> Vec<(ProblemId<Localized>, Result<Fingerprint>)> {
+    let mut results = Vec::new();
+    for language in problem
+        .languages
+        .iter()
+        .filter(|language| options.includes(**language))
+    {
+        let Ok(id) = problem.id.localize(*language) else {
+            continue;
+        };
+        // The walk already paced the first language.
+        if !results.is_empty() {
+            client.pace_bulk();
+        }
+        let result = mirror_statement(client, &options.dir, previous, &id);
+        results.push((id, result));
+    }
+    results
+}
+
+/// Writes the statement and the samples of a problem to the mirror, unless
+/// they didn't change.
+fn mirror_statement(
+    client: &Client,
+    dir: &Path,
+    previous: &Manifest,
+    id: &ProblemId<Localized>,
+) -> Result<Fingerprint> {
+    // This refreshes the caches, so the statement and the samples below
+    // aren't downloaded again.
+    let fingerprint = client.problem_fingerprint(id)?;
+    let problem_dir = dir.join(id.as_str());
+    if previous.is_current(id, fingerprint) && problem_dir.is_dir() {
+        return Ok(fingerprint);
+    }
+
+    let statement = client.statement(id)?;
+    let samples = client.samples(id)?;
+
+    match fs::remove_dir_all(&problem_dir) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err.into()),
+        _ => {}
+    }
+    fs::create_dir_all(&problem_dir)?;
+    fs::write(
+        problem_dir.join("statement.html"),
+        statement.sanitized_html(),
+    )?;
+    fs::write(problem_dir.join("statement.txt"), statement.text())?;
+    for case in &samples {
+        fs::write(problem_dir.join(format!("{}.inp", case.name)), &case.input)?;
+        fs::write(
+            problem_dir.join(format!("{}.cor", case.name)),
+            &case.expected,
+        )?;
+    }
+
+    Ok(fingerprint)
+}
@@ -0,0 +1,75 @@
+//! A minimal `multipart/form-data` encoder, used to upload files.
+
+use std::fmt::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A `multipart/form-data` body under construction.
+pub(crate) struct Multipart {
+    boundary: String,
+    body: Vec<u8>,
+}
+
+impl Multipart {
+    /// Creates an empty form.
+    pub(crate) fn new() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+
+        Self {
+            boundary: format!("----jutge-rs-{nanos:032x}"),
+            body: Vec::new(),
+        }
+    }
+
+    /// Adds a text field to the form.
+    pub(crate) fn text(mut self, name: &str, value: &str) -> Self {
+        self.header(name, None, None);
+        self.body.extend_from_slice(value.as_bytes());
+        self.body.extend_from_slice(b"\r\n");
+        self
+    }
+
+    /// Adds a file field to the form.
+    pub(crate) fn file(
+        mut self,
+        name: &str,
+        filename: &str,
+        content_type: &str,
+        data: &[u8],
+    ) -> Self {
+        self.header(name, Some(filename), Some(content_type));
+        self.body.extend_from_slice(data);
+        self.body.extend_from_slice(b"\r\n");
+        self
+    }
+
+    /// Returns the value of the `Content-Type` header for this form.
+    pub(crate) fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary)
+    }
+
+    /// Finishes the form, returning the encoded body.
+    pub(crate) fn finish(mut self) -> Vec<u8> {
+        self.body
+            .extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        self.body
+    }
+
+    fn header(&mut self, name: &str, filename: Option<&str>, content_type: Option<&str>) {
+        let mut header = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{name}\"",
+            self.boundary
+        );
+        if let Some(filename) = filename {
+            let _ = write!(header, "; filename=\"{filename}\"");
+        }
+        header.push_str("\r\n");
+        if let Some(content_type) = content_type {
+            let _ = write!(header, "Content-Type: {content_type}\r\n");
+        }
+        header.push_str("\r\n");
+
+        self.body.extend_from_slice(header.as_bytes());
+    }
+}
@@ -0,0 +1,402 @@
+//! Notifications of the events of <https://jutge.org> that bots care about,
+//! such as verdicts, new lists of problems and upcoming deadlines.
+
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Display;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::course_progress::json_string;
+use crate::{Deadline, Deadlines, Result, Session, SubmissionId, Timestamp, Verdict};
+
+/// Something that happened in the site, reported by a [`Notifier`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Event {
+    /// A watched submission got its final verdict.
+    VerdictFinalized {
+        /// The submission.
+        submission: SubmissionId,
+
+        /// Its final verdict.
+        verdict: Verdict,
+    },
+
+    /// A new list of problems appeared in a watched course.
+    ListPublished {
+        /// The course of the list.
+        course_id: String,
+
+        /// The new list.
+        list_id: String,
+    },
+
+    /// A watched deadline is closer than the notice of the [`Notifier`].
+    DeadlineApproaching(Deadline),
+}
+
+impl Event {
+    /// Formats the event as a JSON object, as sent to webhooks.
+    ///
+    /// The `event` field tells the kind of event (`verdict_finalized`,
+    /// `list_published` or `deadline_approaching`), and the other fields
+    /// depend on it. The `url` of deadlines links to their page in
+    /// `base_url`, the site the event comes from (such as
+    /// `https://jutge.org`). Times are formatted as RFC 3339, like
+    /// [`Timestamp::to_rfc3339()`].
+    #[must_use]
+    pub fn to_json(&self, base_url: &str) -> String {
+        match self {
+            Self::VerdictFinalized {
+                submission,
+                verdict,
+            } => format!(
+                "{{\"event\":\"verdict_finalized\",\"submission\":{},\"verdict\":{}}}",
+                json_string(&submission.to_string()),
+                json_string(verdict.code()),
+            ),
+            Self::ListPublished { course_id, list_id } => format!(
+                "{{\"event\":\"list_published\",\"course\":{},\"list\":{}}}",
+                json_string(course_id),
+                json_string(list_id),
+            ),
+            Self::DeadlineApproaching(deadline) => format!(
+                "{{\"event\":\"deadline_approaching\",\"title\":{},\"start\":{},\"url\":{}}}",
+                json_string(&deadline.title()),
+                json_string(&deadline.start().to_rfc3339()),
+                json_string(&format!(
+                    "{}{}",
+                    base_url.trim_end_matches('/'),
+                    deadline.path()
+                )),
+            ),
+        }
+    }
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::VerdictFinalized {
+                submission,
+                verdict,
+            } => write!(f, "{submission}: {verdict}"),
+            Self::ListPublished { course_id, list_id } => {
+                write!(f, "New list {list_id} in {course_id}")
+            }
+            Self::DeadlineApproaching(deadline) => {
+                write!(f, "{} at {}", deadline.title(), deadline.start())
+            }
+        }
+    }
+}
+
+impl Session {
+    /// Creates a [`Notifier`] of the events of the site seen by this
+    /// session, which watches nothing yet.
+    #[must_use]
+    pub fn notifier(&self) -> Notifier<'_> {
+        Notifier {
+            session: self,
+            interval: Duration::from_mins(1),
+            notice: Duration::from_hours(24),
+            submissions: Vec::new(),
+            courses: Vec::new(),
+            deadlines: Vec::new(),
+            notified: HashSet::new(),
+            handlers: Vec::new(),
+            webhooks: Vec::new(),
+            unsent: VecDeque::new(),
+            sent: 0,
+            last_poll: None,
+        }
+    }
+}
+
+/// Polls the site for [`Event`]s, and reports them to callbacks and
+/// webhooks.
+///
+/// This is created by [`Session::notifier()`]. Tell it what to watch with
+/// [`Notifier::watch_submission()`], [`Notifier::watch_course()`] and
+/// [`Notifier::watch_deadlines()`], where to report the events with
+/// [`Notifier::on_event()`] and [`Notifier::webhook()`], and then call
+/// [`Notifier::run()`] (or [`Notifier::poll()`] from your own loop).
+///
+/// ```no_run
+/// # fn main() -> jutge::Result<()> {
+/// # let session: jutge::Session = todo!();
+/// session
+///     .notifier()
+///     .watch_course("PRO1")
+///     .on_event(|event| println!("{event}"))
+///     .webhook("https://example.com/hooks/jutge")
+///     .run()
+/// # }
+/// ```
+pub struct Notifier<'a> {
+    session: &'a Session,
+    interval: Duration,
+    notice: Duration,
+    submissions: Vec<SubmissionId>,
+    courses: Vec<WatchedCourse>,
+    deadlines: Vec<Deadline>,
+    notified: HashSet<Deadline>,
+    handlers: Vec<Handler<'a>>,
+    webhooks: Vec<String>,
+    unsent: VecDeque<Event>,
+    sent: usize,
+    last_poll: Option<Instant>,
+}
+
+/// A callback of a [`Notifier`].
+type Handler<'a> = Box<dyn FnMut(&Event) + 'a>;
+
+/// A course watched by a [`Notifier`], with the lists it had in the last
+/// poll (`None` before the first one).
+#[derive(Debug)]
+struct WatchedCourse {
+    course_id: String,
+    lists: Option<HashSet<String>>,
+}
+
+impl std::fmt::Debug for Notifier<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Notifier")
+            .field("session", &self.session)
+            .field("interval", &self.interval)
+            .field("notice", &self.notice)
+            .field("submissions", &self.submissions)
+            .field("courses", &self.courses)
+            .field("deadlines", &self.deadlines)
+            .field("handlers", &self.handlers.len())
+            .field("webhooks", &self.webhooks)
+            .field("unsent", &self.unsent)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Notifier<'a> {
+    /// Sets the time between the starts of two polls of
+    /// [`Notifier::run()`]. Defaults to 1 minute.
+    #[must_use]
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets how long before a deadline starts its
+    /// [`Event::DeadlineApproaching`] is reported. Defaults to 24 hours.
+    #[must_use]
+    pub fn deadline_notice(mut self, notice: Duration) -> Self {
+        self.notice = notice;
+        self
+    }
+
+    /// Watches a submission, to report an [`Event::VerdictFinalized`] when
+    /// the judge finishes it.
+    #[must_use]
+    pub fn watch_submission(mut self, id: &SubmissionId) -> Self {
+        self.submissions.push(id.clone());
+        self
+    }
+
+    /// Watches a course, to report an [`Event::ListPublished`] for each new
+    /// list of problems, and an [`Event::DeadlineApproaching`] for the
+    /// deadlines of all its lists.
+    ///
+    /// The lists the course has in the first poll aren't reported as new,
+    /// and the deadline of each list is fetched once, when the list is
+    /// first seen.
+    #[must_use]
+    pub fn watch_course(mut self, course_id: &str) -> Self {
+        self.courses.push(WatchedCourse {
+            course_id: course_id.into(),
+            lists: None,
+        });
+        self
+    }
+
+    /// Watches some deadlines, such as the ones fetched with
+    /// [`Session::deadlines()`], to report an [`Event::DeadlineApproaching`]
+    /// for each of them.
+    #[must_use]
+    pub fn watch_deadlines(mut self, deadlines: &Deadlines) -> Self {
+        self.deadlines.extend(deadlines.iter().cloned());
+        self
+    }
+
+    /// Adds a callback, which is called with every event, in the order they
+    /// happen.
+    #[must_use]
+    pub fn on_event(mut self, handler: impl FnMut(&Event) + 'a) -> Self {
+        self.handlers.push(Box::new(handler));
+        self
+    }
+
+    /// Adds a webhook: every event is sent to `url` in a `POST` request,
+    /// with its [`Event::to_json()`] as the body.
+    ///
+    /// The requests are sent like the ones to the site, with the proxy,
+    /// timeouts and user agent of the [`Client`](crate::Client) of the
+    /// session.
+    #[must_use]
+    pub fn webhook(mut self, url: &str) -> Self {
+        self.webhooks.push(url.into());
+        self
+    }
+
+    /// Checks whether there's nothing left to watch: no submission without
+    /// its final verdict, no course, no deadline that hasn't been reported
+    /// yet and no event that hasn't been sent to the webhooks.
+    #[must_use]
+    pub fn is_idle(&self) -> bool {
+        let now = Timestamp::now();
+        self.unsent.is_empty()
+            && self.submissions.is_empty()
+            && self.courses.is_empty()
+            && self
+                .deadlines
+                .iter()
+                .all(|deadline| self.notified.contains(deadline) || deadline.start() <= now)
+    }
+
+    /// Polls everything that's watched once, reporting the new events to the
+    /// callbacks and the webhooks, and returning them.
+    ///
+    /// # Errors
+    /// Fails like the methods of [`Session`] that fetch what's watched, or
+    /// with [`Error::UreqError`](crate::Error::UreqError) if a webhook can't
+    /// be reached.
+    ///
+    /// No event is lost on an error: the events found before a fetch fails
+    /// are still reported to the callbacks and the webhooks, and the events
+    /// a webhook couldn't get are sent again to it (and to the webhooks after
+    /// it) in the next poll. The callbacks are never called twice with the
+    /// same event.
+    pub fn poll(&mut self) -> Result<Vec<Event>> {
+        self.last_poll = Some(Instant::now());
+
+        let mut events = Vec::new();
+        let polled = self
+            .poll_submissions(&mut events)
+            .and_then(|()| self.poll_courses(&mut events));
+        self.poll_deadlines(&mut events);
+
+        for event in &events {
+            for handler in &mut self.handlers {
+                handler(event);
+            }
+        }
+        if !self.webhooks.is_empty() {
+            self.unsent.extend(events.iter().cloned());
+        }
+        let sent = self.send_webhooks();
+
+        polled?;
+        sent?;
+        Ok(events)
+    }
+
+    /// Polls everything that's watched every [`Notifier::poll_interval()`],
+    /// until there's nothing left to watch (see [`Notifier::is_idle()`]).
+    ///
+    /// A notifier that watches a course never becomes idle, so this only
+    /// returns on an error.
+    ///
+    /// # Errors
+    /// Fails like [`Notifier::poll()`], on the first poll that fails.
+    pub fn run(&mut self) -> Result<()> {
+        while !self.is_idle() {
+            if let Some(last_poll) = self.last_poll {
+                thread::sleep(self.interval.saturating_sub(last_poll.elapsed()));
+            }
+            self.poll()?;
+        }
+
+        Ok(())
+    }
+
+    /// Sends the events that haven't been sent yet to the webhooks, in order,
+    /// stopping at the first webhook that fails.
+    fn send_webhooks(&mut self) -> Result<()> {
+        let client = self.session.client();
+        while let Some(event) = self.unsent.front() {
+            let json = event.to_json(client.base_url());
+            while let Some(url) = self.webhooks.get(self.sent) {
+                client.post_json(url, &json)?;
+                self.sent += 1;
+            }
+
+            self.unsent.pop_front();
+            self.sent = 0;
+        }
+
+        Ok(())
+    }
+
+    fn poll_submissions(&mut self, events: &mut Vec<Event>) -> Result<()> {
+        let mut i = 0;
+        while i < self.submissions.len() {
+            let status = self.session.submission_status(&self.submissions[i])?;
+            match status.verdict().filter(|_| status.is_final()) {
+                Some(verdict) => events.push(Event::VerdictFinalized {
+                    submission: self.submissions.remove(i),
+                    verdict: verdict.clone(),
+                }),
+                None => i += 1,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn poll_courses(&mut self, events: &mut Vec<Event>) -> Result<()> {
+        for course in &mut self.courses {
+            let lists = self.session.course_lists(&course.course_id)?;
+            let first_poll = course.lists.is_none();
+            let mut known = course.lists.take().unwrap_or_default();
+
+            for list_id in lists {
+                if known.contains(&list_id) {
+                    continue;
+                }
+                match self.session.list_deadline(&course.course_id, &list_id) {
+                    Ok(Some(due)) => self.deadlines.push(Deadline::List {
+                        course_id: course.course_id.clone(),
+                        list_id: list_id.clone(),
+                        due,
+                    }),
+                    Ok(None) => {}
+                    Err(err) => {
+                        // The list is seen again in the next poll.
+                        course.lists = (!first_poll).then_some(known);
+                        return Err(err);
+                    }
+                }
+                if !first_poll {
+                    events.push(Event::ListPublished {
+                        course_id: course.course_id.clone(),
+                        list_id: list_id.clone(),
+                    });
+                }
+                known.insert(list_id);
+            }
+
+            course.lists = Some(known);
+        }
+
+        Ok(())
+    }
+
+    fn poll_deadlines(&mut self, events: &mut Vec<Event>) {
+        let now = Timestamp::now().unix();
+        let notice = i64::try_from(self.notice.as_secs()).unwrap_or(i64::MAX);
+
+        for deadline in &self.deadlines {
+            let left = deadline.start().unix() - now;
+            if left > 0 && left <= notice && self.notified.insert(deadline.clone()) {
+                events.push(Event::DeadlineApproaching(deadline.clone()));
+            }
+        }
+    }
+}
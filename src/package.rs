@@ -0,0 +1,1092 @@
+//! The packages of <https://jutge.org> problems, as written by their setters:
+//! the statements (`problem.<lang>.tex`), the `handler.yml` and the test
+//! cases (pairs of `.inp` and `.cor` files).
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write};
+use std::fs;
+use std::path::{Component, Path};
+use std::str::FromStr;
+
+use crate::{Error, ProblemLanguage, Result, TestCase};
+
+/// The package of a problem.
+///
+/// Packages are read from a directory with [`Package::from_dir()`] (or from a
+/// zip archive with [`Package::from_zip()`]), and can be checked with
+/// [`Package::validate()`] before uploading them with
+/// [`Session::upload_package()`](crate::Session::upload_package).
+///
+/// Paths inside a package are relative to its root and separated by `/`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Package {
+    /// The statements of the problem, from its `problem.<lang>.tex` files,
+    /// sorted by language code.
+    pub statements: Vec<PackageStatement>,
+
+    /// The handler of the problem, from its `handler.yml`, if it has one.
+    pub handler: Option<Handler>,
+
+    /// The test cases of the problem, from its pairs of `<name>.inp` and
+    /// `<name>.cor` files, sorted by name.
+    pub test_cases: Vec<TestCase>,
+
+    /// The other files of the package, such as its solutions or the test
+    /// cases missing a file of their pair, by path.
+    pub files: BTreeMap<String, Vec<u8>>,
+}
+
+/// The statement of a problem in a language, in LaTeX.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageStatement {
+    /// The language of the statement.
+    pub language: ProblemLanguage,
+
+    /// The LaTeX source of the statement.
+    pub tex: String,
+}
+
+/// Something wrong with a [`Package`], as found by [`Package::validate()`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackageIssue {
+    /// The package has no statement.
+    NoStatement,
+
+    /// The package has no `handler.yml`.
+    NoHandler,
+
+    /// The package has no test cases.
+    NoTestCases,
+
+    /// A file looks like a statement, but isn't named after a valid language
+    /// (or isn't valid UTF-8). Contains its path.
+    InvalidStatement(String),
+
+    /// A test case has an `.inp` file, but no `.cor` file. Contains the name
+    /// of the test case.
+    MissingOutput(String),
+
+    /// A test case has a `.cor` file, but no `.inp` file. Contains the name
+    /// of the test case.
+    MissingInput(String),
+
+    /// The `handler.yml` has a key the site doesn't know. Contains the key.
+    UnknownHandlerKey(String),
+
+    /// The `handler.yml` has a value the site doesn't accept for its key.
+    InvalidHandlerValue {
+        /// The key of the value.
+        key: String,
+
+        /// The value given.
+        value: String,
+    },
+}
+
+impl Display for PackageIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoStatement => write!(f, "no problem.<lang>.tex statement"),
+            Self::NoHandler => write!(f, "no handler.yml"),
+            Self::NoTestCases => write!(f, "no test cases"),
+            Self::InvalidStatement(path) => write!(f, "{path} isn't a valid statement"),
+            Self::MissingOutput(name) => write!(f, "{name}.inp has no {name}.cor"),
+            Self::MissingInput(name) => write!(f, "{name}.cor has no {name}.inp"),
+            Self::UnknownHandlerKey(key) => write!(f, "unknown key in handler.yml: {key}"),
+            Self::InvalidHandlerValue { key, value } => {
+                write!(f, "invalid value for {key} in handler.yml: {value}")
+            }
+        }
+    }
+}
+
+impl Package {
+    /// The name of the handler file in a package.
+    pub const HANDLER_FILE: &'static str = "handler.yml";
+
+    /// Reads a package from a directory.
+    ///
+    /// Hidden files and directories, such as `.git`, are left out.
+    ///
+    /// # Errors
+    /// Returns [`Error::IoError`] if the directory can't be read, or
+    /// [`Error::InvalidPackage`] if its `handler.yml` is malformed.
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Self> {
+        fn add_dir(files: &mut BTreeMap<String, Vec<u8>>, dir: &Path, prefix: &str) -> Result<()> {
+            for entry in fs::read_dir(dir)? {
+                let entry = entry?;
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if name.starts_with('.') {
+                    continue;
+                }
+
+                let path = format!("{prefix}{name}");
+                if entry.file_type()?.is_dir() {
+                    add_dir(files, &entry.path(), &format!("{path}/"))?;
+                } else {
+                    files.insert(path, fs::read(entry.path())?);
+                }
+            }
+
+            Ok(())
+        }
+
+        let mut files = BTreeMap::new();
+        add_dir(&mut files, dir.as_ref(), "")?;
+        Self::from_files(files)
+    }
+
+    /// Writes the package to a directory, creating it if needed. Files
+    /// already in the directory are kept, unless the package overwrites
+    /// them.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidPackage`] if a path of the package would
+    /// escape the directory, or [`Error::IoError`] if a file can't be
+    /// written.
+    pub fn write_dir(&self, dir: impl AsRef<Path>) -> Result<()> {
+        let dir = dir.as_ref();
+        for (path, contents) in self.to_files() {
+            let relative = Path::new(&path);
+            if !relative
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)))
+            {
+                return Err(Error::InvalidPackage(format!("invalid path: {path}")));
+            }
+
+            let path = dir.join(relative);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, contents)?;
+        }
+
+        Ok(())
+    }
+
+    /// Gets the statement in a language, if the package has it.
+    #[must_use]
+    pub fn statement(&self, language: ProblemLanguage) -> Option<&str> {
+        self.statements
+            .iter()
+            .find(|statement| statement.language == language)
+            .map(|statement| statement.tex.as_str())
+    }
+
+    /// Checks the consistency of the package, returning everything wrong
+    /// with it, or nothing if it looks ready to be uploaded.
+    #[must_use]
+    pub fn validate(&self) -> Vec<PackageIssue> {
+        let mut issues = Vec::new();
+
+        if self.statements.is_empty() {
+            issues.push(PackageIssue::NoStatement);
+        }
+        match &self.handler {
+            None => issues.push(PackageIssue::NoHandler),
+            Some(handler) => issues.extend(handler.validate()),
+        }
+        if self.test_cases.is_empty() {
+            issues.push(PackageIssue::NoTestCases);
+        }
+
+        for path in self.files.keys() {
+            if statement_language(path).is_some() {
+                issues.push(PackageIssue::InvalidStatement(path.clone()));
+            } else if let Some(name) = path.strip_suffix(".inp") {
+                if !self.files.contains_key(&format!("{name}.cor")) {
+                    issues.push(PackageIssue::MissingOutput(name.into()));
+                }
+            } else if let Some(name) = path.strip_suffix(".cor") {
+                if !self.files.contains_key(&format!("{name}.inp")) {
+                    issues.push(PackageIssue::MissingInput(name.into()));
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Checks the consistency of the package, as [`Package::validate()`]
+    /// does, failing if anything is wrong with it.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidPackage`] with the issues found, if any.
+    pub fn check(&self) -> Result<()> {
+        let issues = self.validate();
+        if issues.is_empty() {
+            return Ok(());
+        }
+
+        let issues: Vec<_> = issues.iter().map(ToString::to_string).collect();
+        Err(Error::InvalidPackage(issues.join("; ")))
+    }
+
+    /// Sorts the files of a package into its parts.
+    fn from_files(mut files: BTreeMap<String, Vec<u8>>) -> Result<Self> {
+        let handler = match files.remove(Self::HANDLER_FILE) {
+            Some(contents) => Some(
+                String::from_utf8(contents)
+                    .map_err(|_| Error::InvalidPackage("handler.yml isn't valid UTF-8".into()))?
+                    .parse()?,
+            ),
+            None => None,
+        };
+
+        let mut statements = Vec::new();
+        let paths: Vec<_> = files.keys().cloned().collect();
+        for path in &paths {
+            let Some(language) =
+                statement_language(path).and_then(|code| code.as_bytes().try_into().ok())
+            else {
+                continue;
+            };
+            if let Ok(tex) = String::from_utf8(files[path].clone()) {
+                files.remove(path);
+                statements.push(PackageStatement { language, tex });
+            }
+        }
+        statements.sort_by_key(|statement| statement.language.code());
+
+        let mut test_cases = Vec::new();
+        for path in &paths {
+            let Some(name) = path.strip_suffix(".inp") else {
+                continue;
+            };
+            let output_path = format!("{name}.cor");
+            let (Some(input), Some(expected)) = (files.get(path), files.get(&output_path)) else {
+                continue;
+            };
+            let (Ok(input), Ok(expected)) = (
+                String::from_utf8(input.clone()),
+                String::from_utf8(expected.clone()),
+            ) else {
+                continue;
+            };
+
+            files.remove(path);
+            files.remove(&output_path);
+            test_cases.push(TestCase {
+                name: name.into(),
+                input,
+                expected,
+            });
+        }
+
+        Ok(Self {
+            statements,
+            handler,
+            test_cases,
+            files,
+        })
+    }
+
+    /// Lays out the parts of a package as files.
+    fn to_files(&self) -> BTreeMap<String, Vec<u8>> {
+        let mut files = self.files.clone();
+
+        for statement in &self.statements {
+            let code = statement.language.code();
+            files.insert(
+                format!("problem.{}.tex", String::from_utf8_lossy(&code)),
+                statement.tex.clone().into_bytes(),
+            );
+        }
+        if let Some(handler) = &self.handler {
+            files.insert(Self::HANDLER_FILE.into(), handler.to_string().into_bytes());
+        }
+        for case in &self.test_cases {
+            files.insert(
+                format!("{}.inp", case.name),
+                case.input.clone().into_bytes(),
+            );
+            files.insert(
+                format!("{}.cor", case.name),
+                case.expected.clone().into_bytes(),
+            );
+        }
+
+        files
+    }
+}
+
+/// Gets the language code of a path named like a statement,
+/// `problem.<code>.tex`, at the root of a package.
+fn statement_language(path: &str) -> Option<&str> {
+    path.strip_prefix("problem.")?
+        .strip_suffix(".tex")
+        .filter(|code| !code.contains(['.', '/']))
+}
+
+/// The `handler.yml` of a problem, which tells the site how to judge its
+/// submissions.
+///
+/// Only the flat `key: value` form used by the site is supported. The keys
+/// keep the order of their names, not the one of the original file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Handler {
+    entries: BTreeMap<String, String>,
+}
+
+impl Handler {
+    /// The keys the site knows.
+    pub const KEYS: &'static [&'static str] = &[
+        "handler",
+        "solution",
+        "source_modifier",
+        "compilers",
+        "checker",
+        "presentation_error",
+        "precision",
+        "relative_error",
+        "separator",
+        "starting",
+        "ending",
+        "invisible_main",
+        "func_name",
+    ];
+
+    /// The values the site accepts for the `handler` key.
+    pub const HANDLERS: &'static [&'static str] = &["std", "graphic", "quiz", "game", "circuits"];
+
+    /// The values the site accepts for the `checker` key.
+    pub const CHECKERS: &'static [&'static str] =
+        &["std", "loosy", "epsilon", "elastic", "elastic2", "external"];
+
+    /// Creates a `Handler` for the standard handler, which compares the
+    /// output of the submissions with the expected one.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the value of a key, if it's set.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+
+    /// Sets the value of a key.
+    #[must_use]
+    pub fn set(mut self, key: &str, value: &str) -> Self {
+        self.entries.insert(key.into(), value.into());
+        self
+    }
+
+    /// Removes a key, returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.entries.remove(key)
+    }
+
+    /// Gets the kind of the handler, which is `std` unless it's set.
+    #[must_use]
+    pub fn kind(&self) -> &str {
+        self.get("handler").unwrap_or("std")
+    }
+
+    /// Gets the checker that compares the outputs, which is `std` unless
+    /// it's set.
+    #[must_use]
+    pub fn checker(&self) -> &str {
+        self.get("checker").unwrap_or("std")
+    }
+
+    /// Gets the language of the solution of the problem, if it's set.
+    #[must_use]
+    pub fn solution(&self) -> Option<&str> {
+        self.get("solution")
+    }
+
+    /// Iterates over the keys and values of the handler, sorted by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Checks the keys and values of the handler.
+    fn validate(&self) -> Vec<PackageIssue> {
+        let mut issues = Vec::new();
+
+        for (key, value) in self.iter() {
+            if !Self::KEYS.contains(&key) {
+                issues.push(PackageIssue::UnknownHandlerKey(key.into()));
+                continue;
+            }
+
+            let allowed = match key {
+                "handler" => Self::HANDLERS,
+                "checker" => Self::CHECKERS,
+                _ => continue,
+            };
+            if !allowed.contains(&value) {
+                issues.push(PackageIssue::InvalidHandlerValue {
+                    key: key.into(),
+                    value: value.into(),
+                });
+            }
+        }
+
+        issues
+    }
+}
+
+impl Default for Handler {
+    fn default() -> Self {
+        Self {
+            entries: BTreeMap::from([("handler".into(), "std".into())]),
+        }
+    }
+}
+
+impl Display for Handler {
+    /// Formats the handler as a `handler.yml`, quoting the values that
+    /// wouldn't be read back as they are otherwise.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (key, value) in self.iter() {
+            let needs_quotes = value != value.trim()
+                || value.starts_with(['"', '\'', '#'])
+                || comment_start(value).is_some();
+            if !needs_quotes {
+                writeln!(f, "{key}: {value}")?;
+            } else if value.contains('\'') {
+                writeln!(f, "{key}: \"{value}\"")?;
+            } else {
+                writeln!(f, "{key}: '{value}'")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Handler {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut entries = BTreeMap::new();
+
+        for line in s.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line == "---" {
+                continue;
+            }
+
+            let malformed =
+                || Error::InvalidPackage(format!("malformed line in handler.yml: {line}"));
+            let (key, value) = line.split_once(':').ok_or_else(malformed)?;
+            let value = value.trim();
+            // Comments are only stripped outside of quoted values, which
+            // may contain a `#`.
+            let value = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let (quoted, rest) = value[1..].split_once(quote).ok_or_else(malformed)?;
+                    let rest = rest.trim_start();
+                    if !rest.is_empty() && !rest.starts_with('#') {
+                        return Err(malformed());
+                    }
+                    quoted
+                }
+                _ => match comment_start(value) {
+                    Some(comment) => value[..comment].trim_end(),
+                    None => value,
+                },
+            };
+
+            entries.insert(key.trim().into(), value.into());
+        }
+
+        Ok(Self { entries })
+    }
+}
+
+/// Finds where the comment of an unquoted value of a `handler.yml` starts,
+/// i.e., the first `#` at its start or after a whitespace.
+fn comment_start(value: &str) -> Option<usize> {
+    value.char_indices().find_map(|(i, c)| {
+        let starts_comment = c == '#'
+            && value[..i]
+                .chars()
+                .next_back()
+                .is_none_or(char::is_whitespace);
+        starts_comment.then_some(i)
+    })
+}
+
+/// Builds the skeleton of the statement of a new problem, in the LaTeX format
+/// of the site (`problem.<lang>.tex`) or as an HTML preview.
+///
+/// The texts of the sections are written in LaTeX, and are copied as they
+/// are to the LaTeX statement. The samples are rendered by the `\Sample`
+/// macro of the site, which reads them from the test cases of the package
+/// named `sample*`.
+///
+/// ```
+/// # use jutge::{Package, ProblemLanguage, StatementBuilder};
+/// let mut package = Package::default();
+/// StatementBuilder::new(ProblemLanguage::English, "Sum of two numbers")
+///     .statement("Write a program that reads two numbers and prints their sum.")
+///     .input("The input consists of two integers $a$ and $b$.")
+///     .output("Print $a + b$.")
+///     .sample("1 2\n", "3\n")
+///     .add_to(&mut package);
+///
+/// assert!(package.statement(ProblemLanguage::English).is_some());
+/// assert_eq!(package.test_cases[0].name, "sample-1");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatementBuilder {
+    language: ProblemLanguage,
+    title: String,
+    author: Option<String>,
+    statement: String,
+    input: String,
+    output: String,
+    observation: Option<String>,
+    scoring: Option<String>,
+    samples: Vec<TestCase>,
+}
+
+impl StatementBuilder {
+    /// Creates a `StatementBuilder` for a statement in a language, with the
+    /// given title and empty sections.
+    #[must_use]
+    pub fn new(language: ProblemLanguage, title: &str) -> Self {
+        Self {
+            language,
+            title: title.into(),
+            author: None,
+            statement: String::new(),
+            input: String::new(),
+            output: String::new(),
+            observation: None,
+            scoring: None,
+            samples: Vec::new(),
+        }
+    }
+
+    /// Sets the author of the problem.
+    #[must_use]
+    pub fn author(mut self, author: &str) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Sets the text of the statement, before its input and output
+    /// sections.
+    #[must_use]
+    pub fn statement(mut self, text: &str) -> Self {
+        self.statement = text.into();
+        self
+    }
+
+    /// Sets the description of the input.
+    #[must_use]
+    pub fn input(mut self, text: &str) -> Self {
+        self.input = text.into();
+        self
+    }
+
+    /// Sets the description of the output.
+    #[must_use]
+    pub fn output(mut self, text: &str) -> Self {
+        self.output = text.into();
+        self
+    }
+
+    /// Adds an observation (hints or remarks), shown after the samples.
+    #[must_use]
+    pub fn observation(mut self, text: &str) -> Self {
+        self.observation = Some(text.into());
+        self
+    }
+
+    /// Adds a scoring section, for partially-scored problems.
+    #[must_use]
+    pub fn scoring(mut self, text: &str) -> Self {
+        self.scoring = Some(text.into());
+        self
+    }
+
+    /// Adds a sample, named `sample-<n>` after the number of samples added.
+    #[must_use]
+    pub fn sample(mut self, input: &str, expected: &str) -> Self {
+        self.samples.push(TestCase {
+            name: format!("sample-{}", self.samples.len() + 1),
+            input: input.into(),
+            expected: expected.into(),
+        });
+        self
+    }
+
+    /// Renders the statement as the site expects it in `problem.<lang>.tex`.
+    #[must_use]
+    pub fn to_tex(&self) -> String {
+        let mut tex = format!("\\Problem{{{}}}\n", self.title);
+        let mut section = |command: &str, text: &str| {
+            let _ = write!(tex, "\n\\{command}\n");
+            if !text.trim().is_empty() {
+                let _ = write!(tex, "\n{}\n", text.trim());
+            }
+        };
+
+        section("Statement", &self.statement);
+        section("Input", &self.input);
+        section("Output", &self.output);
+        if let Some(scoring) = &self.scoring {
+            section("Scoring", scoring);
+        }
+        section("Sample", "");
+        if let Some(observation) = &self.observation {
+            section("Observation", observation);
+        }
+
+        tex
+    }
+
+    /// Renders the statement as a standalone HTML document, to preview it.
+    ///
+    /// The headings are in the language of the statement (or in English, if
+    /// it isn't known), and the texts are escaped, so their LaTeX is shown
+    /// as it is.
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        let [input, output, sample, observation, scoring] = headings(self.language);
+        let code = self.language.code();
+
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{title}</title>\n</head>\n<body>\n<h1>{title}</h1>\n",
+            String::from_utf8_lossy(&code),
+            title = escape_html(&self.title),
+        );
+        if let Some(author) = &self.author {
+            let _ = writeln!(html, "<p class=\"author\">{}</p>", escape_html(author));
+        }
+        let section = |html: &mut String, heading: Option<&str>, text: &str| {
+            if let Some(heading) = heading {
+                let _ = writeln!(html, "<h2>{heading}</h2>");
+            }
+            for paragraph in text.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+                let _ = writeln!(html, "<p>{}</p>", escape_html(paragraph));
+            }
+        };
+
+        section(&mut html, None, &self.statement);
+        section(&mut html, Some(input), &self.input);
+        section(&mut html, Some(output), &self.output);
+        if let Some(text) = &self.scoring {
+            section(&mut html, Some(scoring), text);
+        }
+        for (i, case) in self.samples.iter().enumerate() {
+            let _ = writeln!(
+                html,
+                "<h2>{sample} {}</h2>\n<pre class=\"input\">{}</pre>\n\
+                 <pre class=\"output\">{}</pre>",
+                i + 1,
+                escape_html(&case.input),
+                escape_html(&case.expected),
+            );
+        }
+        if let Some(text) = &self.observation {
+            section(&mut html, Some(observation), text);
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    /// Builds the statement, as stored in a [`Package`].
+    #[must_use]
+    pub fn build(&self) -> PackageStatement {
+        PackageStatement {
+            language: self.language,
+            tex: self.to_tex(),
+        }
+    }
+
+    /// Adds the statement to a package, replacing the one in its language if
+    /// there's one, along with its `problem.<lang>.yml` (with the title and
+    /// the author) and its samples (replacing the test cases with the same
+    /// names).
+    pub fn add_to(&self, package: &mut Package) {
+        package
+            .statements
+            .retain(|statement| statement.language != self.language);
+        package.statements.push(self.build());
+        package
+            .statements
+            .sort_by_key(|statement| statement.language.code());
+
+        let mut metadata = format!("title: {}\n", self.title);
+        if let Some(author) = &self.author {
+            let _ = writeln!(metadata, "author: {author}");
+        }
+        let code = self.language.code();
+        package.files.insert(
+            format!("problem.{}.yml", String::from_utf8_lossy(&code)),
+            metadata.into_bytes(),
+        );
+
+        package
+            .test_cases
+            .retain(|case| !self.samples.iter().any(|sample| sample.name == case.name));
+        package.test_cases.extend(self.samples.iter().cloned());
+        package.test_cases.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+}
+
+/// Gets the headings of the input, output, sample, observation and scoring
+/// sections of a statement in a language, as the site shows them.
+const fn headings(language: ProblemLanguage) -> [&'static str; 5] {
+    match language {
+        ProblemLanguage::Catalan => ["Entrada", "Sortida", "Exemple", "Observació", "Puntuació"],
+        ProblemLanguage::Spanish => ["Entrada", "Salida", "Ejemplo", "Observación", "Puntuación"],
+        ProblemLanguage::French => ["Entrée", "Sortie", "Exemple", "Observation", "Score"],
+        ProblemLanguage::German => ["Eingabe", "Ausgabe", "Beispiel", "Bemerkung", "Bewertung"],
+        ProblemLanguage::English | ProblemLanguage::Other(_) => {
+            ["Input", "Output", "Sample", "Observation", "Scoring"]
+        }
+    }
+}
+
+/// Escapes text so it can be written in HTML, both as the contents of an
+/// element and as the value of an attribute.
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(feature = "archive")]
+mod archive {
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::io::{Cursor, Read, Write};
+    use std::path::Path;
+
+    use zip::write::SimpleFileOptions;
+    use zip::{ZipArchive, ZipWriter};
+
+    use super::Package;
+    use crate::Result;
+
+    impl Package {
+        /// Reads a package from a zip archive, such as the ones made by
+        /// [`zip_problem_dir()`].
+        ///
+        /// Directories and entries whose paths would escape the package are
+        /// left out.
+        ///
+        /// # Errors
+        /// Returns [`Error::ZipError`](crate::Error::ZipError) if the archive
+        /// is malformed, or [`Error::InvalidPackage`](crate::Error::InvalidPackage)
+        /// if its `handler.yml` is malformed.
+        pub fn from_zip(zip: &[u8]) -> Result<Self> {
+            let mut archive = ZipArchive::new(Cursor::new(zip))?;
+
+            let mut files = BTreeMap::new();
+            for i in 0..archive.len() {
+                let mut entry = archive.by_index(i)?;
+                if entry.is_dir() {
+                    continue;
+                }
+                let Some(path) = entry.enclosed_name() else {
+                    continue;
+                };
+
+                let path: Vec<_> = path
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy().into_owned())
+                    .collect();
+                let mut contents = Vec::new();
+                entry.read_to_end(&mut contents)?;
+                files.insert(path.join("/"), contents);
+            }
+
+            Self::from_files(files)
+        }
+
+        /// Packs the package into a zip archive, as expected by
+        /// [`Session::upload_problem()`](crate::Session::upload_problem).
+        ///
+        /// # Errors
+        /// Returns [`Error::ZipError`](crate::Error::ZipError) if the archive
+        /// can't be written.
+        pub fn to_zip(&self) -> Result<Vec<u8>> {
+            let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+            for (path, contents) in self.to_files() {
+                zip.start_file(path, SimpleFileOptions::default())?;
+                zip.write_all(&contents)?;
+            }
+            Ok(zip.finish()?.into_inner())
+        }
+    }
+
+    /// Packs a directory with the sources of a problem (its statements, its
+    /// test cases and its `handler.yml`) into a zip archive, as expected by
+    /// [`Session::upload_problem()`](crate::Session::upload_problem).
+    ///
+    /// Hidden files and directories, such as `.git`, are left out.
+    ///
+    /// # Errors
+    /// Returns [`Error::IoError`](crate::Error::IoError) if the directory
+    /// can't be read, or [`Error::ZipError`](crate::Error::ZipError) if the
+    /// archive can't be written.
+    pub fn zip_problem_dir(dir: impl AsRef<Path>) -> Result<Vec<u8>> {
+        fn add_dir(zip: &mut ZipWriter<Cursor<Vec<u8>>>, dir: &Path, prefix: &str) -> Result<()> {
+            let mut entries = fs::read_dir(dir)?.collect::<std::io::Result<Vec<_>>>()?;
+            entries.sort_by_key(fs::DirEntry::file_name);
+
+            for entry in entries {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if name.starts_with('.') {
+                    continue;
+                }
+
+                let path = format!("{prefix}{name}");
+                if entry.file_type()?.is_dir() {
+                    add_dir(zip, &entry.path(), &format!("{path}/"))?;
+                } else {
+                    zip.start_file(path, SimpleFileOptions::default())?;
+                    zip.write_all(&fs::read(entry.path())?)?;
+                }
+            }
+
+            Ok(())
+        }
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        add_dir(&mut zip, dir.as_ref(), "")?;
+        Ok(zip.finish()?.into_inner())
+    }
+}
+
+#[cfg(feature = "archive")]
+pub use archive::zip_problem_dir;
+
+#[cfg(feature = "client-auth")]
+mod upload {
+    use super::Package;
+    use crate::problem_id_types::Unlocalized;
+    use crate::{ProblemId, Result, Session};
+
+    impl Session {
+        /// Checks a package and uploads it as a new problem, as
+        /// [`Session::upload_problem()`] does.
+        ///
+        /// Returns the id given to the problem by the site.
+        ///
+        /// # Errors
+        /// Returns [`Error::InvalidPackage`](crate::Error::InvalidPackage) if
+        /// [`Package::validate()`] finds any issue, without uploading it.
+        /// Otherwise, fails like [`Session::upload_problem()`].
+        pub fn upload_package(&self, package: &Package) -> Result<ProblemId<Unlocalized>> {
+            package.check()?;
+            self.upload_problem(&package.to_zip()?)
+        }
+
+        /// Checks a package and replaces the one of a problem with it, as
+        /// [`Session::update_problem()`] does.
+        ///
+        /// # Errors
+        /// Returns [`Error::InvalidPackage`](crate::Error::InvalidPackage) if
+        /// [`Package::validate()`] finds any issue, without uploading it.
+        /// Otherwise, fails like [`Session::update_problem()`].
+        pub fn update_package(&self, id: &ProblemId<Unlocalized>, package: &Package) -> Result<()> {
+            package.check()?;
+            self.update_problem(id, &package.to_zip()?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{Handler, Package, PackageIssue};
+    use crate::{ProblemLanguage, TestCase};
+
+    fn handler(yml: &str) -> Handler {
+        yml.parse().unwrap()
+    }
+
+    #[test]
+    fn handler_values() {
+        let handler =
+            handler("---\nhandler: std\nchecker:   epsilon  \nsolution: C++\nprecision: 1e-6\n");
+        assert_eq!(handler.kind(), "std");
+        assert_eq!(handler.checker(), "epsilon");
+        assert_eq!(handler.solution(), Some("C++"));
+        assert_eq!(handler.get("precision"), Some("1e-6"));
+        assert_eq!(handler.get("separator"), None);
+    }
+
+    #[test]
+    fn quoted_handler_values() {
+        let handler = handler(
+            "separator: \" #\"\nstarting: 'a: b'\nending: \"it's\" # the end\nfunc_name: \"\"\n",
+        );
+        assert_eq!(handler.get("separator"), Some(" #"));
+        assert_eq!(handler.get("starting"), Some("a: b"));
+        assert_eq!(handler.get("ending"), Some("it's"));
+        assert_eq!(handler.get("func_name"), Some(""));
+    }
+
+    #[test]
+    fn handler_comments() {
+        let handler = handler(
+            "# The handler of P1\n\nhandler: std # the default\n  # indented\n\
+             checker: loosy\t# tabbed\nsolution: C#\ncompilers: #none\n",
+        );
+        assert_eq!(
+            handler.iter().collect::<Vec<_>>(),
+            [
+                ("checker", "loosy"),
+                ("compilers", ""),
+                ("handler", "std"),
+                ("solution", "C#"),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_handlers() {
+        for yml in ["handler std", "separator: \"unclosed", "separator: 'a' b"] {
+            assert!(yml.parse::<Handler>().is_err(), "{yml}");
+        }
+    }
+
+    #[test]
+    fn handlers_round_trip() {
+        let handler = Handler::new()
+            .set("checker", "std")
+            .set("separator", " #")
+            .set("starting", "'")
+            .set("ending", "# end")
+            .set("func_name", "main");
+        assert_eq!(handler.to_string().parse::<Handler>().unwrap(), handler);
+    }
+
+    #[test]
+    fn unknown_handler_keys_and_values() {
+        let handler = handler("handler: fancy\nchecker: epsilon\ncolour: blue\n");
+        assert_eq!(
+            handler.validate(),
+            [
+                PackageIssue::UnknownHandlerKey("colour".into()),
+                PackageIssue::InvalidHandlerValue {
+                    key: "handler".into(),
+                    value: "fancy".into(),
+                },
+            ]
+        );
+        assert_eq!(Handler::new().validate(), []);
+    }
+
+    fn files(files: &[(&str, &str)]) -> BTreeMap<String, Vec<u8>> {
+        files
+            .iter()
+            .map(|(path, contents)| ((*path).into(), contents.as_bytes().to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn package_parts() {
+        let package = Package::from_files(files(&[
+            ("handler.yml", "handler: std\n"),
+            ("problem.en.tex", "\\Statement"),
+            ("problem.ca.tex", "\\Enunciat"),
+            ("sample.inp", "1 2\n"),
+            ("sample.cor", "3\n"),
+            ("solution.cc", "int main() {}\n"),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            package.statement(ProblemLanguage::English),
+            Some("\\Statement")
+        );
+        assert_eq!(
+            package.statement(ProblemLanguage::Catalan),
+            Some("\\Enunciat")
+        );
+        assert_eq!(package.handler, Some(Handler::new()));
+        assert_eq!(
+            package.test_cases,
+            [TestCase {
+                name: "sample".into(),
+                input: "1 2\n".into(),
+                expected: "3\n".into(),
+            }]
+        );
+        assert_eq!(package.files, files(&[("solution.cc", "int main() {}\n")]));
+        assert_eq!(package.validate(), []);
+    }
+
+    #[test]
+    fn packages_round_trip() {
+        let original = files(&[
+            (
+                "handler.yml",
+                "checker: epsilon\nhandler: std\nseparator: ' #'\n",
+            ),
+            ("problem.es.tex", "\\Enunciado"),
+            ("problem.xx1.tex", "not a statement"),
+            ("a/sample-1.inp", "1\n"),
+            ("a/sample-1.cor", "1\n"),
+            ("lonely.inp", "2\n"),
+            ("binary.png", "\u{89}PNG"),
+        ]);
+
+        let package = Package::from_files(original.clone()).unwrap();
+        assert_eq!(package.to_files(), original);
+        assert_eq!(Package::from_files(package.to_files()).unwrap(), package);
+    }
+
+    #[test]
+    fn package_issues() {
+        assert_eq!(
+            Package::default().validate(),
+            [
+                PackageIssue::NoStatement,
+                PackageIssue::NoHandler,
+                PackageIssue::NoTestCases,
+            ]
+        );
+
+        let package = Package::from_files(files(&[
+            ("handler.yml", "handler: std\nchecker: strict\n"),
+            ("problem.en.tex", "\\Statement"),
+            ("problem.english.tex", "\\Statement"),
+            ("1.inp", "1\n"),
+            ("1.cor", "1\n"),
+            ("2.inp", "2\n"),
+            ("3.cor", "3\n"),
+        ]))
+        .unwrap();
+        assert_eq!(
+            package.validate(),
+            [
+                PackageIssue::InvalidHandlerValue {
+                    key: "checker".into(),
+                    value: "strict".into(),
+                },
+                PackageIssue::MissingOutput("2".into()),
+                PackageIssue::MissingInput("3".into()),
+                PackageIssue::InvalidStatement("problem.english.tex".into()),
+            ]
+        );
+        assert!(package.check().is_err());
+    }
+}
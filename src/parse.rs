@@ -0,0 +1,194 @@
+//! Parsers of the pages of <https://jutge.org>, for pages fetched without a
+//! [`Client`](crate::Client).
+//!
+//! Each parser takes the HTML of a page, as saved or fetched with any HTTP
+//! client, and the same arguments as the method that fetches it, which tell
+//! the page apart and fill the fields of the result that aren't in the page.
+//! They're shorthands for the [`parse()`](Operation::parse) of the operations
+//! in [`sans_io`](crate::sans_io), with the page taken to be at its usual URL
+//! in <https://jutge.org>.
+//!
+//! ```
+//! use jutge::parse_problem_info;
+//!
+//! let html = "<h1>P68688 · Sum of two numbers</h1>";
+//! let info = parse_problem_info(&"P068688_en".parse()?, html)?;
+//! assert_eq!(info.title, "Sum of two numbers");
+//! # Ok::<(), jutge::Error>(())
+//! ```
+//!
+//! The sample test cases, which come in a zip archive instead of a page, are
+//! parsed with [`samples_from_zip()`](crate::samples_from_zip).
+
+use crate::problem_id_types::{Localized, Unlocalized};
+use crate::sans_io::{
+    FetchAlreadyAccepted, FetchAnnouncements, FetchChallenges, FetchCourseLists,
+    FetchCourseProgress, FetchEnrolledCourses, FetchExamScore, FetchExamSubmissions,
+    FetchExamWindow, FetchHome, FetchListDeadline, FetchListProblems, FetchListScore,
+    FetchMessages, FetchProblemInfo, FetchProblemStats, FetchPublicProblems, FetchSetterStatus,
+    FetchStatement, FetchSubmission, FetchSubmissionHistory, FetchSubmissionStatus,
+    FetchSubmissions, FetchUserProfile, FetchUserSearch, Operation, Response,
+};
+use crate::{
+    Announcement, Challenge, CourseProgress, EnrolledCourse, ExamSubmission, ExamWindow,
+    HistoryEntry, Home, ListEntry, Message, ProblemId, ProblemInfo, ProblemStats, PublicProblem,
+    PublicProfile, Result, Score, SetterStatus, Statement, Submission, SubmissionId,
+    SubmissionStatus, Timestamp, UserSummary,
+};
+
+/// The site the pages are taken to come from.
+const SITE_URL: &str = "https://jutge.org";
+
+/// Parses a page with an operation, as if it was the response to its
+/// request.
+fn parse<O: Operation>(operation: &O, html: &str) -> Result<O::Output> {
+    let url = format!("{SITE_URL}{}", operation.request().path);
+    operation.parse(&Response {
+        url: &url,
+        body: html.as_bytes(),
+    })
+}
+
+macro_rules! parsers {
+    ($(
+        $(#[$doc:meta])*
+        $name:ident($($arg:ident: $ty:ty),*) -> $output:ty = $operation:expr;
+    )+) => {
+        $(
+            $(#[$doc])*
+            ///
+            /// # Errors
+            /// Returns [`Error::NotAuthenticated`](crate::Error::NotAuthenticated)
+            /// if the page is the login form, and otherwise the same errors
+            /// as the method that fetches it, such as
+            /// [`Error::UnexpectedPageLayout`](crate::Error::UnexpectedPageLayout).
+            pub fn $name($($arg: $ty,)* html: &str) -> Result<$output> {
+                parse(&$operation, html)
+            }
+        )+
+    };
+}
+
+parsers! {
+    /// Parses the page of a problem into its general information, like
+    /// [`Client::problem_info()`](crate::Client::problem_info).
+    parse_problem_info(problem: &ProblemId<Localized>) -> ProblemInfo =
+        FetchProblemInfo { problem: problem.clone() };
+
+    /// Parses the page of a problem into its statement, like
+    /// [`Client::statement()`](crate::Client::statement).
+    parse_statement(problem: &ProblemId<Localized>) -> Statement =
+        FetchStatement { problem: problem.clone() };
+
+    /// Parses the statistics page of a problem, like
+    /// [`Client::problem_stats()`](crate::Client::problem_stats).
+    parse_problem_stats(problem: &ProblemId<Localized>) -> ProblemStats =
+        FetchProblemStats { problem: problem.clone() };
+
+    /// Parses the page of a submission into its status, like
+    /// [`Session::submission_status()`](crate::Session::submission_status).
+    parse_submission_status(id: &SubmissionId) -> SubmissionStatus =
+        FetchSubmissionStatus { id: id.clone() };
+
+    /// Parses the page of a submission, like
+    /// [`Session::submission()`](crate::Session::submission).
+    parse_submission(id: &SubmissionId) -> Submission =
+        FetchSubmission { id: id.clone() };
+
+    /// Parses the page with the submissions of the logged-in user to a
+    /// problem, like [`Session::submissions()`](crate::Session::submissions).
+    parse_submissions_page(problem: &ProblemId<Localized>) -> Vec<SubmissionId> =
+        FetchSubmissions { problem: problem.clone() };
+
+    /// Parses the page with the submissions of the logged-in user to a
+    /// problem into whether one was accepted, like
+    /// [`Session::already_accepted()`](crate::Session::already_accepted).
+    parse_already_accepted(problem: &ProblemId<Localized>) -> bool =
+        FetchAlreadyAccepted { problem: problem.clone() };
+
+    /// Parses a page of the public problem index, like
+    /// [`Client::all_public_problems()`](crate::Client::all_public_problems).
+    parse_public_problems(page: u32) -> Vec<PublicProblem> =
+        FetchPublicProblems { page };
+
+    /// Parses the landing page of the site, like
+    /// [`Client::home()`](crate::Client::home).
+    parse_home() -> Home = FetchHome;
+
+    /// Parses the challenges listed by the site, like
+    /// [`Client::current_challenges()`](crate::Client::current_challenges),
+    /// including the ones that ended.
+    parse_challenges() -> Vec<Challenge> = FetchChallenges;
+
+    /// Parses the announcements of the logged-in user, like
+    /// [`Session::announcements()`](crate::Session::announcements).
+    parse_announcements() -> Vec<Announcement> = FetchAnnouncements;
+
+    /// Parses the inbox of the logged-in user, like
+    /// [`Session::messages()`](crate::Session::messages).
+    parse_messages() -> Vec<Message> = FetchMessages;
+
+    /// Parses the page with all the submissions of the logged-in user, like
+    /// [`Session::submission_history()`](crate::Session::submission_history).
+    parse_submission_history() -> Vec<HistoryEntry> = FetchSubmissionHistory;
+
+    /// Parses the courses the logged-in user is enrolled in, like
+    /// [`Session::enrolled_courses()`](crate::Session::enrolled_courses).
+    parse_enrolled_courses() -> Vec<EnrolledCourse> = FetchEnrolledCourses;
+
+    /// Parses the page of a list of problems into the points of the
+    /// logged-in user, like
+    /// [`Session::list_score()`](crate::Session::list_score).
+    parse_list_score(course_id: &str, list_id: &str) -> Score =
+        FetchListScore { course_id: course_id.into(), list_id: list_id.into() };
+
+    /// Parses the page of a list of problems into its deadline, like
+    /// [`Session::list_deadline()`](crate::Session::list_deadline).
+    parse_list_deadline(course_id: &str, list_id: &str) -> Option<Timestamp> =
+        FetchListDeadline { course_id: course_id.into(), list_id: list_id.into() };
+
+    /// Parses the page of a list of problems into its problems, like
+    /// [`Session::list_problems()`](crate::Session::list_problems).
+    parse_list_problems(course_id: &str, list_id: &str) -> Vec<ListEntry> =
+        FetchListProblems { course_id: course_id.into(), list_id: list_id.into() };
+
+    /// Parses the page of a course into its lists of problems, like
+    /// [`Session::course_lists()`](crate::Session::course_lists).
+    parse_course_lists(course_id: &str) -> Vec<String> =
+        FetchCourseLists { course_id: course_id.into() };
+
+    /// Parses the progress of the students of a course, like
+    /// [`Session::course_progress()`](crate::Session::course_progress).
+    parse_course_progress(course_id: &str) -> CourseProgress =
+        FetchCourseProgress { course_id: course_id.into() };
+
+    /// Parses the results of a search of users, like
+    /// [`Client::find_user()`](crate::Client::find_user).
+    parse_user_search(query: &str) -> Vec<UserSummary> =
+        FetchUserSearch { query: query.into() };
+
+    /// Parses the public profile of a user, like
+    /// [`Client::user_profile()`](crate::Client::user_profile).
+    parse_user_profile(username: &str) -> PublicProfile =
+        FetchUserProfile { username: username.into() };
+
+    /// Parses the status of a problem as seen by its setters, like
+    /// [`Session::setter_status()`](crate::Session::setter_status).
+    parse_setter_status(problem: &ProblemId<Unlocalized>) -> SetterStatus =
+        FetchSetterStatus { problem: problem.clone() };
+
+    /// Parses the page of an exam into the points of the logged-in user,
+    /// like [`Session::exam_score()`](crate::Session::exam_score).
+    parse_exam_score(exam_id: &str) -> Score =
+        FetchExamScore { exam_id: exam_id.into() };
+
+    /// Parses the page of an exam into its time window, like
+    /// [`Session::exam_window()`](crate::Session::exam_window).
+    parse_exam_window(exam_id: &str) -> ExamWindow =
+        FetchExamWindow { exam_id: exam_id.into() };
+
+    /// Parses the submissions made in an exam, like
+    /// [`Session::exam_submissions()`](crate::Session::exam_submissions).
+    parse_exam_submissions(exam_id: &str) -> Vec<ExamSubmission> =
+        FetchExamSubmissions { exam_id: exam_id.into() };
+}
@@ -0,0 +1,192 @@
+//! Results of parsing pages that only partly match the layout the crate
+//! expects.
+
+use std::fmt::Display;
+
+use crate::{Error, Result};
+
+/// What was parsed from a page, even if some of its fields couldn't be.
+///
+/// The fields that failed are listed in [`Partial::failures`], and hold a
+/// default value in [`Partial::value`]: an empty string or a zero, as
+/// documented by each operation. This lets tools such as archivers keep
+/// most of the data of a page when the site changes the layout of one of
+/// its sections.
+///
+/// This is returned by
+/// [`Client::execute_partial()`](crate::Client::execute_partial).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Partial<T> {
+    /// The parsed value, with defaults in the fields that failed.
+    pub value: T,
+
+    /// The fields that couldn't be parsed.
+    pub failures: Vec<FieldFailure>,
+}
+
+/// A field of a page that couldn't be parsed, in a [`Partial`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FieldFailure {
+    /// The name of the field, such as `title`.
+    pub field: &'static str,
+
+    /// Why the field couldn't be parsed, such as `no title found`.
+    pub reason: String,
+}
+
+impl Display for FieldFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.reason)
+    }
+}
+
+impl<T> Partial<T> {
+    /// Creates a `Partial` where every field was parsed.
+    #[must_use]
+    pub const fn complete(value: T) -> Self {
+        Self {
+            value,
+            failures: Vec::new(),
+        }
+    }
+
+    /// Checks whether every field was parsed.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Converts the value, keeping the failures.
+    #[must_use]
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Partial<U> {
+        Partial {
+            value: f(self.value),
+            failures: self.failures,
+        }
+    }
+
+    /// Gets the value if every field was parsed.
+    ///
+    /// # Errors
+    /// Returns [`Error::UnexpectedPageLayout`] for `page` with the first
+    /// failure, if some field couldn't be parsed.
+    pub fn into_result(self, page: impl Into<String>) -> Result<T> {
+        match self.failures.first() {
+            None => Ok(self.value),
+            Some(failure) => Err(Error::unexpected_layout(page, failure.reason.clone())),
+        }
+    }
+
+    /// Records that a field couldn't be parsed.
+    pub(crate) fn fail(&mut self, field: &'static str, reason: impl Into<String>) {
+        self.failures.push(FieldFailure {
+            field,
+            reason: reason.into(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Partial;
+    use crate::sans_io::{
+        FetchCourseProgress, FetchHome, FetchSetterStatus, FetchStatement, FetchSubmission,
+        Operation, Response,
+    };
+    use crate::{SubmissionId, SubmissionStatus, Verdict};
+
+    fn parse_partial<O: Operation>(operation: &O, html: &str) -> Partial<O::Output> {
+        let url = format!("https://jutge.org{}", operation.request().path);
+        let response = Response {
+            url: &url,
+            body: html.as_bytes(),
+        };
+        assert!(operation.parse(&response).is_err());
+        operation.parse_partial(&response).unwrap()
+    }
+
+    fn failed_fields<T>(partial: &Partial<T>) -> Vec<&str> {
+        partial
+            .failures
+            .iter()
+            .map(|failure| failure.field)
+            .collect()
+    }
+
+    #[test]
+    fn submission_without_status() {
+        let id = SubmissionId::new("P068688_en".parse().unwrap(), 1);
+        let submission = parse_partial(
+            &FetchSubmission { id: id.clone() },
+            "<table><tr><th>Feedback</th><td>Too slow</td></tr>\
+             <tr><th>Time</th><td>0.5 s</td></tr></table>",
+        );
+
+        assert_eq!(failed_fields(&submission), ["status"]);
+        assert_eq!(submission.value.id, id);
+        assert_eq!(
+            submission.value.status,
+            SubmissionStatus::Judged(Verdict::Unknown(String::new()))
+        );
+        assert_eq!(submission.value.feedback.as_deref(), Some("Too slow"));
+        assert!(submission.value.time.is_some());
+    }
+
+    #[test]
+    fn statement_not_found() {
+        let statement = parse_partial(
+            &FetchStatement {
+                problem: "P068688_en".parse().unwrap(),
+            },
+            "<h1>P68688 · Hello world!</h1>",
+        );
+
+        assert_eq!(failed_fields(&statement), ["html"]);
+        assert_eq!(statement.value.html(), "");
+    }
+
+    #[test]
+    fn course_progress_without_problems() {
+        let progress = parse_partial(
+            &FetchCourseProgress {
+                course_id: "PRO1".into(),
+            },
+            "<table><tr><th>Student</th><th>Exercise 1</th></tr>\
+             <tr><td>Ada</td><td>AC</td></tr></table>",
+        );
+
+        assert_eq!(failed_fields(&progress), ["problems"]);
+        assert!(progress.value.problems.is_empty());
+        assert_eq!(progress.value.students.len(), 1);
+        assert_eq!(progress.value.students[0].name, "Ada");
+    }
+
+    #[test]
+    fn setter_status_without_status() {
+        let status = parse_partial(
+            &FetchSetterStatus {
+                problem: "X012345".parse().unwrap(),
+            },
+            "<table><tr><th>Languages</th><td>ca, en</td></tr>\
+             <tr><th>Test cases</th><td>4 files</td></tr></table>",
+        );
+
+        assert_eq!(failed_fields(&status), ["published"]);
+        assert!(!status.value.published);
+        assert_eq!(status.value.languages.len(), 2);
+        assert_eq!(status.value.test_cases, Some(4));
+    }
+
+    #[test]
+    fn home_without_sections() {
+        let response = Response {
+            url: "https://jutge.org/",
+            body: b"<div id=\"news\"><article><h3>New problems</h3></article></div>",
+        };
+        let home = FetchHome.parse_partial(&response).unwrap();
+
+        assert_eq!(failed_fields(&home), ["featured", "activity"]);
+        assert_eq!(home.value.news.len(), 1);
+        assert_eq!(FetchHome.parse(&response).ok(), Some(home.value));
+    }
+}
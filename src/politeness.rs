@@ -0,0 +1,205 @@
+//! The politeness policy of a [`Client`](crate::Client), which keeps it
+//! from putting too much load on the site.
+
+use std::thread;
+use std::time::Duration;
+
+use crate::rate_limit::{ConnectionLimit, RateLimiter};
+use crate::Timestamp;
+
+/// How gently a [`Client`](crate::Client) crawls the site.
+///
+/// The policy applies to every request of the `Client` (and of its
+/// sessions): there's a minimum delay between page fetches and a maximum
+/// number of requests in flight at the same time. On top of that, the bulk
+/// crawls (such as [`Client::for_each_public_problem()`] or
+/// [`Client::samples_batch()`]) pace themselves with their own, more
+/// conservative, delay and concurrency, and can be restricted to off-peak
+/// hours.
+///
+/// The defaults leave the regular requests alone, and space the requests of
+/// bulk crawls by 1 second with at most 2 of them at the same time.
+///
+/// [`Client::for_each_public_problem()`]: crate::Client::for_each_public_problem
+/// [`Client::samples_batch()`]: crate::Client::samples_batch
+///
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use jutge::{Client, Politeness};
+///
+/// // Mirror the site only at night, with 5 seconds between pages.
+/// let politeness = Politeness::new()
+///     .bulk_delay(Duration::from_secs(5))
+///     .bulk_concurrency(1)
+///     .crawl_window(1, 7);
+/// let client = Client::builder().politeness(politeness).build();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Politeness {
+    min_delay: Duration,
+    max_connections: Option<usize>,
+    bulk_delay: Duration,
+    bulk_concurrency: usize,
+    crawl_window: Option<(u32, u32)>,
+}
+
+impl Politeness {
+    /// Creates a `Politeness` policy with the default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        const DEFAULT_BULK_DELAY: Duration = Duration::from_secs(1);
+        const DEFAULT_BULK_CONCURRENCY: usize = 2;
+
+        Self {
+            min_delay: Duration::ZERO,
+            max_connections: None,
+            bulk_delay: DEFAULT_BULK_DELAY,
+            bulk_concurrency: DEFAULT_BULK_CONCURRENCY,
+            crawl_window: None,
+        }
+    }
+
+    /// Sets the minimum time between two requests of the `Client`, whatever
+    /// they are. Defaults to none.
+    #[must_use]
+    pub fn min_delay(mut self, delay: Duration) -> Self {
+        self.min_delay = delay;
+        self
+    }
+
+    /// Sets the maximum number of requests the `Client` has in flight at the
+    /// same time, from all of its threads. Defaults to unlimited.
+    ///
+    /// A zero limit is taken as 1.
+    #[must_use]
+    pub fn max_connections(mut self, max: usize) -> Self {
+        self.max_connections = Some(max.max(1));
+        self
+    }
+
+    /// Sets the minimum time between two requests of bulk crawls. Defaults
+    /// to 1 second.
+    #[must_use]
+    pub fn bulk_delay(mut self, delay: Duration) -> Self {
+        self.bulk_delay = delay;
+        self
+    }
+
+    /// Sets the maximum number of requests bulk crawls have in flight at the
+    /// same time, whatever concurrency they're asked for. Defaults to 2.
+    ///
+    /// A zero limit is taken as 1.
+    #[must_use]
+    pub fn bulk_concurrency(mut self, max: usize) -> Self {
+        self.bulk_concurrency = max.max(1);
+        self
+    }
+
+    /// Restricts bulk crawls to the hours from `start` (included) to `end`
+    /// (excluded) in the Europe/Madrid timezone, where the site is hosted.
+    /// By default, they can run at any time.
+    ///
+    /// The window can wrap around midnight, such as from 22 to 6. Outside of
+    /// it, bulk crawls wait for it to open again before each request. Hours
+    /// are taken modulo 24, and a window that starts when it ends spans the
+    /// whole day.
+    #[must_use]
+    pub fn crawl_window(mut self, start: u32, end: u32) -> Self {
+        self.crawl_window = Some((start % 24, end % 24));
+        self
+    }
+
+    /// Checks whether bulk crawls may run at a given time.
+    #[must_use]
+    pub fn in_crawl_window(&self, time: Timestamp) -> bool {
+        self.until_crawl_window(time).is_zero()
+    }
+
+    /// Gets how long bulk crawls have to wait from a given time for the
+    /// crawl window to open, which is zero if it's open.
+    #[must_use]
+    pub fn until_crawl_window(&self, time: Timestamp) -> Duration {
+        const DAY: u32 = 86_400;
+
+        let Some((start, end)) = self.crawl_window else {
+            return Duration::ZERO;
+        };
+        let (_, _, _, hour, minute, second) = time.to_madrid();
+
+        let open = if start < end {
+            (start..end).contains(&hour)
+        } else {
+            start == end || hour >= start || hour < end
+        };
+        if open {
+            return Duration::ZERO;
+        }
+
+        let now = hour * 3600 + minute * 60 + second;
+        Duration::from_secs(u64::from((start * 3600 + DAY - now) % DAY))
+    }
+}
+
+impl Default for Politeness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The state of a [`Politeness`] policy, shared by a `Client` and its
+/// sessions.
+#[derive(Debug)]
+pub(crate) struct PolitenessLimits {
+    policy: Politeness,
+    connections: Option<ConnectionLimit>,
+    bulk: RateLimiter,
+}
+
+impl PolitenessLimits {
+    pub(crate) fn new(policy: Politeness) -> Self {
+        Self {
+            connections: policy.max_connections.map(ConnectionLimit::new),
+            bulk: RateLimiter::new(policy.bulk_delay),
+            policy,
+        }
+    }
+
+    pub(crate) const fn policy(&self) -> &Politeness {
+        &self.policy
+    }
+
+    /// Creates the limiter of the delay between all the requests.
+    pub(crate) fn throttle(&self) -> RateLimiter {
+        RateLimiter::new(self.policy.min_delay)
+    }
+
+    /// Runs a request once there's a connection available for it.
+    pub(crate) fn connect<T>(&self, request: impl FnOnce() -> T) -> T {
+        match &self.connections {
+            Some(connections) => connections.run(request),
+            None => request(),
+        }
+    }
+
+    /// Blocks until a bulk crawl may make its next request, returning how
+    /// long it waited.
+    pub(crate) fn pace_bulk(&self) -> Duration {
+        let mut waited = Duration::ZERO;
+        loop {
+            let wait = self.policy.until_crawl_window(Timestamp::now());
+            if wait.is_zero() {
+                break;
+            }
+            thread::sleep(wait);
+            waited += wait;
+        }
+        waited + self.bulk.wait()
+    }
+
+    /// Caps the concurrency a bulk crawl asks for.
+    pub(crate) fn bulk_concurrency(&self, requested: usize) -> usize {
+        requested.min(self.policy.bulk_concurrency)
+    }
+}
@@ -0,0 +1,199 @@
+//! Local compilation checks, to catch compilation errors before submitting a
+//! solution.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{Compiler, Error, Result};
+
+/// A command that compiles a source file locally.
+///
+/// The arguments can contain the placeholders `{source}`, which is replaced
+/// by the path of the source file, and `{output}`, which is replaced by the
+/// path of a scratch directory where the compiler can write its output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompilerCommand {
+    program: String,
+    args: Vec<String>,
+}
+
+impl CompilerCommand {
+    /// Creates a `CompilerCommand` that runs `program` without arguments.
+    #[must_use]
+    pub fn new(program: &str) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Adds an argument to the command.
+    #[must_use]
+    pub fn arg(mut self, arg: &str) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Adds several arguments to the command.
+    #[must_use]
+    pub fn args<'a>(mut self, args: impl IntoIterator<Item = &'a str>) -> Self {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Returns a command that mimics a compiler of the site, assuming its
+    /// usual executable is in the `PATH`.
+    ///
+    /// Returns `None` for [`Compiler::Other`].
+    #[must_use]
+    pub fn for_compiler(compiler: &Compiler) -> Option<Self> {
+        let command = match compiler {
+            Compiler::Gcc => Self::new("gcc").args(["-fsyntax-only", "{source}"]),
+            Compiler::Gxx => Self::new("g++").args(["-fsyntax-only", "{source}"]),
+            Compiler::Gxx11 => Self::new("g++").args(["-std=c++11", "-fsyntax-only", "{source}"]),
+            Compiler::Gxx17 => Self::new("g++").args(["-std=c++17", "-fsyntax-only", "{source}"]),
+            Compiler::Clang => Self::new("clang").args(["-fsyntax-only", "{source}"]),
+            Compiler::Python3 => Self::new("python3").args(["-m", "py_compile", "{source}"]),
+            Compiler::Java => Self::new("javac").args(["-d", "{output}", "{source}"]),
+            Compiler::Haskell => Self::new("ghc").args(["-fno-code", "{source}"]),
+            Compiler::Rust => Self::new("rustc").args([
+                "--edition=2021",
+                "--emit=metadata",
+                "--out-dir",
+                "{output}",
+                "{source}",
+            ]),
+            Compiler::Other(_) => return None,
+        };
+
+        Some(command)
+    }
+
+    /// Compiles `source` with this command.
+    ///
+    /// The source is written to a temporary file named `Main.<extension>`
+    /// (the name the site expects for Java sources), which is removed
+    /// afterwards.
+    ///
+    /// # Errors
+    /// Returns [`Error::LocalCompilationFailed`] with the output of the
+    /// compiler if it exits unsuccessfully, or [`Error::IoError`] if it can't
+    /// be run.
+    pub fn check(&self, source: &str, extension: &str) -> Result<()> {
+        let dir = ScratchDir::new()?;
+        let source_path = dir.path().join(format!("Main.{extension}"));
+        fs::write(&source_path, source)?;
+
+        let replace = |arg: &String| {
+            arg.replace("{source}", &source_path.to_string_lossy())
+                .replace("{output}", &dir.path().to_string_lossy())
+        };
+
+        let output = Command::new(&self.program)
+            .args(self.args.iter().map(replace))
+            .current_dir(dir.path())
+            .output()?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            let mut message = String::from_utf8_lossy(&output.stderr).into_owned();
+            message.push_str(&String::from_utf8_lossy(&output.stdout));
+            Err(Error::LocalCompilationFailed(message))
+        }
+    }
+}
+
+/// Compiles `source` locally with the default [`CompilerCommand`] of
+/// `compiler`.
+///
+/// # Errors
+/// Returns [`Error::LocalCompilationFailed`] if the source doesn't compile,
+/// or [`Error::IoError`] if the compiler can't be run. Sources for
+/// [`Compiler::Other`] are never checked.
+pub fn precheck(compiler: &Compiler, source: &str) -> Result<()> {
+    match CompilerCommand::for_compiler(compiler) {
+        Some(command) => command.check(source, compiler.extension()),
+        None => Ok(()),
+    }
+}
+
+/// A temporary directory, removed when dropped.
+pub(crate) struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    /// Creates a new directory, readable only by the user on Unix.
+    ///
+    /// The directory must not exist yet: in a shared temporary directory,
+    /// someone else could have created it (e.g. with links in it to the
+    /// files of the user), so another name is tried instead.
+    pub(crate) fn new() -> Result<Self> {
+        const MAX_ATTEMPTS: u32 = 100;
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+        let mut builder = fs::DirBuilder::new();
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+
+        let mut attempt = 1;
+        loop {
+            let nanos = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.subsec_nanos());
+            let path = std::env::temp_dir().join(format!(
+                "jutge-rs-{}-{}-{nanos:08x}",
+                std::process::id(),
+                COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+
+            match builder.create(&path) {
+                Ok(()) => return Ok(Self(path)),
+                Err(err)
+                    if err.kind() == io::ErrorKind::AlreadyExists && attempt < MAX_ATTEMPTS =>
+                {
+                    attempt += 1;
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScratchDir;
+
+    #[test]
+    fn new_scratch_dirs() {
+        let first = ScratchDir::new().unwrap();
+        let second = ScratchDir::new().unwrap();
+        assert_ne!(first.path(), second.path());
+        assert!(first.path().is_dir());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = first.path().metadata().unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+
+        let path = first.path().to_owned();
+        drop(first);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,23 @@
+//! The types most programs use, to import them all at once.
+//!
+//! ```
+//! use jutge::prelude::*;
+//!
+//! let id: ProblemId<Localized> = "P068688_en".parse()?;
+//! assert_eq!(id.language(), ProblemLanguage::English);
+//! # Ok::<(), Error>(())
+//! ```
+//!
+//! The `Result` of the crate isn't included, so it doesn't shadow the one of
+//! the standard library.
+
+pub use crate::problem_id_types::{Localized, Unlocalized};
+pub use crate::{
+    Compiler, Error, ProblemId, ProblemLanguage, ProblemType, Score, Submission, SubmissionId,
+    SubmissionStatus, Timestamp, Verdict,
+};
+
+#[cfg(feature = "web-client")]
+pub use crate::{Client, ClientBuilder, Problem};
+#[cfg(feature = "client-auth")]
+pub use crate::{Session, SubmissionRequest};
@@ -1,15 +1,25 @@
-use std::{convert::TryInto, fmt::Display, str::FromStr};
+use std::{
+    convert::TryInto,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+    str::FromStr,
+};
 
 use crate::{Error, Result};
 
 /// Represents a <https://jutge.org> problem type.
 ///
 /// Officially, the problem type is indicated by the letter in the problem id.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Problem types are ordered like their letters: [`ProblemType::Game`],
+/// [`ProblemType::Public`] and then [`ProblemType::Private`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ProblemType {
     /// A Game problem (G).
     ///
-    /// Game problems are publicly accessible. See [ProblemType::Public] for more information.
+    /// Game problems are publicly accessible. See [`ProblemType::Public`] for more information.
     Game,
 
     /// A public problem (P).
@@ -84,6 +94,15 @@ pub enum ProblemLanguage {
 
     /// The German language (de)
     German,
+
+    /// A language not known by this crate, identified by its 2 letter code.
+    ///
+    /// The code is expected to be made of lowercase ASCII letters, like the
+    /// ones used by the site.
+    ///
+    /// This allows handling problems in languages added to
+    /// <https://jutge.org> after this version of the crate was released.
+    Other([u8; 2]),
 }
 
 impl ProblemLanguage {
@@ -98,10 +117,25 @@ impl ProblemLanguage {
             Self::Spanish => *as_bytes!("es"),
             Self::French => *as_bytes!("fr"),
             Self::German => *as_bytes!("de"),
+            Self::Other(code) => *code,
         }
     }
 }
 
+impl PartialEq for ProblemLanguage {
+    fn eq(&self, other: &Self) -> bool {
+        self.code() == other.code()
+    }
+}
+
+impl Eq for ProblemLanguage {}
+
+impl Hash for ProblemLanguage {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.code().hash(state);
+    }
+}
+
 impl TryFrom<[u8; 2]> for ProblemLanguage {
     type Error = Error;
 
@@ -114,6 +148,7 @@ impl TryFrom<[u8; 2]> for ProblemLanguage {
             as_bytes!("es") => Ok(Self::Spanish),
             as_bytes!("fr") => Ok(Self::French),
             as_bytes!("de") => Ok(Self::German),
+            [a, b] if a.is_ascii_lowercase() && b.is_ascii_lowercase() => Ok(Self::Other(value)),
             _ => Err(Error::NotAProblemLanguage),
         }
     }
@@ -144,6 +179,7 @@ pub mod problem_id_types {
     }
 
     /// Used on non-localized problem ids
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
     pub struct Unlocalized(pub(super) [u8; 7]);
 
     #[sealed]
@@ -154,6 +190,7 @@ pub mod problem_id_types {
     }
 
     /// Used on localized problem ids
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
     pub struct Localized(pub(super) [u8; 10]);
 
     #[sealed]
@@ -168,11 +205,27 @@ pub mod problem_id_types {
 use problem_id_types::*;
 
 /// Type-safe representation of a problem id.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+///
+/// Problem ids are ordered by their [`ProblemType`], then by their numeric
+/// id and, if they're localized, by the code of their language. This is the
+/// same order as their string representations, since numeric ids always
+/// have 6 digits.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ProblemId<T: ProblemIdType>(T);
 
 impl<T: ProblemIdType> ProblemId<T> {
+    /// The smallest numeric id of the problems of the site. Problems are
+    /// numbered from 1, so an id of 0 is only accepted by the non-strict
+    /// constructors, such as [`ProblemId::new_unlocalized()`].
+    pub const MIN_NUMERIC: u32 = 1;
+
+    /// The largest numeric id that fits in a problem id, which has 6 digits.
+    pub const MAX_NUMERIC: u32 = 999_999;
+
     /// Gets the `ProblemType` of the problem id
+    ///
+    /// # Panics
+    /// Never panics, as a `ProblemId` always starts with a valid problem type.
     #[must_use]
     pub fn problem_type(&self) -> ProblemType {
         (self.0.representation()[0] as char)
@@ -183,17 +236,64 @@ impl<T: ProblemIdType> ProblemId<T> {
     /// Gets the numeric id of the problem id
     #[must_use]
     pub fn problem_id(&self) -> u32 {
-        self.0
-            .representation()
+        self.0.representation()[1..=6]
             .iter()
             .map(|x| x - b'0')
             .fold(0, |acc, x| acc * 10 + u32::from(x))
     }
+
+    /// Gets the problem id as a string slice, such as `P068688_en`, without
+    /// allocating.
+    ///
+    /// # Panics
+    /// Never panics, as a `ProblemId` is always made of ASCII characters.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(self.0.representation()).expect("Problem ids should be ASCII!")
+    }
 }
 
 impl<T: ProblemIdType> Display for ProblemId<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(String::from_utf8_lossy(self.0.representation()).as_ref())
+        f.write_str(self.as_str())
+    }
+}
+
+impl<T: ProblemIdType> AsRef<str> for ProblemId<T> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<T: ProblemIdType> From<ProblemId<T>> for String {
+    fn from(id: ProblemId<T>) -> Self {
+        id.as_str().to_owned()
+    }
+}
+
+/// Same as [`ProblemId::from_str()`], so ids can be parsed where a
+/// `TryFrom` conversion is expected.
+impl<T: ProblemIdType> TryFrom<&str> for ProblemId<T>
+where
+    Self: FromStr<Err = Error>,
+{
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self> {
+        s.parse()
+    }
+}
+
+/// Same as [`ProblemId::from_str()`], so ids can be parsed where a
+/// `TryFrom` conversion is expected.
+impl<T: ProblemIdType> TryFrom<String> for ProblemId<T>
+where
+    Self: FromStr<Err = Error>,
+{
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
     }
 }
 
@@ -203,8 +303,11 @@ impl ProblemId<Unlocalized> {
     /// # Errors
     /// The numeric id must be at most 6 digits long. If it isn't,
     /// [`Error::InvalidProblemId`] will be returned.
+    ///
+    /// # Panics
+    /// Never panics, as the formatted id is always 7 bytes long.
     pub fn new_unlocalized(pt: ProblemType, id: u32) -> Result<Self> {
-        if id < 1_000_000 {
+        if id <= Self::MAX_NUMERIC {
             Ok(Self(Unlocalized(
                 format!("{}{:06}", pt.letter(), id)
                     .into_bytes()
@@ -217,6 +320,87 @@ impl ProblemId<Unlocalized> {
             ))
         }
     }
+
+    /// Same as [`ProblemId::new_unlocalized()`], but also rejects the
+    /// numeric id 0, which no problem of the site has.
+    ///
+    /// # Errors
+    /// The numeric id must be between [`ProblemId::MIN_NUMERIC`] and
+    /// [`ProblemId::MAX_NUMERIC`]. If it isn't, [`Error::InvalidProblemId`]
+    /// will be returned.
+    pub fn new_unlocalized_strict(pt: ProblemType, id: u32) -> Result<Self> {
+        check_numeric_strict(id)?;
+        Self::new_unlocalized(pt, id)
+    }
+
+    /// Iterates over the unlocalized `ProblemId`s of a given type whose
+    /// numeric ids are in `ids`.
+    ///
+    /// Numeric ids that don't fit in a problem id (those over 6 digits) are
+    /// skipped.
+    pub fn range(pt: ProblemType, ids: impl RangeBounds<u32>) -> impl Iterator<Item = Self> {
+        let start = match ids.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match ids.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => u32::MAX,
+        };
+
+        (start..end.min(Self::MAX_NUMERIC + 1))
+            .filter_map(move |id| Self::new_unlocalized(pt, id).ok())
+    }
+
+    /// Gets the `ProblemId` with the same type and the next numeric id, if
+    /// there's one.
+    ///
+    /// This is the same as [`ProblemId::checked_next()`].
+    #[must_use]
+    pub fn next_id(&self) -> Option<Self> {
+        self.checked_next()
+    }
+
+    /// Gets the `ProblemId` with the same type and the next numeric id, or
+    /// `None` past [`ProblemId::MAX_NUMERIC`].
+    ///
+    /// Starting from [`ProblemId::MIN_NUMERIC`], this walks every valid id
+    /// of a type exactly once.
+    #[must_use]
+    pub fn checked_next(&self) -> Option<Self> {
+        let id = self.problem_id().checked_add(1)?;
+        Self::new_unlocalized(self.problem_type(), id).ok()
+    }
+
+    /// Gets the localized `ProblemId` of this problem in a given language.
+    ///
+    /// # Errors
+    /// The code of a [`ProblemLanguage::Other`] language must be made of 2
+    /// lowercase ASCII letters. If it isn't, [`Error::InvalidProblemId`] will
+    /// be returned.
+    pub fn localize(&self, lang: ProblemLanguage) -> Result<ProblemId<Localized>> {
+        ProblemId::new_localized(self.problem_type(), self.problem_id(), lang)
+    }
+
+    /// Leniently parses an unlocalized `ProblemId` from user input.
+    ///
+    /// Unlike [`ProblemId::from_str()`], this accepts lowercase letters,
+    /// numeric ids with less than 6 digits, surrounding whitespace and ids
+    /// embedded in longer strings, such as `p68688_en.cc`. Any language suffix
+    /// is ignored.
+    ///
+    /// # Errors
+    /// If no problem id can be found in `s`, [`Error::InvalidProblemId`] will
+    /// be returned.
+    pub fn parse_lenient(s: &str) -> Result<Self> {
+        let (pt, id, _) = scan_problem_ids(s)
+            .next()
+            .ok_or_else(|| Error::InvalidProblemId("no problem id found in string".into()))?;
+
+        Self::new_unlocalized(pt, id)
+    }
 }
 
 impl FromStr for ProblemId<Unlocalized> {
@@ -242,9 +426,16 @@ impl ProblemId<Localized> {
     /// Creates a localized `ProblemId` from a type, a numeric id and a language.
     ///
     /// # Errors
-    /// The numeric id must be at most 6 digits long. If it isn't,
-    /// [`Error::InvalidProblemId`] will be returned.
+    /// The numeric id must be at most 6 digits long, and the code of a
+    /// [`ProblemLanguage::Other`] language must be made of 2 lowercase ASCII
+    /// letters. If they aren't, [`Error::InvalidProblemId`] will be returned.
     pub fn new_localized(pt: ProblemType, id: u32, lang: ProblemLanguage) -> Result<Self> {
+        if ProblemLanguage::try_from(lang.code()).is_err() {
+            return Err(Error::InvalidProblemId(
+                "language code must be made of 2 lowercase letters".into(),
+            ));
+        }
+
         let internal = {
             let mut internal = [0; 10];
 
@@ -260,7 +451,80 @@ impl ProblemId<Localized> {
         Ok(Self(Localized(internal)))
     }
 
+    /// Same as [`ProblemId::new_localized()`], but also rejects the numeric
+    /// id 0, which no problem of the site has.
+    ///
+    /// # Errors
+    /// The numeric id must be between [`ProblemId::MIN_NUMERIC`] and
+    /// [`ProblemId::MAX_NUMERIC`], and the language must be valid as in
+    /// [`ProblemId::new_localized()`]. If they aren't,
+    /// [`Error::InvalidProblemId`] will be returned.
+    pub fn new_localized_strict(pt: ProblemType, id: u32, lang: ProblemLanguage) -> Result<Self> {
+        check_numeric_strict(id)?;
+        Self::new_localized(pt, id, lang)
+    }
+
+    /// Gets the `ProblemId` with the same type and language and the next
+    /// numeric id, if there's one.
+    ///
+    /// This is the same as [`ProblemId::checked_next()`].
+    #[must_use]
+    pub fn next_id(&self) -> Option<Self> {
+        self.checked_next()
+    }
+
+    /// Gets the `ProblemId` with the same type and language and the next
+    /// numeric id, or `None` past [`ProblemId::MAX_NUMERIC`].
+    #[must_use]
+    pub fn checked_next(&self) -> Option<Self> {
+        let id = self.problem_id().checked_add(1)?;
+        Self::new_localized(self.problem_type(), id, self.language()).ok()
+    }
+
+    /// Leniently parses a localized `ProblemId` from user input.
+    ///
+    /// Unlike [`ProblemId::from_str()`], this accepts lowercase and uppercase
+    /// letters, numeric ids with less than 6 digits, surrounding whitespace
+    /// and ids embedded in longer strings, such as `p68688_en.cc`.
+    ///
+    /// # Errors
+    /// If no localized problem id can be found in `s`,
+    /// [`Error::InvalidProblemId`] will be returned.
+    pub fn parse_lenient(s: &str) -> Result<Self> {
+        let (pt, id, lang) = scan_problem_ids(s)
+            .find_map(|(pt, id, rest)| {
+                let rest = rest.as_bytes();
+                match rest {
+                    [b'_', a, b, ..] if !rest.get(3).is_some_and(u8::is_ascii_alphabetic) => {
+                        let code = [a.to_ascii_lowercase(), b.to_ascii_lowercase()];
+                        Some((pt, id, ProblemLanguage::try_from(code).ok()?))
+                    }
+                    _ => None,
+                }
+            })
+            .ok_or_else(|| {
+                Error::InvalidProblemId("no localized problem id found in string".into())
+            })?;
+
+        Self::new_localized(pt, id, lang)
+    }
+
+    /// Gets the unlocalized `ProblemId` of the same problem.
+    ///
+    /// # Panics
+    /// Never panics, as a localized `ProblemId` always starts with a valid
+    /// unlocalized one.
+    #[must_use]
+    pub fn unlocalized(&self) -> ProblemId<Unlocalized> {
+        ProblemId::new_unlocalized(self.problem_type(), self.problem_id())
+            .expect("Localized problem id should contain a valid unlocalized one")
+    }
+
     /// Gets the `ProblemLanguage` of the `ProblemId`
+    ///
+    /// # Panics
+    /// Never panics, as a localized `ProblemId` always ends with a valid
+    /// problem language.
     #[must_use]
     pub fn language(&self) -> ProblemLanguage {
         let code = &self.0.representation()[8..=9];
@@ -295,3 +559,514 @@ impl FromStr for ProblemId<Localized> {
         Self::new_localized(pt, id, lang)
     }
 }
+
+/// Checks that a numeric id is in the range of the problems of the site.
+fn check_numeric_strict(id: u32) -> Result<()> {
+    if (ProblemId::<Unlocalized>::MIN_NUMERIC..=ProblemId::<Unlocalized>::MAX_NUMERIC).contains(&id)
+    {
+        Ok(())
+    } else {
+        Err(Error::InvalidProblemId(format!(
+            "numeric id must be between {} and {}",
+            ProblemId::<Unlocalized>::MIN_NUMERIC,
+            ProblemId::<Unlocalized>::MAX_NUMERIC,
+        )))
+    }
+}
+
+/// Scans a string for substrings that look like problem ids, yielding their
+/// type, their numeric id and the rest of the string after them.
+fn scan_problem_ids(s: &str) -> impl Iterator<Item = (ProblemType, u32, &str)> {
+    let bytes = s.as_bytes();
+
+    (0..bytes.len()).filter_map(move |start| {
+        if start > 0 && bytes[start - 1].is_ascii_alphanumeric() {
+            return None;
+        }
+
+        let pt = ProblemType::try_from(char::from(bytes[start].to_ascii_uppercase())).ok()?;
+        let digits = bytes[start + 1..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if !(1..=6).contains(&digits) {
+            return None;
+        }
+
+        let end = start + 1 + digits;
+        let id = s[start + 1..end].parse().ok()?;
+
+        Some((pt, id, &s[end..]))
+    })
+}
+
+/// A problem id parsed without checking that its type letter is known.
+///
+/// New problem types may be introduced by <https://jutge.org> after this
+/// version of the crate was released. A `RawProblemId` accepts any uppercase
+/// ASCII letter as the type, so tools that crawl the site don't stop working
+/// when that happens. It can be converted to a [`ProblemId`] once its type is
+/// known to be valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RawProblemId {
+    letter: char,
+    id: u32,
+    language: Option<ProblemLanguage>,
+}
+
+impl RawProblemId {
+    /// Gets the type letter of the problem id.
+    #[must_use]
+    pub const fn letter(&self) -> char {
+        self.letter
+    }
+
+    /// Gets the `ProblemType` of the problem id, if its letter is known.
+    #[must_use]
+    pub fn problem_type(&self) -> Option<ProblemType> {
+        self.letter.try_into().ok()
+    }
+
+    /// Gets the numeric id of the problem id.
+    #[must_use]
+    pub const fn problem_id(&self) -> u32 {
+        self.id
+    }
+
+    /// Gets the `ProblemLanguage` of the problem id, if it's localized.
+    #[must_use]
+    pub const fn language(&self) -> Option<ProblemLanguage> {
+        self.language
+    }
+}
+
+impl Display for RawProblemId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{:06}", self.letter, self.id)?;
+        if let Some(language) = self.language {
+            let [a, b] = language.code();
+            write!(f, "_{}{}", char::from(a), char::from(b))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RawProblemId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.len() != 7 && s.len() != 10 {
+            return Err(Error::InvalidProblemId(
+                "string should be 7 or 10 characters long".into(),
+            ));
+        }
+
+        let letter = char::from(s.as_bytes()[0]);
+        if !letter.is_ascii_uppercase() {
+            return Err(Error::InvalidProblemId(
+                "first character should be an uppercase letter".into(),
+            ));
+        }
+
+        let id = s
+            .get(1..=6)
+            .filter(|digits| digits.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|digits| digits.parse().ok())
+            .ok_or_else(|| Error::InvalidProblemId("characters 2 to 7 should be numeric".into()))?;
+
+        let language = match &s.as_bytes()[7..] {
+            [] => None,
+            [b'_', code @ ..] => Some(code.try_into()?),
+            _ => {
+                return Err(Error::InvalidProblemId(
+                    "unexpected character in problem id".into(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            letter,
+            id,
+            language,
+        })
+    }
+}
+
+impl From<ProblemId<Unlocalized>> for RawProblemId {
+    fn from(id: ProblemId<Unlocalized>) -> Self {
+        Self {
+            letter: id.problem_type().letter(),
+            id: id.problem_id(),
+            language: None,
+        }
+    }
+}
+
+impl From<ProblemId<Localized>> for RawProblemId {
+    fn from(id: ProblemId<Localized>) -> Self {
+        Self {
+            letter: id.problem_type().letter(),
+            id: id.problem_id(),
+            language: Some(id.language()),
+        }
+    }
+}
+
+impl TryFrom<RawProblemId> for ProblemId<Unlocalized> {
+    type Error = Error;
+
+    /// Converts the id, dropping its language if it has one.
+    fn try_from(raw: RawProblemId) -> Result<Self> {
+        Self::new_unlocalized(raw.letter.try_into()?, raw.id)
+    }
+}
+
+impl TryFrom<RawProblemId> for ProblemId<Localized> {
+    type Error = Error;
+
+    fn try_from(raw: RawProblemId) -> Result<Self> {
+        let language = raw
+            .language
+            .ok_or_else(|| Error::InvalidProblemId("problem id isn't localized".into()))?;
+
+        Self::new_localized(raw.letter.try_into()?, raw.id, language)
+    }
+}
+
+/// A borrowed problem id: a string slice checked to hold a problem id, such
+/// as `P068688_en`, without copying it.
+///
+/// This lets scrapers inspect the ids found in big pages, such as submission
+/// listings, and only convert the ones they keep into an owned [`ProblemId`]
+/// (with [`ProblemIdRef::to_problem_id()`] or [`From`]).
+///
+/// Only ids in the form printed by [`ProblemId`] are accepted: an uppercase
+/// type letter, 6 digits and, for localized ids, an underscore and a
+/// lowercase language code.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProblemIdRef<'a, T: ProblemIdType> {
+    id: &'a str,
+    kind: PhantomData<T>,
+}
+
+// Derived, these would only be implemented if `T` were `Copy`.
+impl<T: ProblemIdType> Clone for ProblemIdRef<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ProblemIdType> Copy for ProblemIdRef<'_, T> {}
+
+impl<'a, T: ProblemIdType> ProblemIdRef<'a, T> {
+    /// Gets the borrowed string slice.
+    #[must_use]
+    pub const fn as_str(&self) -> &'a str {
+        self.id
+    }
+
+    /// Gets the `ProblemType` of the problem id.
+    ///
+    /// # Panics
+    /// Never panics, as a `ProblemIdRef` always starts with a valid problem
+    /// type.
+    #[must_use]
+    pub fn problem_type(&self) -> ProblemType {
+        char::from(self.id.as_bytes()[0])
+            .try_into()
+            .expect("Problem ID first char should represent a Problem Type!")
+    }
+
+    /// Gets the numeric id of the problem id.
+    #[must_use]
+    pub fn problem_id(&self) -> u32 {
+        self.id.as_bytes()[1..=6]
+            .iter()
+            .fold(0, |acc, digit| acc * 10 + u32::from(digit - b'0'))
+    }
+
+    /// Copies the id into an owned [`ProblemId`].
+    #[must_use]
+    pub fn to_problem_id(&self) -> ProblemId<T>
+    where
+        ProblemId<T>: From<Self>,
+    {
+        ProblemId::from(*self)
+    }
+
+    const fn new_unchecked(id: &'a str) -> Self {
+        Self {
+            id,
+            kind: PhantomData,
+        }
+    }
+}
+
+impl<'a> ProblemIdRef<'a, Unlocalized> {
+    /// Checks that a string slice is an unlocalized problem id, such as
+    /// `P068688`.
+    ///
+    /// # Errors
+    /// If `s` isn't an unlocalized problem id, [`Error::InvalidProblemId`]
+    /// will be returned.
+    pub fn new(s: &'a str) -> Result<Self> {
+        check_unlocalized(s.as_bytes())?;
+        Ok(Self::new_unchecked(s))
+    }
+
+    /// Finds the unlocalized problem ids embedded in a string, without
+    /// copying them. The ids of localized problem ids are included, without
+    /// their language.
+    ///
+    /// Ids that are part of longer words or numbers aren't included.
+    pub fn scan(s: &'a str) -> impl Iterator<Item = Self> + 'a {
+        scan_canonical_ids(s).map(|start| Self::new_unchecked(&s[start..start + 7]))
+    }
+}
+
+impl<'a> ProblemIdRef<'a, Localized> {
+    /// Checks that a string slice is a localized problem id, such as
+    /// `P068688_en`.
+    ///
+    /// # Errors
+    /// If `s` isn't a localized problem id, [`Error::InvalidProblemId`] will
+    /// be returned.
+    pub fn new(s: &'a str) -> Result<Self> {
+        let bytes = s.as_bytes();
+        if bytes.len() != 10 {
+            return Err(Error::InvalidProblemId(
+                "string should be 10 characters long".into(),
+            ));
+        }
+        check_unlocalized(&bytes[..7])?;
+        if bytes[7] != b'_' || !bytes[8..].iter().all(u8::is_ascii_lowercase) {
+            return Err(Error::InvalidProblemId(
+                "problem id should end with `_` and a lowercase language code".into(),
+            ));
+        }
+
+        Ok(Self::new_unchecked(s))
+    }
+
+    /// Finds the localized problem ids embedded in a string, without copying
+    /// them.
+    ///
+    /// Ids that are part of longer words aren't included.
+    pub fn scan(s: &'a str) -> impl Iterator<Item = Self> + 'a {
+        scan_canonical_ids(s).filter_map(|start| {
+            let id = s.get(start..start + 10)?;
+            if s.as_bytes()
+                .get(start + 10)
+                .is_some_and(u8::is_ascii_alphanumeric)
+            {
+                return None;
+            }
+            Self::new(id).ok()
+        })
+    }
+
+    /// Gets the `ProblemLanguage` of the problem id.
+    ///
+    /// # Panics
+    /// Never panics, as a localized `ProblemIdRef` always ends with a valid
+    /// problem language.
+    #[must_use]
+    pub fn language(&self) -> ProblemLanguage {
+        self.id.as_bytes()[8..=9]
+            .try_into()
+            .expect("Problem id last two chars should represent a problem language")
+    }
+
+    /// Gets the unlocalized problem id of the same problem, borrowing the
+    /// same string.
+    #[must_use]
+    pub fn unlocalized(&self) -> ProblemIdRef<'a, Unlocalized> {
+        ProblemIdRef::new_unchecked(&self.id[..7])
+    }
+}
+
+impl<T: ProblemIdType> ProblemId<T> {
+    /// Borrows the problem id as a [`ProblemIdRef`].
+    #[must_use]
+    pub fn as_id_ref(&self) -> ProblemIdRef<'_, T> {
+        ProblemIdRef::new_unchecked(self.as_str())
+    }
+}
+
+impl From<ProblemIdRef<'_, Unlocalized>> for ProblemId<Unlocalized> {
+    fn from(id: ProblemIdRef<'_, Unlocalized>) -> Self {
+        Self(Unlocalized(id.as_str().as_bytes().try_into().expect(
+            "Unlocalized problem id refs should be 7 bytes long!",
+        )))
+    }
+}
+
+impl From<ProblemIdRef<'_, Localized>> for ProblemId<Localized> {
+    fn from(id: ProblemIdRef<'_, Localized>) -> Self {
+        Self(Localized(id.as_str().as_bytes().try_into().expect(
+            "Localized problem id refs should be 10 bytes long!",
+        )))
+    }
+}
+
+impl<T: ProblemIdType> Display for ProblemIdRef<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.id)
+    }
+}
+
+impl<T: ProblemIdType> AsRef<str> for ProblemIdRef<'_, T> {
+    fn as_ref(&self) -> &str {
+        self.id
+    }
+}
+
+impl<T: ProblemIdType> PartialEq<ProblemId<T>> for ProblemIdRef<'_, T> {
+    fn eq(&self, other: &ProblemId<T>) -> bool {
+        self.id == other.as_str()
+    }
+}
+
+impl<T: ProblemIdType> PartialEq<ProblemIdRef<'_, T>> for ProblemId<T> {
+    fn eq(&self, other: &ProblemIdRef<'_, T>) -> bool {
+        self.as_str() == other.id
+    }
+}
+
+/// Checks that a string starts with an unlocalized problem id in canonical
+/// form, and is 7 bytes long.
+fn check_unlocalized(bytes: &[u8]) -> Result<()> {
+    let [letter, digits @ ..] = bytes else {
+        return Err(Error::InvalidProblemId("string is empty".into()));
+    };
+    if !ProblemType::is_valid_letter(char::from(*letter)) {
+        return Err(Error::NotAProblemType);
+    }
+    if digits.len() != 6 || !digits.iter().all(u8::is_ascii_digit) {
+        return Err(Error::InvalidProblemId(
+            "problem id should have 6 digits after its type".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Finds where the unlocalized problem ids in canonical form embedded in a
+/// string start, skipping the ones that are part of longer words or numbers.
+fn scan_canonical_ids(s: &str) -> impl Iterator<Item = usize> + '_ {
+    let bytes = s.as_bytes();
+
+    (0..bytes.len().saturating_sub(6)).filter(move |&start| {
+        let before = start.checked_sub(1).map(|i| bytes[i]);
+        let after = bytes.get(start + 7);
+
+        !before.is_some_and(|b| b.is_ascii_alphanumeric())
+            && !after.is_some_and(u8::is_ascii_alphanumeric)
+            && check_unlocalized(&bytes[start..start + 7]).is_ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::problem_id_types::{Localized, Unlocalized};
+    use super::{ProblemId, ProblemIdRef, ProblemLanguage, ProblemType};
+
+    fn lenient(s: &str) -> Option<String> {
+        ProblemId::<Unlocalized>::parse_lenient(s)
+            .ok()
+            .map(|id| id.to_string())
+    }
+
+    fn lenient_localized(s: &str) -> Option<String> {
+        ProblemId::<Localized>::parse_lenient(s)
+            .ok()
+            .map(|id| id.to_string())
+    }
+
+    #[test]
+    fn lenient_unlocalized_ids() {
+        assert_eq!(lenient("P068688").as_deref(), Some("P068688"));
+        assert_eq!(lenient("  p68688 ").as_deref(), Some("P068688"));
+        assert_eq!(lenient("p68688_en.cc").as_deref(), Some("P068688"));
+        assert_eq!(lenient("solutions/x1.py").as_deref(), Some("X000001"));
+        assert_eq!(lenient("AP068688 then G12").as_deref(), Some("G000012"));
+        assert_eq!(lenient("P1234567"), None);
+        assert_eq!(lenient("Q068688"), None);
+        assert_eq!(lenient(""), None);
+    }
+
+    #[test]
+    fn lenient_localized_ids() {
+        assert_eq!(
+            lenient_localized("P68688_en").as_deref(),
+            Some("P068688_en")
+        );
+        assert_eq!(
+            lenient_localized(" x12345_CA.cc").as_deref(),
+            Some("X012345_ca")
+        );
+        assert_eq!(
+            lenient_localized("P1 and P2_es").as_deref(),
+            Some("P000002_es")
+        );
+        assert_eq!(lenient_localized("P68688_eng"), None);
+        assert_eq!(lenient_localized("P68688_e"), None);
+        assert_eq!(lenient_localized("P68688"), None);
+    }
+
+    #[test]
+    fn id_refs() {
+        let id = ProblemIdRef::<Localized>::new("P068688_en").unwrap();
+        assert_eq!(id.problem_type(), ProblemType::Public);
+        assert_eq!(id.problem_id(), 68688);
+        assert!(matches!(id.language(), ProblemLanguage::English));
+        assert_eq!(id.unlocalized().as_str(), "P068688");
+        assert_eq!(id.to_problem_id().to_string(), "P068688_en");
+
+        let id = ProblemIdRef::<Unlocalized>::new("X000001").unwrap();
+        assert_eq!(id.problem_id(), 1);
+        assert_eq!(ProblemId::from(id).to_string(), "X000001");
+    }
+
+    #[test]
+    fn invalid_id_refs() {
+        for id in ["", "P68688", "p068688", "Q068688", "P06868a", "P0686880"] {
+            assert!(ProblemIdRef::<Unlocalized>::new(id).is_err(), "{id}");
+        }
+        for id in [
+            "P068688",
+            "P068688_EN",
+            "P068688-en",
+            "P068688_e1",
+            "P068688_eng",
+        ] {
+            assert!(ProblemIdRef::<Localized>::new(id).is_err(), "{id}");
+        }
+    }
+
+    #[test]
+    fn scanned_id_refs() {
+        let text = "P068688_en, (X012345) AP000001 P0000012 and P000003_enx";
+
+        let unlocalized: Vec<_> = ProblemIdRef::<Unlocalized>::scan(text)
+            .map(|id| id.as_str())
+            .collect();
+        assert_eq!(unlocalized, ["P068688", "X012345", "P000003"]);
+
+        let localized: Vec<_> = ProblemIdRef::<Localized>::scan(text)
+            .map(|id| id.as_str())
+            .collect();
+        assert_eq!(localized, ["P068688_en"]);
+    }
+
+    #[test]
+    fn scanned_id_refs_followed_by_letters() {
+        assert_eq!(ProblemIdRef::<Unlocalized>::scan("P000001abc").count(), 0);
+        assert_eq!(ProblemIdRef::<Localized>::scan("P000001abc").count(), 0);
+
+        let ids: Vec<_> = ProblemIdRef::<Unlocalized>::scan("P000002x P000003_ca P000004")
+            .map(|id| id.as_str())
+            .collect();
+        assert_eq!(ids, ["P000003", "P000004"]);
+    }
+}
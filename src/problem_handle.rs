@@ -0,0 +1,140 @@
+use std::cell::{OnceCell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::problem_id_types::{Localized, Unlocalized};
+use crate::{
+    Client, ProblemId, ProblemInfo, ProblemLanguage, ProblemStats, Result, Statement, TestCase,
+};
+#[cfg(feature = "client-auth")]
+use crate::{Session, SubmissionId};
+
+/// A handle to a <https://jutge.org> problem, which fetches its data on
+/// first access and remembers it.
+///
+/// This is created by [`Client::problem()`]. Methods that don't take a
+/// language use the problem in the default language of the `Client` (see
+/// [`Client::localize()`]).
+///
+/// Failed fetches aren't remembered, so calling a method again after an
+/// error retries it.
+#[derive(Debug)]
+pub struct Problem<'a> {
+    client: &'a Client,
+    id: ProblemId<Unlocalized>,
+    info: OnceCell<ProblemInfo>,
+    statements: RefCell<HashMap<ProblemLanguage, Rc<Statement>>>,
+    samples: OnceCell<Vec<TestCase>>,
+    stats: OnceCell<ProblemStats>,
+    #[cfg(feature = "client-auth")]
+    submissions: OnceCell<Vec<SubmissionId>>,
+}
+
+impl Client {
+    /// Creates a [`Problem`] handle, which fetches the data of a problem
+    /// lazily.
+    ///
+    /// This doesn't send any request, so it succeeds even if the problem
+    /// doesn't exist.
+    #[must_use]
+    pub fn problem(&self, id: &ProblemId<Unlocalized>) -> Problem<'_> {
+        Problem {
+            client: self,
+            id: id.clone(),
+            info: OnceCell::new(),
+            statements: RefCell::new(HashMap::new()),
+            samples: OnceCell::new(),
+            stats: OnceCell::new(),
+            #[cfg(feature = "client-auth")]
+            submissions: OnceCell::new(),
+        }
+    }
+}
+
+impl Problem<'_> {
+    /// Gets the id of the problem.
+    #[must_use]
+    pub const fn id(&self) -> &ProblemId<Unlocalized> {
+        &self.id
+    }
+
+    /// Gets the general information about the problem, like
+    /// [`Client::problem_info()`].
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Client::problem_info()`].
+    pub fn info(&self) -> Result<&ProblemInfo> {
+        memoize(&self.info, || self.client.problem_info(&self.localized()?))
+    }
+
+    /// Gets the statement of the problem in a language, like
+    /// [`Client::statement()`].
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Client::statement()`], and
+    /// [`Error::InvalidProblemId`](crate::Error::InvalidProblemId) if the
+    /// language isn't valid.
+    pub fn statement(&self, lang: ProblemLanguage) -> Result<Rc<Statement>> {
+        if let Some(statement) = self.statements.borrow().get(&lang) {
+            return Ok(Rc::clone(statement));
+        }
+
+        let statement = Rc::new(self.client.statement(&self.id.localize(lang)?)?);
+        self.statements
+            .borrow_mut()
+            .insert(lang, Rc::clone(&statement));
+        Ok(statement)
+    }
+
+    /// Gets the sample test cases of the problem, like
+    /// [`Client::samples()`].
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Client::samples()`].
+    pub fn samples(&self) -> Result<&[TestCase]> {
+        memoize(&self.samples, || self.client.samples(&self.localized()?)).map(Vec::as_slice)
+    }
+
+    /// Gets the public statistics of the problem, like
+    /// [`Client::problem_stats()`].
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Client::problem_stats()`].
+    pub fn stats(&self) -> Result<&ProblemStats> {
+        memoize(&self.stats, || {
+            self.client.problem_stats(&self.localized()?)
+        })
+    }
+
+    /// Gets the submissions of the user of a session to the problem, like
+    /// [`Session::submissions()`].
+    ///
+    /// The list is fetched only once, so it doesn't include the submissions
+    /// made afterwards, and it's fetched with the first session given. Create
+    /// a new handle to see the new submissions, or those of another session.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Session::submissions()`].
+    #[cfg(feature = "client-auth")]
+    pub fn my_submissions(&self, session: &Session) -> Result<&[SubmissionId]> {
+        memoize(&self.submissions, || {
+            session.submissions(&self.localized()?)
+        })
+        .map(Vec::as_slice)
+    }
+
+    /// Gets the id of the problem in the default language of the `Client`.
+    fn localized(&self) -> Result<ProblemId<Localized>> {
+        self.client.localize(&self.id)
+    }
+}
+
+/// Gets the value of a cell, initializing it with `fetch` if it's empty.
+fn memoize<T>(cell: &OnceCell<T>, fetch: impl FnOnce() -> Result<T>) -> Result<&T> {
+    if let Some(value) = cell.get() {
+        return Ok(value);
+    }
+
+    let value = fetch()?;
+    Ok(cell.get_or_init(|| value))
+}
@@ -0,0 +1,187 @@
+//! The index of public problems of <https://jutge.org>.
+
+use crate::problem_id_types::Unlocalized;
+use crate::{ProblemId, ProblemLanguage};
+
+/// An entry of the public problem index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PublicProblem {
+    /// The id of the problem.
+    pub id: ProblemId<Unlocalized>,
+
+    /// The title of the problem, in the first language it's listed in.
+    pub title: String,
+
+    /// The languages the statement of the problem is available in.
+    pub languages: Vec<ProblemLanguage>,
+}
+
+#[cfg(feature = "web-client")]
+mod fetch {
+    use std::collections::{HashSet, VecDeque};
+    use std::ops::ControlFlow;
+
+    use scraper::Html;
+
+    use super::PublicProblem;
+    use crate::batch::run_pipeline;
+    use crate::problem_id_types::{Localized, Unlocalized};
+    use crate::sans_io::{FetchPublicProblems, Operation, Request, Response};
+    use crate::scrape::{selector, text};
+    use crate::{Client, ProblemId, Result};
+
+    impl Client {
+        /// Walks the public problem index of the site.
+        ///
+        /// The returned iterator fetches the listing pages one by one, as
+        /// entries are consumed, and stops at the first page that doesn't
+        /// list any new problem. It ends after the first error.
+        ///
+        /// The pages are fetched at the pace of the bulk crawls of the
+        /// [`Politeness`](crate::Politeness) policy of the `Client`.
+        #[must_use]
+        pub fn all_public_problems(&self) -> PublicProblems<'_> {
+            PublicProblems {
+                client: self,
+                page: 1,
+                seen: HashSet::new(),
+                buffer: VecDeque::new(),
+                done: false,
+            }
+        }
+
+        /// Runs `fetch` on every problem of the public problem index, with
+        /// up to `concurrency` problems at the same time, and hands each
+        /// problem and its result to `consume` as soon as it's ready.
+        ///
+        /// This is meant for archive-scale work, such as mirroring every
+        /// public problem: the index is walked as workers become free (see
+        /// [`Client::all_public_problems()`]) and nothing is collected, so
+        /// memory use stays bounded regardless of the size of the index.
+        /// While `consume` is busy, it holds back the workers. Problems are
+        /// consumed in the order `fetch` finishes with them.
+        ///
+        /// Breaking from `consume` stops the walk.
+        ///
+        /// The walk is paced, and its concurrency capped, by the
+        /// [`Politeness`](crate::Politeness) policy of the `Client`.
+        ///
+        /// # Errors
+        /// Errors of `fetch` are handed to `consume`. The walk stops at the
+        /// first error fetching the index, which is returned.
+        pub fn for_each_public_problem<T: Send>(
+            &self,
+            concurrency: usize,
+            fetch: impl Fn(&Self, &PublicProblem) -> Result<T> + Sync,
+            consume: impl FnMut(PublicProblem, Result<T>) -> ControlFlow<()>,
+        ) -> Result<()> {
+            run_pipeline(
+                self.all_public_problems(),
+                self.bulk_concurrency(concurrency),
+                |problem| {
+                    self.pace_bulk();
+                    fetch(self, problem)
+                },
+                consume,
+            )
+        }
+    }
+
+    /// An iterator over the public problem index.
+    ///
+    /// This is created by [`Client::all_public_problems()`].
+    #[derive(Debug)]
+    pub struct PublicProblems<'a> {
+        client: &'a Client,
+        page: u32,
+        seen: HashSet<ProblemId<Unlocalized>>,
+        buffer: VecDeque<PublicProblem>,
+        done: bool,
+    }
+
+    impl Iterator for PublicProblems<'_> {
+        type Item = Result<PublicProblem>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            while self.buffer.is_empty() && !self.done {
+                let page = FetchPublicProblems { page: self.page };
+                self.client.pace_bulk();
+                let problems = match self.client.execute(&page) {
+                    Ok(problems) => problems,
+                    Err(err) => {
+                        self.done = true;
+                        return Some(Err(err));
+                    }
+                };
+
+                let seen = &mut self.seen;
+                self.buffer.extend(
+                    problems
+                        .into_iter()
+                        .filter(|problem| seen.insert(problem.id.clone())),
+                );
+                self.done = self.buffer.is_empty();
+                self.page += 1;
+            }
+
+            self.buffer.pop_front().map(Ok)
+        }
+    }
+
+    impl Operation for FetchPublicProblems {
+        type Output = Vec<PublicProblem>;
+
+        fn request(&self) -> Request {
+            Request::page(format!("/problems/public?page={}", self.page))
+        }
+
+        fn parse(&self, response: &Response<'_>) -> Result<Vec<PublicProblem>> {
+            Ok(parse_index(&response.html()?))
+        }
+    }
+
+    /// Parses the problems listed in a page of the index, one per table row.
+    fn parse_index(html: &Html) -> Vec<PublicProblem> {
+        html.select(&selector("table tr"))
+            .filter_map(|row| {
+                let links: Vec<_> = row.select(&selector("a[href]")).collect();
+
+                let id = links.iter().find_map(|link| {
+                    ProblemId::<Unlocalized>::parse_lenient(link.value().attr("href")?).ok()
+                })?;
+
+                let mut languages = Vec::new();
+                for link in &links {
+                    let Some(href) = link.value().attr("href") else {
+                        continue;
+                    };
+                    if let Ok(localized) = ProblemId::<Localized>::parse_lenient(href) {
+                        let same_problem = localized.problem_type() == id.problem_type()
+                            && localized.problem_id() == id.problem_id();
+                        if same_problem && !languages.contains(&localized.language()) {
+                            languages.push(localized.language());
+                        }
+                    }
+                }
+
+                let title = links
+                    .iter()
+                    .map(|link| text(*link))
+                    .find(|title| {
+                        !title.is_empty() && ProblemId::<Unlocalized>::parse_lenient(title).is_err()
+                    })
+                    .unwrap_or_default();
+
+                Some(PublicProblem {
+                    id,
+                    title,
+                    languages,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "web-client")]
+pub use fetch::PublicProblems;
@@ -0,0 +1,117 @@
+use crate::problem_id_types::{Localized, ProblemIdType};
+use crate::sans_io::{FetchProblemInfo, Operation, Request, Response};
+use crate::scrape::{selector, text, text_of};
+use crate::{Client, Error, Partial, ProblemId, Result};
+
+/// The general information about a <https://jutge.org> problem, as shown in
+/// its page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ProblemInfo {
+    /// The id of the problem.
+    pub id: ProblemId<Localized>,
+
+    /// The title of the problem, in the language of the id.
+    pub title: String,
+
+    /// The limits of the solutions to the problem, such as "Time limit: 1
+    /// second", if the page shows them.
+    pub limits: Option<String>,
+}
+
+impl Client {
+    /// Fetches the general information about a problem.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`] if the problem requires logging
+    /// in, or [`Error::UnexpectedPageLayout`] if the title can't be found in
+    /// the problem page. With
+    /// [`ClientBuilder::lenient_parsing()`](crate::ClientBuilder::lenient_parsing),
+    /// a missing title is left empty instead.
+    pub fn problem_info(&self, id: &ProblemId<Localized>) -> Result<ProblemInfo> {
+        self.execute(&FetchProblemInfo {
+            problem: id.clone(),
+        })
+    }
+
+    /// Checks whether a problem exists, without downloading its statement.
+    ///
+    /// Private problems that the logged-in user can't access are reported as
+    /// non-existing, as the site doesn't differentiate between both cases.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`] if the site requires logging in to
+    /// see the problem (this is always the case for private problems when the
+    /// `Client` isn't logged in), or [`Error::Offline`] if the `Client` is
+    /// offline and the problem isn't in its disk cache.
+    pub fn problem_exists<T: ProblemIdType>(&self, id: &ProblemId<T>) -> Result<bool> {
+        let path = format!("/problems/{id}");
+
+        if let Some(exists) = self.existence_cache.get(&path) {
+            return Ok(exists);
+        }
+        #[cfg(feature = "cache")]
+        if self.is_on_disk(&path) {
+            return Ok(true);
+        }
+
+        let response = match self.head(&path) {
+            Err(Error::UreqError(err)) if matches!(*err, ureq::Error::Status(405, _)) => {
+                self.get(&path)
+            }
+            response => response,
+        };
+
+        let exists = match response {
+            Ok(response) => response.get_url() == self.url(&path),
+            Err(Error::UreqError(err)) if matches!(*err, ureq::Error::Status(404 | 410, _)) => {
+                false
+            }
+            Err(err) => return Err(err),
+        };
+
+        self.existence_cache.insert(path, exists);
+        Ok(exists)
+    }
+}
+
+impl Operation for FetchProblemInfo {
+    type Output = ProblemInfo;
+
+    fn request(&self) -> Request {
+        Request::page(format!("/problems/{}", self.problem)).cacheable()
+    }
+
+    fn parse(&self, response: &Response<'_>) -> Result<ProblemInfo> {
+        self.parse_partial(response)?
+            .into_result(self.request().path)
+    }
+
+    /// A title that can't be found is left empty.
+    fn parse_partial(&self, response: &Response<'_>) -> Result<Partial<ProblemInfo>> {
+        let html = response.html()?;
+
+        // The heading reads "P68688 · Title", and the title of the page
+        // "Jutge.org - Title".
+        let title = html
+            .select(&selector("h1"))
+            .map(text)
+            .find_map(|heading| Some(heading.split_once('·')?.1.trim().to_owned()))
+            .or_else(|| {
+                html.select(&selector("title"))
+                    .map(text)
+                    .find_map(|title| Some(title.split_once(" - ")?.1.trim().to_owned()))
+            })
+            .filter(|title| !title.is_empty());
+
+        let mut info = Partial::complete(ProblemInfo {
+            id: self.problem.clone(),
+            title: title.clone().unwrap_or_default(),
+            limits: text_of(html.root_element(), "#limits, .limits"),
+        });
+        if title.is_none() {
+            info.fail("title", "no title found");
+        }
+        Ok(info)
+    }
+}
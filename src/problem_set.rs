@@ -0,0 +1,179 @@
+//! Sets of problems, to compare collections of problems such as the lists of
+//! a course and the problems solved by a user.
+
+use std::collections::{btree_set, BTreeMap, BTreeSet};
+
+use crate::problem_id_types::{ProblemIdType, Unlocalized};
+use crate::{HistoryEntry, ProblemId, ProblemType};
+
+/// A set of problems, regardless of their language.
+///
+/// Problems are kept by their unlocalized id, so `P068688_en` and
+/// `P068688_ca` are the same problem, and iterated in the order of
+/// [`ProblemId`]: by type, and then by numeric id.
+///
+/// # Example
+/// ```no_run
+/// # #[cfg(feature = "courses")]
+/// # {
+/// use jutge::{Client, ProblemSet};
+///
+/// let session = Client::new().login("alex@example.com", "password")?;
+///
+/// let list: ProblemSet = session
+///     .list_problems("PRO1", "L1")?
+///     .into_iter()
+///     .map(|entry| entry.problem)
+///     .collect();
+/// let solved = ProblemSet::accepted(&session.submission_history()?);
+///
+/// for problem in &list.difference(&solved) {
+///     println!("Still to solve: {problem}");
+/// }
+/// # }
+/// # Ok::<(), jutge::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ProblemSet {
+    problems: BTreeSet<ProblemId<Unlocalized>>,
+}
+
+impl ProblemSet {
+    /// Creates an empty `ProblemSet`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the set of the problems with an accepted submission in the
+    /// history of a user.
+    #[must_use]
+    pub fn accepted(history: &[HistoryEntry]) -> Self {
+        history
+            .iter()
+            .filter(|entry| entry.verdict.is_accepted())
+            .map(|entry| entry.problem.clone())
+            .collect()
+    }
+
+    /// Adds a problem to the set, returning whether it wasn't in it yet.
+    pub fn insert<T: ProblemIdType>(&mut self, problem: &ProblemId<T>) -> bool {
+        self.problems.insert(unlocalized(problem))
+    }
+
+    /// Removes a problem from the set, returning whether it was in it.
+    pub fn remove<T: ProblemIdType>(&mut self, problem: &ProblemId<T>) -> bool {
+        self.problems.remove(&unlocalized(problem))
+    }
+
+    /// Checks whether a problem is in the set, in any language.
+    #[must_use]
+    pub fn contains<T: ProblemIdType>(&self, problem: &ProblemId<T>) -> bool {
+        self.problems.contains(&unlocalized(problem))
+    }
+
+    /// Gets the number of problems in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.problems.len()
+    }
+
+    /// Checks whether the set has no problems.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    /// Iterates over the problems of the set, in order.
+    pub fn iter(&self) -> btree_set::Iter<'_, ProblemId<Unlocalized>> {
+        self.problems.iter()
+    }
+
+    /// Gets the problems that are in this set but not in `other`, such as
+    /// the problems of a list that a user hasn't solved yet.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        self.problems.difference(&other.problems).cloned().collect()
+    }
+
+    /// Gets the problems that are both in this set and in `other`, such as
+    /// the problems of a list that a user has solved.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.problems
+            .intersection(&other.problems)
+            .cloned()
+            .collect()
+    }
+
+    /// Gets the problems that are in this set, in `other` or in both.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        self.problems.union(&other.problems).cloned().collect()
+    }
+
+    /// Checks whether all the problems of this set are in `other`, such as
+    /// whether a user has solved every problem of a list.
+    #[must_use]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.problems.is_subset(&other.problems)
+    }
+
+    /// Splits the set by the type of its problems. Types without problems
+    /// are left out.
+    #[must_use]
+    pub fn group_by_type(&self) -> BTreeMap<ProblemType, Self> {
+        let mut groups: BTreeMap<ProblemType, Self> = BTreeMap::new();
+        for problem in &self.problems {
+            groups
+                .entry(problem.problem_type())
+                .or_default()
+                .problems
+                .insert(problem.clone());
+        }
+        groups
+    }
+}
+
+/// Gets the unlocalized id of a problem.
+///
+/// # Panics
+/// Never panics, as every problem id contains a valid unlocalized one.
+fn unlocalized<T: ProblemIdType>(problem: &ProblemId<T>) -> ProblemId<Unlocalized> {
+    ProblemId::new_unlocalized(problem.problem_type(), problem.problem_id())
+        .expect("Problem ids should contain a valid unlocalized one")
+}
+
+impl<T: ProblemIdType> FromIterator<ProblemId<T>> for ProblemSet {
+    fn from_iter<I: IntoIterator<Item = ProblemId<T>>>(problems: I) -> Self {
+        let mut set = Self::new();
+        set.extend(problems);
+        set
+    }
+}
+
+impl<T: ProblemIdType> Extend<ProblemId<T>> for ProblemSet {
+    fn extend<I: IntoIterator<Item = ProblemId<T>>>(&mut self, problems: I) {
+        for problem in problems {
+            self.insert(&problem);
+        }
+    }
+}
+
+impl IntoIterator for ProblemSet {
+    type Item = ProblemId<Unlocalized>;
+    type IntoIter = btree_set::IntoIter<ProblemId<Unlocalized>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.problems.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ProblemSet {
+    type Item = &'a ProblemId<Unlocalized>;
+    type IntoIter = btree_set::Iter<'a, ProblemId<Unlocalized>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.problems.iter()
+    }
+}
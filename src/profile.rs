@@ -0,0 +1,189 @@
+use crate::form::FormFile;
+use crate::scrape::error_alert;
+use crate::{Error, Result, Session};
+
+/// The changes to apply to the profile of the logged-in user with
+/// [`Session::update_profile()`].
+///
+/// Fields set to `None` keep their current value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProfileUpdate {
+    /// The full name of the user.
+    pub name: Option<String>,
+
+    /// The institution the user belongs to.
+    pub institution: Option<String>,
+
+    /// The personal webpage of the user.
+    pub webpage: Option<String>,
+
+    /// The country of the user.
+    pub country: Option<String>,
+
+    /// A short description of the user.
+    pub description: Option<String>,
+}
+
+impl ProfileUpdate {
+    fn fields(&self) -> [(&'static str, Option<&str>); 5] {
+        [
+            ("name", self.name.as_deref()),
+            ("institution", self.institution.as_deref()),
+            ("webpage", self.webpage.as_deref()),
+            ("country", self.country.as_deref()),
+            ("description", self.description.as_deref()),
+        ]
+    }
+}
+
+impl Session {
+    /// Downloads the profile photo of the logged-in user.
+    ///
+    /// The image is returned as it's served by the site, usually a PNG or
+    /// JPEG file.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`] if the session has expired.
+    pub fn avatar(&self) -> Result<Vec<u8>> {
+        self.client().get_file("/profile/avatar")
+    }
+
+    /// Replaces the profile photo of the logged-in user.
+    ///
+    /// `image` should contain a PNG, JPEG or GIF file.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`] if the session has expired, or
+    /// [`Error::AvatarRejected`] if the site doesn't accept the image.
+    pub fn set_avatar(&self, image: &[u8]) -> Result<()> {
+        let (filename, content_type) = image_format(image);
+        let form = self.client().fetch_form("/profile/avatar", "avatar")?;
+        let file = FormFile {
+            field: "avatar",
+            filename,
+            content_type,
+            data: image,
+        };
+
+        let response = self.client().submit_form_with_files(&form, &[file])?;
+
+        match error_alert(&self.client().read_authenticated_html(response)?) {
+            None => Ok(()),
+            Some(reason) => Err(Error::AvatarRejected(reason)),
+        }
+    }
+
+    /// Changes the password of the logged-in user.
+    ///
+    /// # Errors
+    /// Returns [`Error::WrongPassword`] if `old` isn't the current password,
+    /// or [`Error::PasswordRejected`] if the site doesn't accept `new`.
+    /// Returns [`Error::NotAuthenticated`] if the session has expired.
+    pub fn change_password(&self, old: &str, new: &str) -> Result<()> {
+        let mut form = self
+            .client()
+            .fetch_form("/profile/password", "old_password")?;
+        form.set("old_password", old)
+            .set("new_password", new)
+            .set("new_password2", new);
+
+        let response = self.client().submit_form(&form)?;
+
+        match error_alert(&self.client().read_authenticated_html(response)?) {
+            None => Ok(()),
+            Some(reason) if mentions_current_password(&reason) => Err(Error::WrongPassword),
+            Some(reason) => Err(Error::PasswordRejected(reason)),
+        }
+    }
+
+    /// Updates the profile of the logged-in user.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`] if the session has expired.
+    /// If the site rejects the changes, [`Error::ProfileRejected`] will be
+    /// returned.
+    pub fn update_profile(&self, update: &ProfileUpdate) -> Result<()> {
+        let mut form = self.client().fetch_form("/profile/edit", "name")?;
+        for (name, value) in update.fields() {
+            if let Some(value) = value {
+                form.set(name, value);
+            }
+        }
+
+        let response = self.client().submit_form(&form)?;
+
+        match error_alert(&self.client().read_authenticated_html(response)?) {
+            None => Ok(()),
+            Some(reason) => Err(Error::ProfileRejected(reason)),
+        }
+    }
+}
+
+/// Checks whether an error message from the password form refers to the
+/// current password, in any of the languages of the site.
+///
+/// Whole phrases are matched, and messages that talk about the new password
+/// aren't taken as a wrong current password, even if they compare it to the
+/// current one.
+fn mentions_current_password(reason: &str) -> bool {
+    let reason = reason.to_lowercase();
+    let mentions = |phrases: &[&str]| phrases.iter().any(|phrase| reason.contains(phrase));
+
+    mentions(&[
+        "current password",
+        "contrasenya actual",
+        "contraseña actual",
+        "mot de passe actuel",
+        "aktuelles passwort",
+        "aktuelle passwort",
+    ]) && !mentions(&[
+        "new password",
+        "nova contrasenya",
+        "contrasenya nova",
+        "nueva contraseña",
+        "contraseña nueva",
+        "nouveau mot de passe",
+        "neues passwort",
+        "neue passwort",
+    ])
+}
+
+/// Guesses a filename and content type for an image from its magic number.
+fn image_format(image: &[u8]) -> (&'static str, &'static str) {
+    match image {
+        [0x89, b'P', b'N', b'G', ..] => ("avatar.png", "image/png"),
+        [0xFF, 0xD8, 0xFF, ..] => ("avatar.jpg", "image/jpeg"),
+        [b'G', b'I', b'F', b'8', ..] => ("avatar.gif", "image/gif"),
+        _ => ("avatar", "application/octet-stream"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mentions_current_password;
+
+    #[test]
+    fn current_password_messages() {
+        assert!(mentions_current_password("The current password is wrong"));
+        assert!(mentions_current_password(
+            "La contrasenya actual no és correcta"
+        ));
+        assert!(mentions_current_password(
+            "La contraseña actual es incorrecta"
+        ));
+    }
+
+    #[test]
+    fn new_password_messages() {
+        assert!(!mentions_current_password(
+            "The new password can't be the same as the old one"
+        ));
+        assert!(!mentions_current_password("The password is too short"));
+        assert!(!mentions_current_password(
+            "The new password must be different from the current password"
+        ));
+        assert!(!mentions_current_password(
+            "La nova contrasenya ha de ser diferent de la contrasenya actual"
+        ));
+    }
+}
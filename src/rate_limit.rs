@@ -0,0 +1,227 @@
+//! Rate limiting of the requests made by a [`Client`](crate::Client).
+
+use std::sync::{Condvar, Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use ureq::Response;
+
+use crate::timestamp::days_from_civil;
+
+/// Enforces a minimum interval between operations, even when they're
+/// performed from several threads.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    interval: Duration,
+    next: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    /// Creates a `RateLimiter` that lets an operation through every
+    /// `interval`.
+    pub(crate) fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            next: Mutex::new(None),
+        }
+    }
+
+    /// Blocks until the next operation is allowed, returning how long it
+    /// waited.
+    pub(crate) fn wait(&self) -> Duration {
+        let deadline = {
+            let mut next = self.next.lock().unwrap_or_else(PoisonError::into_inner);
+            let now = Instant::now();
+            let deadline = next.map_or(now, |next| next.max(now));
+
+            *next = Some(deadline + self.interval);
+            deadline
+        };
+
+        let wait = deadline.saturating_duration_since(Instant::now());
+        thread::sleep(wait);
+        wait
+    }
+
+    /// Holds back the next operation until at least `delay` from now, e.g.
+    /// because the server asked to slow down.
+    pub(crate) fn pause(&self, delay: Duration) {
+        let mut next = self.next.lock().unwrap_or_else(PoisonError::into_inner);
+        let until = Instant::now() + delay;
+
+        *next = Some(next.map_or(until, |next| next.max(until)));
+    }
+}
+
+/// Limits how many operations run at the same time, even when they're
+/// performed from several threads.
+#[derive(Debug)]
+pub(crate) struct ConnectionLimit {
+    max: usize,
+    running: Mutex<usize>,
+    freed: Condvar,
+}
+
+impl ConnectionLimit {
+    /// Creates a `ConnectionLimit` that lets up to `max` operations run at
+    /// the same time.
+    pub(crate) fn new(max: usize) -> Self {
+        Self {
+            max: max.max(1),
+            running: Mutex::new(0),
+            freed: Condvar::new(),
+        }
+    }
+
+    /// Runs an operation once fewer than the maximum are running.
+    pub(crate) fn run<T>(&self, operation: impl FnOnce() -> T) -> T {
+        {
+            let running = self.running.lock().unwrap_or_else(PoisonError::into_inner);
+            let mut running = self
+                .freed
+                .wait_while(running, |running| *running >= self.max)
+                .unwrap_or_else(PoisonError::into_inner);
+            *running += 1;
+        }
+
+        let _slot = Slot(self);
+        operation()
+    }
+}
+
+/// A running operation of a [`ConnectionLimit`], which frees its slot when
+/// dropped, even if the operation panics.
+struct Slot<'a>(&'a ConnectionLimit);
+
+impl Drop for Slot<'_> {
+    fn drop(&mut self) {
+        *self
+            .0
+            .running
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) -= 1;
+        self.0.freed.notify_one();
+    }
+}
+
+/// Checks whether a response is a throttling signal from the server, that
+/// is, a 429 or 503 status.
+pub(crate) fn is_throttling(response: &Response) -> bool {
+    matches!(response.status(), 429 | 503)
+}
+
+/// Gets how long the server asked to wait before retrying a request, if it
+/// did so with a `Retry-After` header.
+pub(crate) fn retry_after(response: &Response) -> Option<Duration> {
+    response.header("Retry-After").and_then(parse_retry_after)
+}
+
+/// Parses the value of a `Retry-After` header, which is either a number of
+/// seconds or an HTTP date.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = parse_http_date(value)?;
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// Parses an HTTP date in the preferred format, such as
+/// `Sun, 06 Nov 1994 08:49:37 GMT`, between the years 1970 and 9999.
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let (_, date) = value.split_once(", ")?;
+    let [day, month, year, time, "GMT"] = date.split(' ').collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    let [hours, minutes, seconds] = time.split(':').collect::<Vec<_>>()[..] else {
+        return None;
+    };
+
+    let day: u32 = day.parse().ok()?;
+    let month = MONTHS.iter().position(|m| *m == month)? + 1;
+    let year: i64 = year.parse().ok()?;
+    let hours: u64 = hours.parse().ok()?;
+    let minutes: u64 = minutes.parse().ok()?;
+    let seconds: u64 = seconds.parse().ok()?;
+    // Leap seconds are written as `:60`.
+    if !(1..=31).contains(&day)
+        || !(1970..=9999).contains(&year)
+        || hours > 23
+        || minutes > 59
+        || seconds > 60
+    {
+        return None;
+    }
+    let seconds = hours * 3600 + minutes * 60 + seconds;
+
+    let days = u64::try_from(days_from_civil(year, u32::try_from(month).ok()?, day)).ok()?;
+    Some(UNIX_EPOCH + Duration::from_secs(days * 86_400 + seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{parse_http_date, parse_retry_after};
+
+    #[test]
+    fn retry_after_seconds() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_mins(2)));
+        assert_eq!(parse_retry_after(" 0 "), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("-5"), None);
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+
+    #[test]
+    fn retry_after_dates() {
+        assert_eq!(
+            parse_retry_after("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Some(Duration::ZERO)
+        );
+
+        let wait = parse_retry_after("Fri, 31 Dec 9999 23:59:59 GMT").unwrap();
+        assert!(wait > Duration::from_hours(1_000 * 365 * 24));
+    }
+
+    #[test]
+    fn http_dates() {
+        assert_eq!(
+            parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Some(UNIX_EPOCH + Duration::from_secs(784_111_777))
+        );
+        assert_eq!(
+            parse_http_date("Thu, 29 Feb 2024 00:00:00 GMT"),
+            Some(UNIX_EPOCH + Duration::from_hours(474_768))
+        );
+    }
+
+    #[test]
+    fn invalid_http_dates() {
+        assert_eq!(parse_http_date("06 Nov 1994 08:49:37 GMT"), None);
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 08:49:37 CET"), None);
+        assert_eq!(parse_http_date("Sun, 06 Nevember 1994 08:49:37 GMT"), None);
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 08:49 GMT"), None);
+        assert_eq!(parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT"), None);
+        assert_eq!(parse_http_date("Sun, 32 Nov 1994 08:49:37 GMT"), None);
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 24:00:00 GMT"), None);
+        assert_eq!(parse_http_date("Sun, 06 Nov 1969 08:49:37 GMT"), None);
+        assert_eq!(
+            parse_http_date("Sun, 06 Nov 99999999999999 08:49:37 GMT"),
+            None
+        );
+        assert_eq!(
+            parse_http_date("Sun, 06 Nov 1994 99999999999999:00:00 GMT"),
+            None
+        );
+    }
+}
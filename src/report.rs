@@ -0,0 +1,447 @@
+//! Progress reports of a <https://jutge.org> user: the problems they solved,
+//! by week, by category and by course.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+
+use crate::course_progress::{json_string, write_csv_row};
+use crate::problem_id_types::Unlocalized;
+use crate::{ProblemId, Result, Timestamp, Verdict};
+
+/// A submission from the history of a user, as listed by
+/// [`Session::submission_history()`](crate::Session::submission_history).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct HistoryEntry {
+    /// The problem the submission was sent to.
+    pub problem: ProblemId<Unlocalized>,
+
+    /// The verdict of the submission.
+    pub verdict: Verdict,
+
+    /// The time the submission was made, if the site shows it.
+    pub submitted: Option<Timestamp>,
+
+    /// The category of the problem, such as `Loops`, if the site shows it.
+    pub category: Option<String>,
+}
+
+/// A course a user is enrolled in, with its problems, as listed in their
+/// dashboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EnrolledCourse {
+    /// The id of the course.
+    pub course_id: String,
+
+    /// The name of the course.
+    pub name: String,
+
+    /// The problems of the course.
+    pub problems: Vec<ProblemId<Unlocalized>>,
+}
+
+/// A problem solved by a user, in a [`ProgressReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolvedProblem {
+    /// The problem.
+    pub problem: ProblemId<Unlocalized>,
+
+    /// The time of the first accepted submission to the problem, if the
+    /// site shows it.
+    pub solved: Option<Timestamp>,
+
+    /// The category of the problem, if the site shows it.
+    pub category: Option<String>,
+}
+
+/// The number of problems solved and attempted in a group of a
+/// [`ProgressReport`], such as a week.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tally {
+    /// What the group is: the date of the Monday of a week (such as
+    /// `2024-03-04`), a category or the id of a course.
+    pub key: String,
+
+    /// The number of problems solved in the group.
+    pub solved: usize,
+
+    /// The number of problems submitted to in the group, solved or not.
+    pub attempted: usize,
+
+    /// The number of problems of the group, if it's a course.
+    pub total: Option<usize>,
+}
+
+/// A snapshot of the progress of a user, built from their submission history
+/// and their courses.
+///
+/// Reports are fetched with
+/// [`Session::progress_report()`](crate::Session::progress_report), or built
+/// with [`ProgressReport::new()`], and can be exported with
+/// [`ProgressReport::to_csv()`] and [`ProgressReport::to_json()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgressReport {
+    /// The time the report was made.
+    pub generated: Timestamp,
+
+    /// The problems solved, in the order they were solved. Problems solved
+    /// at an unknown time go last.
+    pub solved: Vec<SolvedProblem>,
+
+    /// The problems solved (for the first time) and attempted each week, in
+    /// the Europe/Madrid timezone, from the oldest to the newest. Weeks
+    /// without submissions are left out.
+    pub per_week: Vec<Tally>,
+
+    /// The problems solved and attempted in each category, sorted by
+    /// category. Problems without a category are left out.
+    pub per_category: Vec<Tally>,
+
+    /// The problems solved and attempted in each course, in the order the
+    /// courses were given.
+    pub per_course: Vec<Tally>,
+}
+
+impl ProgressReport {
+    /// Builds the report of a submission history (in any order) and the
+    /// courses of its user.
+    #[must_use]
+    pub fn new(history: &[HistoryEntry], courses: &[EnrolledCourse], generated: Timestamp) -> Self {
+        let mut entries: Vec<_> = history.iter().collect();
+        // Sorted by time, with the unknown times last.
+        entries.sort_by_key(|entry| (entry.submitted.is_none(), entry.submitted));
+
+        let mut solved = Vec::new();
+        let mut solved_ids = BTreeSet::new();
+        let mut attempted = BTreeSet::new();
+        let mut weeks: BTreeMap<(i64, u32, u32), (BTreeSet<_>, usize)> = BTreeMap::new();
+        let mut categories: BTreeMap<&str, (BTreeSet<_>, BTreeSet<_>)> = BTreeMap::new();
+
+        for entry in entries {
+            let problem = &entry.problem;
+            let accepted = entry.verdict.is_accepted();
+            let first_solve = accepted && solved_ids.insert(problem.clone());
+            attempted.insert(problem.clone());
+
+            if let Some(time) = entry.submitted {
+                let week = weeks.entry(time.madrid_week_start()).or_default();
+                week.0.insert(problem.clone());
+                week.1 += usize::from(first_solve);
+            }
+            if let Some(category) = &entry.category {
+                let tally = categories.entry(category).or_default();
+                tally.1.insert(problem.clone());
+                if accepted {
+                    tally.0.insert(problem.clone());
+                }
+            }
+            if first_solve {
+                solved.push(SolvedProblem {
+                    problem: problem.clone(),
+                    solved: entry.submitted,
+                    category: entry.category.clone(),
+                });
+            }
+        }
+
+        let per_week = weeks
+            .into_iter()
+            .map(|((year, month, day), (problems, solved))| Tally {
+                key: format!("{year:04}-{month:02}-{day:02}"),
+                solved,
+                attempted: problems.len(),
+                total: None,
+            })
+            .collect();
+        let per_category = categories
+            .into_iter()
+            .map(|(category, (solved, problems))| Tally {
+                key: category.into(),
+                solved: solved.len(),
+                attempted: problems.len(),
+                total: None,
+            })
+            .collect();
+        let per_course = courses
+            .iter()
+            .map(|course| Tally {
+                key: course.course_id.clone(),
+                solved: course
+                    .problems
+                    .iter()
+                    .filter(|problem| solved_ids.contains(*problem))
+                    .count(),
+                attempted: course
+                    .problems
+                    .iter()
+                    .filter(|problem| attempted.contains(*problem))
+                    .count(),
+                total: Some(course.problems.len()),
+            })
+            .collect();
+
+        Self {
+            generated,
+            solved,
+            per_week,
+            per_category,
+            per_course,
+        }
+    }
+
+    /// Writes the tallies of the report as CSV, with a row per week,
+    /// category and course.
+    ///
+    /// The columns are the kind of group (`week`, `category` or `course`),
+    /// its key, the number of problems solved and attempted in it, and the
+    /// number of problems of the course (empty for the other groups).
+    ///
+    /// # Errors
+    /// Returns [`Error::IoError`](crate::Error::IoError) if the CSV can't be
+    /// written.
+    pub fn to_csv(&self, writer: &mut impl Write) -> Result<()> {
+        let header = ["group", "key", "solved", "attempted", "total"].map(String::from);
+        write_csv_row(writer, &header)?;
+
+        for (group, tally) in self.tallies() {
+            write_csv_row(
+                writer,
+                &[
+                    group.to_owned(),
+                    tally.key.clone(),
+                    tally.solved.to_string(),
+                    tally.attempted.to_string(),
+                    tally
+                        .total
+                        .map(|total| total.to_string())
+                        .unwrap_or_default(),
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Formats the report as JSON.
+    ///
+    /// The JSON is an object with the time the report was `generated`, the
+    /// `solved` problems (each with its `problem` id, the time it was
+    /// `solved` and its `category`, or `null` if unknown) and the tallies
+    /// `per_week`, `per_category` and `per_course` (each with its `key`, the
+    /// number of problems `solved` and `attempted`, and the `total` of a
+    /// course, or `null`). Times are formatted as RFC 3339, like
+    /// [`Timestamp::to_rfc3339()`].
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let null_or = |value: Option<String>| value.unwrap_or_else(|| "null".into());
+
+        let solved: Vec<_> = self
+            .solved
+            .iter()
+            .map(|solved| {
+                format!(
+                    "{{\"problem\":{},\"solved\":{},\"category\":{}}}",
+                    json_string(solved.problem.as_str()),
+                    null_or(solved.solved.map(|time| json_string(&time.to_rfc3339()))),
+                    null_or(solved.category.as_deref().map(json_string)),
+                )
+            })
+            .collect();
+        let tallies = |tallies: &[Tally]| {
+            let tallies: Vec<_> = tallies
+                .iter()
+                .map(|tally| {
+                    format!(
+                        "{{\"key\":{},\"solved\":{},\"attempted\":{},\"total\":{}}}",
+                        json_string(&tally.key),
+                        tally.solved,
+                        tally.attempted,
+                        null_or(tally.total.map(|total| total.to_string())),
+                    )
+                })
+                .collect();
+            tallies.join(",")
+        };
+
+        format!(
+            "{{\"generated\":{},\"solved\":[{}],\"per_week\":[{}],\
+             \"per_category\":[{}],\"per_course\":[{}]}}",
+            json_string(&self.generated.to_rfc3339()),
+            solved.join(","),
+            tallies(&self.per_week),
+            tallies(&self.per_category),
+            tallies(&self.per_course),
+        )
+    }
+
+    /// Iterates over all the tallies, with the kind of group they belong to.
+    fn tallies(&self) -> impl Iterator<Item = (&'static str, &Tally)> {
+        [
+            ("week", &self.per_week),
+            ("category", &self.per_category),
+            ("course", &self.per_course),
+        ]
+        .into_iter()
+        .flat_map(|(group, tallies)| tallies.iter().map(move |tally| (group, tally)))
+    }
+}
+
+#[cfg(feature = "web-client")]
+mod fetch {
+    use super::{EnrolledCourse, HistoryEntry};
+    use crate::problem_id_types::Unlocalized;
+    use crate::sans_io::{
+        FetchEnrolledCourses, FetchSubmissionHistory, Operation, Request, Response,
+    };
+    use crate::scrape::{selector, text};
+    use crate::{Error, ProblemId, Result, Timestamp, Verdict};
+    #[cfg(feature = "client-auth")]
+    use crate::{ProgressReport, Session};
+
+    #[cfg(feature = "client-auth")]
+    impl Session {
+        /// Fetches all the submissions of the logged-in user, from the
+        /// newest to the oldest.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAuthenticated`] if the session has expired,
+        /// or [`Error::UnexpectedPageLayout`] if the submissions can't be
+        /// found in the page.
+        pub fn submission_history(&self) -> Result<Vec<HistoryEntry>> {
+            self.client().execute(&FetchSubmissionHistory)
+        }
+
+        /// Fetches the courses the logged-in user is enrolled in, as listed
+        /// in their dashboard.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAuthenticated`] if the session has expired.
+        pub fn enrolled_courses(&self) -> Result<Vec<EnrolledCourse>> {
+            self.client().execute(&FetchEnrolledCourses)
+        }
+
+        /// Builds the [`ProgressReport`] of the logged-in user, from their
+        /// submission history and their courses.
+        ///
+        /// # Errors
+        /// Fails like [`Session::submission_history()`] and
+        /// [`Session::enrolled_courses()`].
+        pub fn progress_report(&self) -> Result<ProgressReport> {
+            let history = self.submission_history()?;
+            let courses = self.enrolled_courses()?;
+            Ok(ProgressReport::new(&history, &courses, Timestamp::now()))
+        }
+    }
+
+    impl Operation for FetchSubmissionHistory {
+        type Output = Vec<HistoryEntry>;
+
+        fn request(&self) -> Request {
+            Request::page("/submissions")
+        }
+
+        fn parse(&self, response: &Response<'_>) -> Result<Vec<HistoryEntry>> {
+            let html = response.html()?;
+            let no_table = || Error::unexpected_layout(self.request().path, "no submissions found");
+
+            let table = html
+                .select(&selector("table"))
+                .next()
+                .ok_or_else(no_table)?;
+            let header: Vec<_> = table
+                .select(&selector("tr"))
+                .next()
+                .ok_or_else(no_table)?
+                .select(&selector("th"))
+                .map(|cell| text(cell).to_lowercase())
+                .collect();
+            let column = |words: &[&str]| {
+                header
+                    .iter()
+                    .position(|heading| words.iter().any(|w| heading.contains(w)))
+            };
+            let (Some(problem_column), Some(verdict_column)) = (
+                column(&["problem", "problema"]),
+                column(&["verdict", "veredicte", "veredicto"]),
+            ) else {
+                return Err(no_table());
+            };
+            let date_column = column(&["date", "data", "fecha"]);
+            let category_column = column(&["categor"]);
+
+            Ok(table
+                .select(&selector("tr"))
+                .skip(1)
+                .filter_map(|row| {
+                    let cells: Vec<_> = row.select(&selector("td")).collect();
+                    let problem = cells.get(problem_column)?;
+                    let problem = problem
+                        .select(&selector("a[href]"))
+                        .filter_map(|link| link.value().attr("href"))
+                        .chain([text(*problem).as_str()])
+                        .find_map(|s| ProblemId::<Unlocalized>::parse_lenient(s).ok())?;
+                    let cell = |column: Option<usize>| {
+                        column
+                            .and_then(|i| cells.get(i))
+                            .map(|cell| text(*cell))
+                            .filter(|text| !text.is_empty())
+                    };
+
+                    Some(HistoryEntry {
+                        problem,
+                        verdict: cell(Some(verdict_column))?.parse::<Verdict>().ok()?,
+                        submitted: cell(date_column)
+                            .and_then(|date| Timestamp::find_in_listing(&date)),
+                        category: cell(category_column),
+                    })
+                })
+                .collect())
+        }
+    }
+
+    impl Operation for FetchEnrolledCourses {
+        type Output = Vec<EnrolledCourse>;
+
+        fn request(&self) -> Request {
+            Request::page("/dashboard")
+        }
+
+        fn parse(&self, response: &Response<'_>) -> Result<Vec<EnrolledCourse>> {
+            let html = response.html()?;
+
+            Ok(html
+                .select(&selector(".course"))
+                .filter_map(|course| {
+                    let link = course.select(&selector("a[href^='/courses/']")).next()?;
+                    let course_id = link
+                        .value()
+                        .attr("href")?
+                        .trim_start_matches("/courses/")
+                        .split('/')
+                        .next()
+                        .filter(|id| !id.is_empty())?
+                        .to_owned();
+
+                    let mut problems = Vec::new();
+                    for href in course
+                        .select(&selector("a[href^='/problems/']"))
+                        .filter_map(|link| link.value().attr("href"))
+                    {
+                        if let Ok(problem) = ProblemId::<Unlocalized>::parse_lenient(href) {
+                            if !problems.contains(&problem) {
+                                problems.push(problem);
+                            }
+                        }
+                    }
+
+                    Some(EnrolledCourse {
+                        course_id,
+                        name: text(link),
+                        problems,
+                    })
+                })
+                .collect())
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! Address overrides for the hosts the [`Client`](crate::Client) connects to.
+
+use std::collections::HashMap;
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs};
+
+/// A `ureq` resolver that pins some hosts to fixed addresses, and resolves
+/// the rest with the system resolver.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Overrides {
+    hosts: HashMap<String, SocketAddr>,
+}
+
+impl Overrides {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.hosts.is_empty()
+    }
+
+    pub(crate) fn insert(&mut self, host: &str, addr: SocketAddr) {
+        self.hosts.insert(host.to_ascii_lowercase(), addr);
+    }
+}
+
+impl ureq::Resolver for Overrides {
+    fn resolve(&self, netloc: &str) -> io::Result<Vec<SocketAddr>> {
+        let host = netloc.rsplit_once(':').map_or(netloc, |(host, _)| host);
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+
+        match self.hosts.get(&host.to_ascii_lowercase()) {
+            Some(addr) => Ok(vec![*addr]),
+            None => netloc.to_socket_addrs().map(Iterator::collect),
+        }
+    }
+}
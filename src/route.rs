@@ -0,0 +1,167 @@
+//! Typed paths of the pages of <https://jutge.org>, and raw responses to
+//! them.
+
+use std::fmt::Display;
+
+use crate::problem_id_types::{Localized, Unlocalized};
+use crate::{ProblemId, SubmissionId};
+
+/// A page of <https://jutge.org>.
+///
+/// Routes can be fetched as is with
+/// [`Client::get_raw()`](crate::Client::get_raw), for the pages (or the
+/// parts of them) the crate doesn't parse yet. [`Route::Other`] reaches any
+/// other path of the site.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Route {
+    /// The home page.
+    Home,
+
+    /// The dashboard of the logged-in user.
+    Dashboard,
+
+    /// The inbox of the logged-in user.
+    Messages,
+
+    /// The submissions of the logged-in user to all problems.
+    SubmissionHistory,
+
+    /// A page of the public problems, starting from 1.
+    PublicProblems {
+        /// The page.
+        page: u32,
+    },
+
+    /// The statement of a problem.
+    Problem(ProblemId<Localized>),
+
+    /// The zip file with the samples of a problem.
+    ProblemZip(ProblemId<Localized>),
+
+    /// The statistics of a problem.
+    ProblemStatistics(ProblemId<Localized>),
+
+    /// The submissions of the logged-in user to a problem.
+    ProblemSubmissions(ProblemId<Localized>),
+
+    /// A submission.
+    Submission(SubmissionId),
+
+    /// The source code of a submission.
+    SubmissionSource(SubmissionId),
+
+    /// A course.
+    Course(String),
+
+    /// A list of problems of a course.
+    CourseList {
+        /// The course of the list.
+        course_id: String,
+
+        /// The list.
+        list_id: String,
+    },
+
+    /// The progress of the students of a course.
+    CourseProgress(String),
+
+    /// An exam.
+    Exam(String),
+
+    /// The setter page of a problem.
+    InstructorProblem(ProblemId<Unlocalized>),
+
+    /// The submissions of an exam, as seen by its instructors.
+    InstructorExamSubmissions(String),
+
+    /// Any other path of the site, including the query (if any), such as
+    /// `/problems/public?page=1`.
+    Other(String),
+}
+
+impl Route {
+    /// Gets the path of the page in the site.
+    #[must_use]
+    pub fn path(&self) -> String {
+        match self {
+            Self::Home => "/".into(),
+            Self::Dashboard => "/dashboard".into(),
+            Self::Messages => "/messages".into(),
+            Self::SubmissionHistory => "/submissions".into(),
+            Self::PublicProblems { page } => format!("/problems/public?page={page}"),
+            Self::Problem(problem) => format!("/problems/{problem}"),
+            Self::ProblemZip(problem) => format!("/problems/{problem}/zip"),
+            Self::ProblemStatistics(problem) => format!("/problems/{problem}/statistics"),
+            Self::ProblemSubmissions(problem) => format!("/problems/{problem}/submissions"),
+            Self::Submission(id) => format!("/problems/{}/submissions/{}", id.problem(), id.code()),
+            Self::SubmissionSource(id) => {
+                format!(
+                    "/problems/{}/submissions/{}/source",
+                    id.problem(),
+                    id.code()
+                )
+            }
+            Self::Course(course_id) => format!("/courses/{course_id}"),
+            Self::CourseList { course_id, list_id } => format!("/courses/{course_id}/{list_id}"),
+            Self::CourseProgress(course_id) => format!("/courses/{course_id}/progress"),
+            Self::Exam(exam_id) => format!("/exams/{exam_id}"),
+            Self::InstructorProblem(problem) => format!("/instructor/problems/{problem}"),
+            Self::InstructorExamSubmissions(exam_id) => {
+                format!("/instructor/exams/{exam_id}/submissions")
+            }
+            Self::Other(path) => path.clone(),
+        }
+    }
+}
+
+impl Display for Route {
+    /// Formats the route as its path.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.path())
+    }
+}
+
+/// A response of the site, as is.
+///
+/// This is returned by [`Client::get_raw()`](crate::Client::get_raw).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawResponse {
+    /// The HTTP status of the response, such as 200.
+    pub status: u16,
+
+    /// The URL the response came from, after following the redirects.
+    pub url: String,
+
+    /// The headers of the response, in the order they were sent, except
+    /// that the values of a repeated header come together, after its first
+    /// one.
+    pub headers: Vec<(String, String)>,
+
+    /// The body of the response.
+    pub body: Vec<u8>,
+}
+
+impl RawResponse {
+    /// Gets the value of the first header with the given name, ignoring
+    /// case.
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Gets the body as text, replacing the invalid UTF-8 sequences.
+    #[must_use]
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    /// Checks whether the status is a success (2xx).
+    #[must_use]
+    pub const fn is_success(&self) -> bool {
+        self.status >= 200 && self.status < 300
+    }
+}
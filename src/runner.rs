@@ -0,0 +1,203 @@
+//! Local execution of solutions against test cases.
+
+/// A test case: an input and the output expected for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TestCase {
+    /// The name of the test case, such as `sample-1`.
+    pub name: String,
+
+    /// The input given to the solution.
+    pub input: String,
+
+    /// The output the solution must produce.
+    pub expected: String,
+}
+
+#[cfg(feature = "runner")]
+mod execution {
+    use std::io::{Read, Write};
+    use std::process::{Command, Stdio};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use super::TestCase;
+    use crate::{Diff, Result, Verdict};
+
+    /// The result of running a solution against a [`TestCase`].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum TestOutcome {
+        /// The solution produced the expected output.
+        Passed,
+
+        /// The output only differs from the expected one in whitespace.
+        PresentationError(Diff),
+
+        /// The output differs from the expected one.
+        WrongAnswer(Diff),
+
+        /// The solution exited unsuccessfully.
+        RuntimeError {
+            /// The exit code of the solution, if it wasn't killed by a signal.
+            code: Option<i32>,
+
+            /// What the solution wrote to its standard error.
+            stderr: String,
+        },
+
+        /// The solution didn't finish within the time limit.
+        TimeLimitExceeded,
+    }
+
+    impl TestOutcome {
+        /// Checks whether the solution passed the test case.
+        #[must_use]
+        pub const fn is_passed(&self) -> bool {
+            matches!(self, Self::Passed)
+        }
+
+        /// Gets the verdict the judge would give to this outcome.
+        #[must_use]
+        pub fn verdict(&self) -> Verdict {
+            match self {
+                Self::Passed => Verdict::Accepted,
+                Self::PresentationError(_) => Verdict::PresentationError,
+                Self::WrongAnswer(_) => Verdict::WrongAnswer,
+                Self::RuntimeError { .. } | Self::TimeLimitExceeded => Verdict::ExecutionError,
+            }
+        }
+    }
+
+    /// Runs a compiled solution (or an interpreter with a script) against test
+    /// cases.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Runner {
+        program: String,
+        args: Vec<String>,
+        time_limit: Option<Duration>,
+    }
+
+    impl Runner {
+        /// Creates a `Runner` that executes `program` without arguments and
+        /// without a time limit.
+        #[must_use]
+        pub fn new(program: &str) -> Self {
+            Self {
+                program: program.into(),
+                args: Vec::new(),
+                time_limit: None,
+            }
+        }
+
+        /// Adds an argument to the executed command.
+        #[must_use]
+        pub fn arg(mut self, arg: &str) -> Self {
+            self.args.push(arg.into());
+            self
+        }
+
+        /// Sets the maximum (wall clock) time a test case can take.
+        #[must_use]
+        pub fn time_limit(mut self, limit: Duration) -> Self {
+            self.time_limit = Some(limit);
+            self
+        }
+
+        /// Runs the solution against a test case.
+        ///
+        /// # Errors
+        /// Returns [`Error::IoError`](crate::Error::IoError) if the solution
+        /// can't be executed.
+        pub fn run(&self, case: &TestCase) -> Result<TestOutcome> {
+            self.run_timed(case).map(|(outcome, _)| outcome)
+        }
+
+        /// Same as [`Runner::run()`], but also returns the (wall clock) time the
+        /// solution took. If it exceeded the time limit, this is the time it ran
+        /// before being killed.
+        ///
+        /// # Errors
+        /// Returns [`Error::IoError`](crate::Error::IoError) if the solution
+        /// can't be executed.
+        pub fn run_timed(&self, case: &TestCase) -> Result<(TestOutcome, Duration)> {
+            let mut child = Command::new(&self.program)
+                .args(&self.args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()?;
+
+            let stdin = child.stdin.take();
+            let input = case.input.clone();
+            let writer = thread::spawn(move || {
+                if let Some(mut stdin) = stdin {
+                    // The solution may exit without reading all its input.
+                    let _ = stdin.write_all(input.as_bytes());
+                }
+            });
+            let stdout = read_in_background(child.stdout.take());
+            let stderr = read_in_background(child.stderr.take());
+
+            let start = Instant::now();
+            let status = loop {
+                if let Some(status) = child.try_wait()? {
+                    break Some(status);
+                }
+                if self.time_limit.is_some_and(|limit| start.elapsed() > limit) {
+                    child.kill()?;
+                    child.wait()?;
+                    break None;
+                }
+                thread::sleep(Duration::from_millis(5));
+            };
+            let time = start.elapsed();
+
+            let _ = writer.join();
+            let stdout = stdout.join().unwrap_or_default();
+            let stderr = stderr.join().unwrap_or_default();
+
+            let Some(status) = status else {
+                return Ok((TestOutcome::TimeLimitExceeded, time));
+            };
+            if !status.success() {
+                let outcome = TestOutcome::RuntimeError {
+                    code: status.code(),
+                    stderr,
+                };
+                return Ok((outcome, time));
+            }
+
+            let outcome = match Diff::between(&case.expected, &stdout) {
+                None => TestOutcome::Passed,
+                Some(diff) if diff.is_whitespace_only() => TestOutcome::PresentationError(diff),
+                Some(diff) => TestOutcome::WrongAnswer(diff),
+            };
+            Ok((outcome, time))
+        }
+
+        /// Runs the solution against several test cases, returning the outcome
+        /// of each of them in order.
+        ///
+        /// # Errors
+        /// Returns [`Error::IoError`](crate::Error::IoError) if the solution
+        /// can't be executed.
+        pub fn run_all(&self, cases: &[TestCase]) -> Result<Vec<TestOutcome>> {
+            cases.iter().map(|case| self.run(case)).collect()
+        }
+    }
+
+    /// Reads a pipe to a string in a separate thread, so the child process never
+    /// blocks writing to it.
+    fn read_in_background(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+        thread::spawn(move || {
+            let mut output = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut output);
+            }
+            String::from_utf8_lossy(&output).into_owned()
+        })
+    }
+}
+
+#[cfg(feature = "runner")]
+pub use execution::{Runner, TestOutcome};
@@ -0,0 +1,133 @@
+use std::io::{Cursor, Read};
+use std::ops::ControlFlow;
+
+use zip::ZipArchive;
+
+use crate::batch::{run_batch, run_pipeline};
+use crate::problem_id_types::Localized;
+use crate::sans_io::{FetchSamples, Operation, Request, Response};
+use crate::{BatchOutcome, Client, ProblemId, Result, TestCase};
+
+impl Client {
+    /// Downloads the sample test cases of a problem.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`](crate::Error::NotAuthenticated) if
+    /// the problem requires logging in, or
+    /// [`Error::ZipError`](crate::Error::ZipError) if the downloaded archive
+    /// is malformed.
+    pub fn samples(&self, id: &ProblemId<Localized>) -> Result<Vec<TestCase>> {
+        self.execute(&FetchSamples {
+            problem: id.clone(),
+        })
+    }
+
+    /// Downloads the sample test cases of several problems, with up to
+    /// `concurrency` downloads at the same time.
+    ///
+    /// A failed download doesn't stop the rest: the returned
+    /// [`BatchOutcome`] holds the samples or the error of each problem.
+    ///
+    /// The downloads are paced, and their concurrency capped, by the
+    /// [`Politeness`](crate::Politeness) policy of the `Client`.
+    pub fn samples_batch(
+        &self,
+        ids: &[ProblemId<Localized>],
+        concurrency: usize,
+    ) -> BatchOutcome<Vec<TestCase>> {
+        run_batch(ids, self.bulk_concurrency(concurrency), |id| {
+            self.pace_bulk();
+            self.samples(id)
+        })
+    }
+
+    /// Downloads the sample test cases of a stream of problems, with up to
+    /// `concurrency` downloads at the same time, and hands the samples (or
+    /// the error) of each problem to `consume` as soon as they arrive.
+    ///
+    /// Unlike [`Client::samples_batch()`], nothing is collected, so memory
+    /// use stays bounded however many problems there are: ids are pulled
+    /// from `ids` only as downloads finish, and `consume` holds back the
+    /// downloads while it's busy. Problems are consumed in the order their
+    /// downloads finish. Breaking from `consume` stops the downloads.
+    ///
+    /// Like in [`Client::samples_batch()`], the downloads are paced by the
+    /// [`Politeness`](crate::Politeness) policy of the `Client`.
+    pub fn for_each_samples(
+        &self,
+        ids: impl IntoIterator<Item = ProblemId<Localized>, IntoIter: Send>,
+        concurrency: usize,
+        consume: impl FnMut(ProblemId<Localized>, Result<Vec<TestCase>>) -> ControlFlow<()>,
+    ) {
+        let ids = ids.into_iter().map(Ok);
+        // The ids never fail, so neither does the pipeline.
+        let _ = run_pipeline(
+            ids,
+            self.bulk_concurrency(concurrency),
+            |id| {
+                self.pace_bulk();
+                self.samples(id)
+            },
+            consume,
+        );
+    }
+}
+
+impl Operation for FetchSamples {
+    type Output = Vec<TestCase>;
+
+    fn request(&self) -> Request {
+        Request::file(format!("/problems/{}/zip", self.problem))
+    }
+
+    fn parse(&self, response: &Response<'_>) -> Result<Vec<TestCase>> {
+        samples_from_zip(response.body)
+    }
+}
+
+/// Extracts the sample test cases from the zip archive of a problem, as
+/// downloaded from <https://jutge.org>.
+///
+/// Each `sample*.inp` file in the archive is paired with the `.cor` file with
+/// the same name, which contains its expected output. The test cases are
+/// sorted by name.
+///
+/// # Errors
+/// Returns [`Error::ZipError`](crate::Error::ZipError) if the archive is
+/// malformed.
+pub fn samples_from_zip(zip: &[u8]) -> Result<Vec<TestCase>> {
+    let mut archive = ZipArchive::new(Cursor::new(zip))?;
+
+    let stems: Vec<String> = archive
+        .file_names()
+        .filter_map(std::result::Result::ok)
+        .filter_map(|path| {
+            let stem = path.strip_suffix(".inp")?;
+            let name = stem.rsplit('/').next().unwrap_or(stem);
+            name.starts_with("sample").then(|| stem.into())
+        })
+        .collect();
+
+    let mut cases = Vec::new();
+    for stem in stems {
+        let output_path = format!("{stem}.cor");
+        if archive.index_for_name(&output_path).is_none() {
+            continue;
+        }
+
+        cases.push(TestCase {
+            name: stem.rsplit('/').next().unwrap_or(&stem).into(),
+            input: read_entry(&mut archive, &format!("{stem}.inp"))?,
+            expected: read_entry(&mut archive, &output_path)?,
+        });
+    }
+
+    cases.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(cases)
+}
+
+fn read_entry(archive: &mut ZipArchive<Cursor<&[u8]>>, name: &str) -> Result<String> {
+    let mut contents = String::new();
+    archive.by_name(name)?.read_to_string(&mut contents)?;
+    Ok(contents)
+}
@@ -0,0 +1,470 @@
+//! The transport-independent core of the client.
+//!
+//! Each read-only operation of the [`Client`] is described by an
+//! [`Operation`]: the [`Request`] it needs, and a pure function that parses
+//! the [`Response`] into its result. The blocking `Client` is a thin shim
+//! that sends the request with `ureq` (throttled, cached and size-limited as
+//! configured) and hands the response to the operation with
+//! [`Client::execute()`]. Any other transport, such as an async HTTP client,
+//! can drive the same operations, so both behave exactly the same.
+//!
+//! ```no_run
+//! use jutge::sans_io::{FetchStatement, Operation, Response};
+//!
+//! let operation = FetchStatement { problem: "P068688_en".parse()? };
+//! let request = operation.request();
+//!
+//! // Send `request` with any HTTP client...
+//! let url = format!("https://jutge.org{}", request.path);
+//! let body = b"<div id=\"statement\">...</div>";
+//!
+//! let statement = operation.parse(&Response { url: &url, body })?;
+//! # Ok::<(), jutge::Error>(())
+//! ```
+//!
+//! Transports are expected to follow redirects, and to fail with
+//! [`Error::NotAuthenticated`] when a request ends up redirected to the login
+//! page, or when a [file](Expect::File) is served as an HTML page.
+//!
+//! Flows that need several requests and forms, such as logging in or
+//! submitting, still live in the `Client` and the [`Session`](crate::Session).
+
+use scraper::Html;
+
+use crate::problem_id_types::{Localized, Unlocalized};
+use crate::scrape::is_login_page;
+use crate::{Client, Error, Partial, ProblemId, Result, SubmissionId};
+
+/// The HTTP method of a [`Request`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    /// A `GET` request.
+    Get,
+
+    /// A `HEAD` request.
+    Head,
+}
+
+impl Method {
+    /// Gets the name of the method, such as `GET`.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Get => "GET",
+            Self::Head => "HEAD",
+        }
+    }
+}
+
+/// What a [`Request`] expects as the body of its response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Expect {
+    /// An HTML page.
+    Page,
+
+    /// A file, such as a zip archive. Files can be bigger and slower to
+    /// download than pages.
+    File,
+}
+
+/// A request needed by an [`Operation`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Request {
+    /// The method of the request.
+    pub method: Method,
+
+    /// The path of the request in the site, including the query, such as
+    /// `/problems/public?page=1`.
+    pub path: String,
+
+    /// What the body of the response is expected to be.
+    pub expect: Expect,
+
+    /// Whether the response can be cached, because it doesn't change
+    /// between requests (e.g. problem statements).
+    pub cacheable: bool,
+}
+
+impl Request {
+    /// Creates a `GET` request for a page that can't be cached.
+    #[must_use]
+    pub fn page(path: impl Into<String>) -> Self {
+        Self {
+            method: Method::Get,
+            path: path.into(),
+            expect: Expect::Page,
+            cacheable: false,
+        }
+    }
+
+    /// Creates a `GET` request for a file that can be cached.
+    #[must_use]
+    pub fn file(path: impl Into<String>) -> Self {
+        Self {
+            method: Method::Get,
+            path: path.into(),
+            expect: Expect::File,
+            cacheable: true,
+        }
+    }
+
+    /// Marks the response of the request as cacheable.
+    #[must_use]
+    pub fn cacheable(mut self) -> Self {
+        self.cacheable = true;
+        self
+    }
+}
+
+/// The response to a [`Request`], as given to [`Operation::parse()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Response<'a> {
+    /// The absolute URL of the response, after following redirects.
+    pub url: &'a str,
+
+    /// The body of the response.
+    pub body: &'a [u8],
+}
+
+impl Response<'_> {
+    /// Parses the body of the response as an HTML page.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`] if the page is the login form.
+    pub(crate) fn html(&self) -> Result<Html> {
+        let html = Html::parse_document(&String::from_utf8_lossy(self.body));
+
+        if is_login_page(&html) {
+            Err(Error::NotAuthenticated)
+        } else {
+            Ok(html)
+        }
+    }
+}
+
+/// A read-only operation on the site: a request and a parser of its
+/// response.
+pub trait Operation {
+    /// The result of the operation.
+    type Output;
+
+    /// Describes the request needed by the operation.
+    fn request(&self) -> Request;
+
+    /// Parses the response to the request.
+    ///
+    /// # Errors
+    /// Returns the same errors as the corresponding method of the
+    /// [`Client`] or the [`Session`](crate::Session), such as
+    /// [`Error::UnexpectedPageLayout`].
+    fn parse(&self, response: &Response<'_>) -> Result<Self::Output>;
+
+    /// Parses the response to the request, keeping what can be parsed when
+    /// some fields of the page can't.
+    ///
+    /// By default, this is [`Operation::parse()`], which either parses
+    /// every field or fails. Operations whose pages have fields that can be
+    /// missing without making the rest useless override it.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Operation::parse()`], except for the
+    /// failures of the fields that are reported in the [`Partial`].
+    fn parse_partial(&self, response: &Response<'_>) -> Result<Partial<Self::Output>> {
+        self.parse(response).map(Partial::complete)
+    }
+}
+
+/// Fetches the general information about a problem, like
+/// [`Client::problem_info()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchProblemInfo {
+    /// The problem.
+    pub problem: ProblemId<Localized>,
+}
+
+/// Fetches the [`Statement`](crate::Statement) of a problem, like
+/// [`Client::statement()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchStatement {
+    /// The problem.
+    pub problem: ProblemId<Localized>,
+}
+
+/// Fetches the sample test cases of a problem, like [`Client::samples()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchSamples {
+    /// The problem.
+    pub problem: ProblemId<Localized>,
+}
+
+/// Downloads a file attached to a statement, like
+/// [`Client::download_attachment()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchAttachment {
+    /// The path of the file in the site, as found by
+    /// [`Statement::attachments()`](crate::Statement::attachments).
+    pub path: String,
+}
+
+/// Fetches the public statistics of a problem, like
+/// [`Client::problem_stats()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchProblemStats {
+    /// The problem.
+    pub problem: ProblemId<Localized>,
+}
+
+/// Fetches the status of a submission, like
+/// [`Session::submission_status()`](crate::Session::submission_status).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchSubmissionStatus {
+    /// The submission.
+    pub id: SubmissionId,
+}
+
+/// Fetches the submissions of the logged-in user to a problem, like
+/// [`Session::submissions()`](crate::Session::submissions).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchSubmissions {
+    /// The problem.
+    pub problem: ProblemId<Localized>,
+}
+
+/// Fetches a submission, like
+/// [`Session::submission()`](crate::Session::submission).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchSubmission {
+    /// The submission.
+    pub id: SubmissionId,
+}
+
+/// Fetches the source of a submission, like
+/// [`Session::submission_source()`](crate::Session::submission_source).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchSubmissionSource {
+    /// The submission.
+    pub id: SubmissionId,
+}
+
+/// Checks whether the logged-in user got a submission to a problem accepted,
+/// like [`Session::already_accepted()`](crate::Session::already_accepted).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchAlreadyAccepted {
+    /// The problem.
+    pub problem: ProblemId<Localized>,
+}
+
+/// Fetches one page of the public problem index. Pages start at 1.
+///
+/// [`Client::all_public_problems()`] walks all the pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FetchPublicProblems {
+    /// The number of the page.
+    pub page: u32,
+}
+
+/// Fetches the landing page of the site, like [`Client::home()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FetchHome;
+
+/// Fetches the challenges listed by the site, like
+/// [`Client::current_challenges()`], which leaves out the ones that ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FetchChallenges;
+
+/// Fetches the announcements of the logged-in user, like
+/// [`Session::announcements()`](crate::Session::announcements).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FetchAnnouncements;
+
+/// Fetches all the submissions of the logged-in user, like
+/// [`Session::submission_history()`](crate::Session::submission_history).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FetchSubmissionHistory;
+
+/// Fetches the courses the logged-in user is enrolled in, like
+/// [`Session::enrolled_courses()`](crate::Session::enrolled_courses).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FetchEnrolledCourses;
+
+/// Fetches the inbox of the logged-in user, like
+/// [`Session::messages()`](crate::Session::messages).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FetchMessages;
+
+/// Fetches the points of the logged-in user in a list of problems, like
+/// [`Session::list_score()`](crate::Session::list_score).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchListScore {
+    /// The course of the list.
+    pub course_id: String,
+
+    /// The list.
+    pub list_id: String,
+}
+
+/// Fetches the deadline of a list of problems, like
+/// [`Session::list_deadline()`](crate::Session::list_deadline).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchListDeadline {
+    /// The course of the list.
+    pub course_id: String,
+
+    /// The list.
+    pub list_id: String,
+}
+
+/// Fetches the lists of problems of a course, like
+/// [`Session::course_lists()`](crate::Session::course_lists).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchCourseLists {
+    /// The course.
+    pub course_id: String,
+}
+
+/// Fetches the problems of a list of problems, like
+/// [`Session::list_problems()`](crate::Session::list_problems).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchListProblems {
+    /// The course of the list.
+    pub course_id: String,
+
+    /// The list.
+    pub list_id: String,
+}
+
+/// Searches the users of the site, like
+/// [`Client::find_user()`](crate::Client::find_user).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchUserSearch {
+    /// What to search.
+    pub query: String,
+}
+
+/// Fetches the public profile of a user, like
+/// [`Client::user_profile()`](crate::Client::user_profile).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchUserProfile {
+    /// The username of the user.
+    pub username: String,
+}
+
+/// Fetches the progress of the students of a course, like
+/// [`Session::course_progress()`](crate::Session::course_progress).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchCourseProgress {
+    /// The course.
+    pub course_id: String,
+}
+
+/// Fetches the status of a problem as seen by its setters, like
+/// [`Session::setter_status()`](crate::Session::setter_status).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchSetterStatus {
+    /// The problem.
+    pub problem: ProblemId<Unlocalized>,
+}
+
+/// Fetches the points of the logged-in user in an exam, like
+/// [`Session::exam_score()`](crate::Session::exam_score).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchExamScore {
+    /// The exam.
+    pub exam_id: String,
+}
+
+/// Fetches the submissions made in an exam, like
+/// [`Session::exam_submissions()`](crate::Session::exam_submissions).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchExamSubmissions {
+    /// The exam.
+    pub exam_id: String,
+}
+
+/// Fetches the time window of an exam, like
+/// [`Session::exam_window()`](crate::Session::exam_window).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FetchExamWindow {
+    /// The exam.
+    pub exam_id: String,
+}
+
+impl Client {
+    /// Performs an [`Operation`] with the transport of the `Client`.
+    ///
+    /// With [`ClientBuilder::lenient_parsing()`](crate::ClientBuilder::lenient_parsing),
+    /// the fields of the page that can't be parsed are left with defaults
+    /// instead of failing (see [`Client::execute_partial()`]).
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`] if the request requires logging
+    /// in, network errors as [`Error::UreqError`], and the errors of the
+    /// parser of the operation.
+    pub fn execute<O: Operation>(&self, operation: &O) -> Result<O::Output> {
+        if self.lenient_parsing() {
+            Ok(self.execute_partial(operation)?.value)
+        } else {
+            self.fetch_response(&operation.request(), |response| operation.parse(response))
+        }
+    }
+
+    /// Performs an [`Operation`] with the transport of the `Client`,
+    /// keeping what can be parsed when some fields of the page can't (see
+    /// [`Operation::parse_partial()`]).
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Client::execute()`], except for the
+    /// failures of the fields that are reported in the [`Partial`].
+    pub fn execute_partial<O: Operation>(&self, operation: &O) -> Result<Partial<O::Output>> {
+        self.fetch_response(&operation.request(), |response| {
+            operation.parse_partial(response)
+        })
+    }
+
+    /// Sends a request and hands its response to a parser.
+    fn fetch_response<T>(
+        &self,
+        request: &Request,
+        parse: impl FnOnce(&Response<'_>) -> Result<T>,
+    ) -> Result<T> {
+        let path = request.path.as_str();
+
+        match (request.method, request.expect) {
+            (Method::Head, _) => {
+                let response = self.head(path)?;
+                let url = response.get_url().to_owned();
+                parse(&Response {
+                    url: &url,
+                    body: &[],
+                })
+            }
+            (Method::Get, Expect::File) => {
+                let body = if request.cacheable {
+                    self.get_cached_file(path, false)?
+                } else {
+                    self.get_file(path)?.into()
+                };
+                parse(&Response {
+                    url: &self.url(path),
+                    body: &body,
+                })
+            }
+            (Method::Get, Expect::Page) if request.cacheable => {
+                let body = self.get_cached_page_body(path, false)?;
+                parse(&Response {
+                    url: &self.url(path),
+                    body: &body,
+                })
+            }
+            (Method::Get, Expect::Page) => {
+                let response = self.get(path)?;
+                let url = response.get_url().to_owned();
+                let body = self.read_page_body(response)?;
+                parse(&Response {
+                    url: &url,
+                    body: &body,
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! A stable JSON representation of the entities fetched from
+//! <https://jutge.org>, for tools that aren't written in Rust.
+//!
+//! The entities implement [`serde::Serialize`], and [`JsonEntity`] wraps
+//! them in an envelope that tells what they are and which version of the
+//! schema they follow:
+//!
+//! ```json
+//! {"schema_version": 1, "kind": "problem_info", "data": {"id": "P068688_en", ...}}
+//! ```
+//!
+//! Within a [`SCHEMA_VERSION`], fields may be added to the entities, but
+//! none is renamed, removed or changes its type. The field names are the
+//! names of the fields of the Rust types. The values that the crate models
+//! with their own types are written as strings in the format used by the
+//! site:
+//!
+//! - Problem ids as their code, such as `P068688_en`.
+//! - Submission ids as `P068688_en/S001`.
+//! - Verdicts as their code, such as `AC`.
+//! - Compilers as their id, such as `G++17`.
+//! - Languages as their 2 letter code, such as `en`.
+//! - Timestamps in RFC 3339 format, in the Europe/Madrid timezone, such as
+//!   `2024-03-05T14:30:00+01:00`.
+//!
+//! Execution times are written as a number of seconds, such as `0.123`, and
+//! memory usages as a number of bytes.
+//!
+//! Missing values are written as `null`.
+//!
+//! This module is only available with the `serde` feature.
+
+use serde::{Serialize, Serializer};
+use serde_json::{json, Value};
+
+use crate::problem_id_types::ProblemIdType;
+use crate::{
+    Compiler, CourseProgress, EnrolledCourse, ExecutionTime, HistoryEntry, ListEntry, MemoryUsage,
+    ProblemId, ProblemLanguage, ProblemStats, PublicProblem, PublicProfile, Submission,
+    SubmissionId, TestCase, Timestamp, UserSummary, Verdict,
+};
+
+/// The version of the JSON schema of the entities, written in the envelope
+/// of [`JsonEntity::to_json_value()`].
+///
+/// It's increased whenever a change to the schema can break its consumers,
+/// such as renaming or removing a field.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// An entity with a stable JSON representation.
+///
+/// # Example
+/// ```
+/// use jutge::{JsonEntity, UserSummary};
+/// use serde_json::json;
+///
+/// let user = UserSummary {
+///     username: "alexd".into(),
+///     name: "Alex Doe".into(),
+/// };
+/// assert_eq!(
+///     user.to_json_value(),
+///     json!({
+///         "schema_version": 1,
+///         "kind": "user_summary",
+///         "data": {"username": "alexd", "name": "Alex Doe"},
+///     }),
+/// );
+/// ```
+pub trait JsonEntity: Serialize {
+    /// The name of the kind of entity in the envelope, such as
+    /// `problem_info`.
+    const KIND: &'static str;
+
+    /// Converts the entity to JSON, in an envelope with the
+    /// [`SCHEMA_VERSION`] and the [`JsonEntity::KIND`] of the entity.
+    fn to_json_value(&self) -> Value {
+        envelope(Self::KIND, self)
+    }
+
+    /// Converts a list of entities to JSON, in a single envelope whose data
+    /// is an array.
+    fn list_to_json_value(entities: &[Self]) -> Value
+    where
+        Self: Sized,
+    {
+        envelope(Self::KIND, entities)
+    }
+}
+
+/// Wraps the data of an entity in its envelope.
+///
+/// # Panics
+/// Never panics, as the entities of the crate are serialized to JSON
+/// without errors.
+fn envelope(kind: &str, data: &(impl Serialize + ?Sized)) -> Value {
+    json!({
+        "schema_version": SCHEMA_VERSION,
+        "kind": kind,
+        "data": serde_json::to_value(data).expect("Entities should be serializable to JSON"),
+    })
+}
+
+macro_rules! json_entities {
+    ($($entity:ty => $kind:literal),+ $(,)?) => {
+        $(
+            impl JsonEntity for $entity {
+                const KIND: &'static str = $kind;
+            }
+        )+
+    };
+}
+
+json_entities! {
+    CourseProgress => "course_progress",
+    EnrolledCourse => "enrolled_course",
+    HistoryEntry => "history_entry",
+    ListEntry => "list_entry",
+    ProblemStats => "problem_stats",
+    PublicProblem => "public_problem",
+    PublicProfile => "public_profile",
+    Submission => "submission",
+    TestCase => "test_case",
+    UserSummary => "user_summary",
+}
+
+#[cfg(feature = "web-client")]
+json_entities! {
+    crate::Announcement => "announcement",
+    crate::Challenge => "challenge",
+    crate::Message => "message",
+    crate::ProblemInfo => "problem_info",
+}
+
+impl<T: ProblemIdType> Serialize for ProblemId<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl Serialize for SubmissionId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl Serialize for Verdict {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl Serialize for Compiler {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.id())
+    }
+}
+
+impl Serialize for ProblemLanguage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&String::from_utf8_lossy(&self.code()))
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_rfc3339())
+    }
+}
+
+impl Serialize for ExecutionTime {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.duration().as_secs_f64())
+    }
+}
+
+impl Serialize for MemoryUsage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.bytes())
+    }
+}
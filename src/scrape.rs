@@ -0,0 +1,122 @@
+//! Helpers shared by the HTML scrapers of the crate.
+//!
+//! The pages of the site are written by hand and change without notice, so
+//! scrapers must never panic on them: they look for what they need with the
+//! lenient helpers of this module, skip the parts they don't understand, and
+//! fail with [`Error::UnexpectedPageLayout`] when something they can't do
+//! without is missing.
+
+use scraper::{ElementRef, Html, Selector};
+
+use crate::{Error, Score};
+
+/// Parses a CSS selector known at compile time.
+///
+/// # Panics
+/// Panics if `selector` isn't a valid CSS selector.
+pub(crate) fn selector(selector: &str) -> Selector {
+    Selector::parse(selector).expect("Selectors in the crate should be valid!")
+}
+
+impl Error {
+    /// Builds the error for a page that doesn't have what a scraper needs.
+    ///
+    /// `page` is the path of the page in the site, and `hint` tells what was
+    /// missing, such as `no statement found`.
+    pub(crate) fn unexpected_layout(page: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self::UnexpectedPageLayout {
+            page: page.into(),
+            hint: hint.into(),
+        }
+    }
+}
+
+/// Returns the text contents of an element, with whitespace collapsed.
+pub(crate) fn text(element: ElementRef<'_>) -> String {
+    element
+        .text()
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the text of the first element matching `css` inside `element`, if
+/// any.
+pub(crate) fn text_of(element: ElementRef<'_>, css: &str) -> Option<String> {
+    element
+        .select(&selector(css))
+        .next()
+        .map(text)
+        .filter(|s| !s.is_empty())
+}
+
+/// Checks whether a page is the <https://jutge.org> login form.
+pub(crate) fn is_login_page(html: &Html) -> bool {
+    html.select(&selector("form input[name=\"email\"]"))
+        .next()
+        .is_some()
+        && html
+            .select(&selector("form input[name=\"password\"]"))
+            .next()
+            .is_some()
+}
+
+/// Returns the text of the error alert shown in a page after submitting a
+/// form, if any.
+#[cfg(feature = "client-auth")]
+pub(crate) fn error_alert(html: &Html) -> Option<String> {
+    html.select(&selector("div.alert-danger"))
+        .next()
+        .map(text)
+        .filter(|s| !s.is_empty())
+}
+
+/// Checks whether a page tells the user that their account is blocked,
+/// either in its heading or in an error alert.
+#[cfg(feature = "client-auth")]
+pub(crate) fn is_blocked_account_page(html: &Html) -> bool {
+    html.select(&selector("h1, h2, div.alert-danger"))
+        .any(|element| is_blocked_account_message(&text(element)))
+}
+
+/// Checks whether a message of the site says that the account of the user
+/// is blocked, in any of the languages of the site.
+#[cfg(feature = "client-auth")]
+pub(crate) fn is_blocked_account_message(message: &str) -> bool {
+    let message = message.to_lowercase();
+    let blocked = [
+        "blocked",
+        "suspended",
+        "bloquejat",
+        "bloqueado",
+        "bloqueada",
+        "bloqué",
+        "gesperrt",
+    ];
+    let account = [
+        "account",
+        "user",
+        "compte",
+        "usuari",
+        "cuenta",
+        "usuario",
+        "utilisateur",
+        "konto",
+        "benutzer",
+    ];
+
+    blocked.iter().any(|word| message.contains(word))
+        && account.iter().any(|word| message.contains(word))
+}
+
+/// Parses a score shown as `awarded/total`, such as `75/100` or `7.5 / 10`.
+/// Anything after the total, such as `points`, is ignored.
+pub(crate) fn parse_score(value: &str) -> Option<Score> {
+    let (awarded, total) = value.split_once('/')?;
+    let number = |s: &str| s.trim().replace(',', ".").parse::<f64>().ok();
+
+    Some(Score {
+        awarded: number(awarded.split_whitespace().last()?)?,
+        total: number(total.split_whitespace().next()?)?,
+    })
+}
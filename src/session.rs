@@ -0,0 +1,126 @@
+use std::io::Write;
+
+use cookie_store::CookieStore;
+
+use crate::form::Form;
+use crate::scrape::{is_blocked_account_page, is_login_page};
+use crate::{Client, Error, Result};
+
+/// An authenticated session of a user in <https://jutge.org>.
+///
+/// A `Session` is obtained by logging in with [`Client::login()`], or by
+/// resuming a saved session with [`Client::resume()`]. Methods that need a
+/// logged-in user, such as submitting or reading the inbox, are only
+/// available on a `Session`, while the ones that work anonymously stay on
+/// the [`Client`].
+///
+/// Each `Session` has its own cookies and in-memory caches, so a process can
+/// hold sessions of several users at once (e.g. an instructor and a test
+/// student) without them affecting each other, nor the `Client` they were
+/// created from. Requests and submissions are still throttled together with
+/// those of the `Client`.
+///
+/// The site may still end a session at any time, in which case the methods
+/// of the `Session` fail with [`Error::NotAuthenticated`].
+#[derive(Debug)]
+pub struct Session {
+    client: Client,
+    email: Option<String>,
+}
+
+impl Client {
+    /// Logs in to <https://jutge.org> with the given credentials, in a new
+    /// [`Session`].
+    ///
+    /// The cookies of the `Client` aren't used nor changed.
+    ///
+    /// # Errors
+    /// If the credentials are rejected, [`Error::LoginFailed`] will be
+    /// returned, or [`Error::AccountBlocked`] if the site has blocked the
+    /// account. Network errors are returned as [`Error::UreqError`].
+    pub fn login(&self, email: &str, password: &str) -> Result<Session> {
+        let client = self.fork(CookieStore::default());
+
+        let home = client.read_html(client.get("/")?)?;
+        let mut form = Form::containing(&home, "/", "password", &client.url(""))?;
+        form.set("email", email).set("password", password);
+
+        let response = match client.submit_form(&form) {
+            Err(Error::NotAuthenticated) => return Err(Error::LoginFailed),
+            response => response?,
+        };
+
+        let html = client.read_html(response)?;
+        if is_blocked_account_page(&html) {
+            return Err(Error::AccountBlocked);
+        }
+        if is_login_page(&html) {
+            return Err(Error::LoginFailed);
+        }
+
+        // Logging in may switch the interface to the language of the profile
+        // of the user, so a forced one is set again.
+        let client = if self.ui_language().is_some() {
+            client.fork(client.cookies())
+        } else {
+            client
+        };
+
+        Ok(Session {
+            client,
+            email: Some(email.into()),
+        })
+    }
+
+    /// Resumes the session restored with
+    /// [`ClientBuilder::session()`](crate::ClientBuilder::session), checking
+    /// that the site still accepts it.
+    ///
+    /// The new [`Session`] starts with a copy of the cookies of the `Client`,
+    /// so this can be called several times to get independent sessions of
+    /// the same user. The email of the user isn't known in a resumed session.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`] if the `Client` isn't logged in.
+    pub fn resume(&self) -> Result<Session> {
+        let client = self.fork(self.cookies());
+        client.read_authenticated_html(client.get("/dashboard")?)?;
+
+        Ok(Session {
+            client,
+            email: None,
+        })
+    }
+}
+
+impl Session {
+    /// Gets the `Client` of the session, which sends the requests with the
+    /// cookies of the session.
+    ///
+    /// The methods of the `Client` that don't need a logged-in user can be
+    /// called on it too, and see the site as the user does (e.g. private
+    /// problems they have access to).
+    #[must_use]
+    pub const fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Gets the email the user logged in with, if the session was started
+    /// with [`Client::login()`].
+    #[must_use]
+    pub fn email(&self) -> Option<&str> {
+        self.email.as_deref()
+    }
+
+    /// Saves the session, so it can be restored later with
+    /// [`ClientBuilder::session()`](crate::ClientBuilder::session).
+    ///
+    /// The session is written as JSON, and includes the cookies that
+    /// authenticate the user, so it should be stored safely.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidSession`] if the session can't be written.
+    pub fn save(&self, writer: &mut impl Write) -> Result<()> {
+        self.client.save_cookies(writer)
+    }
+}
@@ -0,0 +1,194 @@
+use crate::problem_id_types::Unlocalized;
+use crate::sans_io::{FetchSetterStatus, Operation, Request, Response};
+use crate::scrape::{selector, text};
+use crate::{Partial, ProblemId, ProblemLanguage, Result};
+
+/// The status of a problem as seen by its setters, which isn't shown in its
+/// public page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetterStatus {
+    /// The id of the problem.
+    pub id: ProblemId<Unlocalized>,
+
+    /// Whether the problem is visible to everyone, rather than just to its
+    /// setters and the courses it's assigned to.
+    pub published: bool,
+
+    /// The languages the statement of the problem is available in.
+    pub languages: Vec<ProblemLanguage>,
+
+    /// The number of test cases of the problem, if the site shows it.
+    pub test_cases: Option<u32>,
+
+    /// The problems the site found when checking the package of the problem,
+    /// such as a test case without its expected output.
+    pub errors: Vec<String>,
+}
+
+impl Operation for FetchSetterStatus {
+    type Output = SetterStatus;
+
+    fn request(&self) -> Request {
+        Request::page(format!("/instructor/problems/{}", self.problem))
+    }
+
+    fn parse(&self, response: &Response<'_>) -> Result<SetterStatus> {
+        self.parse_partial(response)?
+            .into_result(self.request().path)
+    }
+
+    /// A status that can't be found is left as not published.
+    fn parse_partial(&self, response: &Response<'_>) -> Result<Partial<SetterStatus>> {
+        let html = response.html()?;
+
+        let mut published = None;
+        let mut languages = Vec::new();
+        let mut test_cases = None;
+        for row in html.select(&selector("table tr")) {
+            let cells: Vec<_> = row.select(&selector("th, td")).map(text).collect();
+            let [label, value, ..] = cells.as_slice() else {
+                continue;
+            };
+
+            let label = label.to_lowercase();
+            let value = value.to_lowercase();
+            if ["status", "estat", "estado"]
+                .iter()
+                .any(|w| label.contains(w))
+            {
+                published = Some(["public", "públic"].iter().any(|w| value.contains(w)));
+            } else if ["language", "idioma"].iter().any(|w| label.contains(w)) {
+                languages = value
+                    .split(|c: char| !c.is_ascii_alphabetic())
+                    .filter_map(|code| code.as_bytes().try_into().ok())
+                    .collect();
+            } else if ["test", "joc", "juego"].iter().any(|w| label.contains(w)) {
+                test_cases = value
+                    .split_whitespace()
+                    .find_map(|number| number.parse().ok());
+            }
+        }
+
+        let errors = html
+            .select(&selector(".alert-danger, ul.errors li"))
+            .map(text)
+            .filter(|error| !error.is_empty())
+            .collect();
+
+        let mut status = Partial::complete(SetterStatus {
+            id: self.problem.clone(),
+            published: published.unwrap_or_default(),
+            languages,
+            test_cases,
+            errors,
+        });
+        if published.is_none() {
+            status.fail("published", "no status found");
+        }
+        Ok(status)
+    }
+}
+
+#[cfg(feature = "client-auth")]
+mod upload {
+    use scraper::Html;
+
+    use super::SetterStatus;
+    use crate::client::instructor_only;
+    use crate::form::FormFile;
+    use crate::problem_id_types::Unlocalized;
+    use crate::sans_io::FetchSetterStatus;
+    use crate::scrape::error_alert;
+    use crate::{Error, ProblemId, Result, Session};
+
+    impl Session {
+        /// Uploads the package of a new problem, as a zip archive with its
+        /// statements, its test cases and its `handler.yml` (see
+        /// [`zip_problem_dir()`](crate::zip_problem_dir)).
+        ///
+        /// Returns the id given to the problem by the site.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAnInstructor`] if the user of the session can't
+        /// set problems, or [`Error::PackageRejected`] if the site doesn't
+        /// accept the package.
+        pub fn upload_problem(&self, package: &[u8]) -> Result<ProblemId<Unlocalized>> {
+            let page = "/instructor/problems/new";
+            let response = self.send_package(page, package)?;
+
+            ProblemId::<Unlocalized>::parse_lenient(
+                response.get_url().rsplit('/').next().unwrap_or_default(),
+            )
+            .or_else(|_| {
+                Err(rejection(
+                    &self.client().read_authenticated_html(response)?,
+                    page,
+                    "uploading didn't lead to a problem page",
+                ))
+            })
+        }
+
+        /// Replaces the package of a problem set by the user of the session.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAnInstructor`] if the user of the session can't
+        /// set the problem, or [`Error::PackageRejected`] if the site doesn't
+        /// accept the package.
+        pub fn update_problem(&self, id: &ProblemId<Unlocalized>, package: &[u8]) -> Result<()> {
+            let page = format!("/instructor/problems/{id}");
+            let edit_page = format!("{page}/edit");
+            let response = self.send_package(&edit_page, package)?;
+
+            if response.get_url() == self.client().url(&page) {
+                Ok(())
+            } else {
+                Err(rejection(
+                    &self.client().read_authenticated_html(response)?,
+                    &edit_page,
+                    "updating didn't lead to the problem page",
+                ))
+            }
+        }
+
+        /// Fetches the status of a problem set by the user of the session.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAnInstructor`] if the user of the session can't
+        /// set the problem, or [`Error::UnexpectedPageLayout`] if the status
+        /// can't be found in the page of the problem.
+        pub fn setter_status(&self, id: &ProblemId<Unlocalized>) -> Result<SetterStatus> {
+            self.client()
+                .execute(&FetchSetterStatus {
+                    problem: id.clone(),
+                })
+                .map_err(instructor_only)
+        }
+
+        /// Submits a package with the form at `page`.
+        fn send_package(&self, page: &str, package: &[u8]) -> Result<ureq::Response> {
+            let client = self.client();
+            let form = client
+                .fetch_form(page, "package")
+                .map_err(instructor_only)?;
+            let file = FormFile {
+                field: "package",
+                filename: "problem.zip",
+                content_type: "application/zip",
+                data: package,
+            };
+
+            client
+                .submit_form_with_files(&form, &[file])
+                .map_err(instructor_only)
+        }
+    }
+
+    /// Builds the error for a package the site didn't accept when sent with the
+    /// form at `page`.
+    fn rejection(html: &Html, page: &str, unexpected: &str) -> Error {
+        match error_alert(html) {
+            Some(reason) => Error::PackageRejected(reason),
+            None => Error::unexpected_layout(page, unexpected),
+        }
+    }
+}
@@ -0,0 +1,601 @@
+//! Persistent storage of the state of sync tools, such as a
+//! [`Watcher`](crate::Watcher) or a workspace [`Manifest`](crate::Manifest).
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::iter::Peekable;
+use std::path::{Path, PathBuf};
+use std::str::{Chars, FromStr};
+
+use crate::course_progress::json_string;
+use crate::{Error, Result};
+
+/// Somewhere to keep records that outlive the process, by key.
+///
+/// The records are stored as text, and read and written as typed values
+/// with [`StateStore::get()`] and [`StateStore::put()`]: any type whose
+/// [`Display`] and [`FromStr`] implementations round-trip can be a record,
+/// such as a [`Manifest`](crate::Manifest) or a
+/// [`WatchState`](crate::WatchState).
+///
+/// The crate provides a [`MemoryStore`] and a [`JsonFileStore`], and with the
+/// `sled` and `sqlite` features, a `SledStore` and a `SqliteStore` that keep
+/// the records in a database. Implement this trait to keep the records
+/// anywhere else.
+pub trait StateStore {
+    /// Reads the text of the record with the given key, if there's one.
+    ///
+    /// # Errors
+    /// Returns an error if the record can't be read.
+    fn load(&self, key: &str) -> Result<Option<String>>;
+
+    /// Writes the text of the record with the given key, replacing the
+    /// previous one.
+    ///
+    /// # Errors
+    /// Returns an error if the record can't be written.
+    fn store(&mut self, key: &str, record: &str) -> Result<()>;
+
+    /// Removes the record with the given key, if there's one.
+    ///
+    /// # Errors
+    /// Returns an error if the record can't be removed.
+    fn delete(&mut self, key: &str) -> Result<()>;
+
+    /// Reads the record with the given key, if there's one.
+    ///
+    /// # Errors
+    /// Fails like [`StateStore::load()`], or with the error of parsing the
+    /// record.
+    fn get<T: FromStr<Err = Error>>(&self, key: &str) -> Result<Option<T>>
+    where
+        Self: Sized,
+    {
+        self.load(key)?.map(|record| record.parse()).transpose()
+    }
+
+    /// Writes the record with the given key, replacing the previous one.
+    ///
+    /// # Errors
+    /// Fails like [`StateStore::store()`].
+    fn put<T: Display>(&mut self, key: &str, record: &T) -> Result<()>
+    where
+        Self: Sized,
+    {
+        self.store(key, &record.to_string())
+    }
+}
+
+impl<S: StateStore + ?Sized> StateStore for Box<S> {
+    fn load(&self, key: &str) -> Result<Option<String>> {
+        (**self).load(key)
+    }
+
+    fn store(&mut self, key: &str, record: &str) -> Result<()> {
+        (**self).store(key, record)
+    }
+
+    fn delete(&mut self, key: &str) -> Result<()> {
+        (**self).delete(key)
+    }
+}
+
+/// A [`StateStore`] that keeps its records in memory, so they're lost when
+/// it's dropped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryStore {
+    records: BTreeMap<String, String>,
+}
+
+impl MemoryStore {
+    /// Creates an empty `MemoryStore`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StateStore for MemoryStore {
+    fn load(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.records.get(key).cloned())
+    }
+
+    fn store(&mut self, key: &str, record: &str) -> Result<()> {
+        self.records.insert(key.into(), record.into());
+        Ok(())
+    }
+
+    fn delete(&mut self, key: &str) -> Result<()> {
+        self.records.remove(key);
+        Ok(())
+    }
+}
+
+/// A [`StateStore`] that keeps its records in a JSON file, as an object
+/// with a string per record.
+///
+/// The whole file is rewritten on every change, through a temporary file
+/// next to it, so a crash in the middle of a write doesn't lose the
+/// previous records.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonFileStore {
+    path: PathBuf,
+    records: BTreeMap<String, String>,
+}
+
+impl JsonFileStore {
+    /// Opens the store kept in a file, which is created on the first
+    /// change if it doesn't exist.
+    ///
+    /// # Errors
+    /// Returns [`Error::IoError`] if the file can't be read, or
+    /// [`Error::InvalidState`] if it isn't a JSON object of strings.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_owned();
+        let records = match fs::read_to_string(&path) {
+            Ok(contents) => parse_object(&contents)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(Self { path, records })
+    }
+
+    /// Gets the path of the file of the store.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn save(&self) -> Result<()> {
+        let records: Vec<_> = self
+            .records
+            .iter()
+            .map(|(key, record)| format!("  {}: {}", json_string(key), json_string(record)))
+            .collect();
+        let json = format!("{{\n{}\n}}\n", records.join(",\n"));
+
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        let mut file = File::create(&temporary)?;
+        file.write_all(json.as_bytes())?;
+        // Otherwise, the file could be renamed before its contents reach
+        // the disk, and a crash would leave it empty.
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&temporary, &self.path)?;
+
+        // Makes the rename itself durable, where directories can be synced.
+        #[cfg(unix)]
+        {
+            let dir = self.path.parent().filter(|dir| !dir.as_os_str().is_empty());
+            File::open(dir.unwrap_or(Path::new(".")))?.sync_all()?;
+        }
+        Ok(())
+    }
+}
+
+impl StateStore for JsonFileStore {
+    fn load(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.records.get(key).cloned())
+    }
+
+    fn store(&mut self, key: &str, record: &str) -> Result<()> {
+        self.records.insert(key.into(), record.into());
+        self.save()
+    }
+
+    fn delete(&mut self, key: &str) -> Result<()> {
+        if self.records.remove(key).is_some() {
+            self.save()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sled")]
+pub use sled_store::SledStore;
+#[cfg(feature = "sqlite")]
+pub use sqlite_store::SqliteStore;
+
+#[cfg(feature = "sled")]
+mod sled_store {
+    use std::path::Path;
+
+    use super::StateStore;
+    use crate::{Error, Result};
+
+    /// A [`StateStore`] that keeps its records in a tree of a `sled`
+    /// database, a value per record.
+    ///
+    /// Every change is flushed to disk before it returns.
+    ///
+    /// This is only available with the `sled` feature.
+    #[derive(Debug, Clone)]
+    pub struct SledStore {
+        tree: sled::Tree,
+        /// The database the store opened itself, which is flushed when the
+        /// store is dropped.
+        db: Option<sled::Db>,
+    }
+
+    impl SledStore {
+        /// Opens the store kept in a `sled` database in a directory, which
+        /// is created if it doesn't exist, using its default tree.
+        ///
+        /// # Errors
+        /// Returns [`Error::SledError`] if the database can't be opened.
+        pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+            let db = sled::open(path)?;
+            Ok(Self {
+                tree: db.open_tree("jutge")?,
+                db: Some(db),
+            })
+        }
+
+        /// Creates a store that keeps its records in a tree of a `sled`
+        /// database opened elsewhere, e.g. to share the database with other
+        /// data.
+        #[must_use]
+        pub fn from_tree(tree: sled::Tree) -> Self {
+            Self { tree, db: None }
+        }
+    }
+
+    impl Drop for SledStore {
+        fn drop(&mut self) {
+            if let Some(db) = &self.db {
+                // Changes are flushed as they are made, so this can only
+                // fail if the disk already did.
+                let _ = db.flush();
+            }
+        }
+    }
+
+    impl StateStore for SledStore {
+        fn load(&self, key: &str) -> Result<Option<String>> {
+            self.tree
+                .get(key)?
+                .map(|record| {
+                    String::from_utf8(record.to_vec())
+                        .map_err(|_| Error::InvalidState(format!("record {key} isn't valid UTF-8")))
+                })
+                .transpose()
+        }
+
+        fn store(&mut self, key: &str, record: &str) -> Result<()> {
+            self.tree.insert(key, record)?;
+            self.tree.flush()?;
+            Ok(())
+        }
+
+        fn delete(&mut self, key: &str) -> Result<()> {
+            if self.tree.remove(key)?.is_some() {
+                self.tree.flush()?;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+mod sqlite_store {
+    use std::path::Path;
+
+    use rusqlite::{params, Connection, OptionalExtension};
+
+    use super::StateStore;
+    use crate::Result;
+
+    /// A [`StateStore`] that keeps its records in an `SQLite` database, in
+    /// a table of keys and records named `jutge_state`.
+    ///
+    /// This is only available with the `sqlite` feature.
+    #[derive(Debug)]
+    pub struct SqliteStore {
+        connection: Connection,
+    }
+
+    impl SqliteStore {
+        /// Opens the store kept in an `SQLite` database file, which is
+        /// created if it doesn't exist.
+        ///
+        /// # Errors
+        /// Returns [`Error::SqliteError`](crate::Error::SqliteError) if the
+        /// database can't be opened or its table can't be created.
+        pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+            Self::from_connection(Connection::open(path)?)
+        }
+
+        /// Creates a store that keeps its records in a database opened
+        /// elsewhere, such as an in-memory one, creating its table if
+        /// needed.
+        ///
+        /// # Errors
+        /// Returns [`Error::SqliteError`](crate::Error::SqliteError) if the
+        /// table can't be created.
+        pub fn from_connection(connection: Connection) -> Result<Self> {
+            connection.execute(
+                "CREATE TABLE IF NOT EXISTS jutge_state \
+                 (key TEXT PRIMARY KEY, record TEXT NOT NULL)",
+                [],
+            )?;
+            Ok(Self { connection })
+        }
+    }
+
+    impl StateStore for SqliteStore {
+        fn load(&self, key: &str) -> Result<Option<String>> {
+            Ok(self
+                .connection
+                .query_row(
+                    "SELECT record FROM jutge_state WHERE key = ?1",
+                    params![key],
+                    |row| row.get(0),
+                )
+                .optional()?)
+        }
+
+        fn store(&mut self, key: &str, record: &str) -> Result<()> {
+            self.connection.execute(
+                "INSERT INTO jutge_state (key, record) VALUES (?1, ?2) \
+                 ON CONFLICT (key) DO UPDATE SET record = excluded.record",
+                params![key, record],
+            )?;
+            Ok(())
+        }
+
+        fn delete(&mut self, key: &str) -> Result<()> {
+            self.connection
+                .execute("DELETE FROM jutge_state WHERE key = ?1", params![key])?;
+            Ok(())
+        }
+    }
+}
+
+/// Parses a JSON object whose values are all strings.
+fn parse_object(json: &str) -> Result<BTreeMap<String, String>> {
+    let invalid = |what: &str| Error::InvalidState(format!("malformed store file: {what}"));
+
+    let mut chars = json.chars().peekable();
+    let mut records = BTreeMap::new();
+    skip_whitespace(&mut chars);
+    if chars.next() != Some('{') {
+        return Err(invalid("expected an object"));
+    }
+    skip_whitespace(&mut chars);
+    if chars.next_if_eq(&'}').is_none() {
+        loop {
+            skip_whitespace(&mut chars);
+            let key = parse_string(&mut chars).ok_or_else(|| invalid("expected a key"))?;
+            skip_whitespace(&mut chars);
+            if chars.next() != Some(':') {
+                return Err(invalid("expected `:`"));
+            }
+            skip_whitespace(&mut chars);
+            let record = parse_string(&mut chars).ok_or_else(|| invalid("expected a string"))?;
+            records.insert(key, record);
+
+            skip_whitespace(&mut chars);
+            match chars.next() {
+                Some(',') => {}
+                Some('}') => break,
+                _ => return Err(invalid("expected `,` or `}`")),
+            }
+        }
+    }
+    skip_whitespace(&mut chars);
+    if chars.next().is_some() {
+        return Err(invalid("trailing characters"));
+    }
+
+    Ok(records)
+}
+
+/// Skips the whitespace before the next JSON token.
+fn skip_whitespace(chars: &mut Peekable<Chars<'_>>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+/// Parses a JSON string, including its quotes.
+fn parse_string(chars: &mut Peekable<Chars<'_>>) -> Option<String> {
+    if chars.next()? != '"' {
+        return None;
+    }
+
+    let mut string = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(string),
+            '\\' => string.push(match chars.next()? {
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                'b' => '\u{8}',
+                'f' => '\u{c}',
+                'u' => {
+                    let code = parse_code_unit(chars)?;
+                    if (0xd800..0xdc00).contains(&code) {
+                        // Characters out of the BMP are escaped as a pair of
+                        // surrogates.
+                        if chars.next()? != '\\' || chars.next()? != 'u' {
+                            return None;
+                        }
+                        let low = parse_code_unit(chars)?;
+                        if !(0xdc00..0xe000).contains(&low) {
+                            return None;
+                        }
+                        char::from_u32(0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00))?
+                    } else {
+                        char::from_u32(code)?
+                    }
+                }
+                c @ ('"' | '\\' | '/') => c,
+                _ => return None,
+            }),
+            c => string.push(c),
+        }
+    }
+}
+
+/// Parses the 4 hexadecimal digits of a `\u` escape of a JSON string.
+fn parse_code_unit(chars: &mut Peekable<Chars<'_>>) -> Option<u32> {
+    let code: String = chars.by_ref().take(4).collect();
+    if code.len() != 4 || !code.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(&code, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::parse_object;
+
+    fn object(json: &str) -> Option<BTreeMap<String, String>> {
+        parse_object(json).ok()
+    }
+
+    fn records(records: &[(&str, &str)]) -> BTreeMap<String, String> {
+        records
+            .iter()
+            .map(|(key, record)| ((*key).into(), (*record).into()))
+            .collect()
+    }
+
+    #[test]
+    fn objects() {
+        assert_eq!(object("{}"), Some(BTreeMap::new()));
+        assert_eq!(object(" \n{ }\n"), Some(BTreeMap::new()));
+        assert_eq!(
+            object("{\"a\": \"1\", \"b\":\"\"}"),
+            Some(records(&[("a", "1"), ("b", "")]))
+        );
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(
+            object(r#"{"k": "\"\\\/\n\r\t\b\f"}"#),
+            Some(records(&[("k", "\"\\/\n\r\t\u{8}\u{c}")]))
+        );
+        assert_eq!(
+            object(r#"{"k": "\u00e9\u00C9 \u20ac"}"#),
+            Some(records(&[("k", "éÉ €")]))
+        );
+    }
+
+    #[test]
+    fn characters_out_of_the_bmp() {
+        assert_eq!(
+            object(r#"{"k": "\ud83d\ude00"}"#),
+            Some(records(&[("k", "😀")]))
+        );
+        assert_eq!(object(r#"{"k": "😀"}"#), Some(records(&[("k", "😀")])));
+    }
+
+    #[test]
+    fn malformed_objects() {
+        for json in [
+            "",
+            "[]",
+            "{",
+            r#"{"a"}"#,
+            r#"{"a": 1}"#,
+            r#"{"a": "1",}"#,
+            r#"{"a": "1"} x"#,
+            r#"{"a": "1" "b": "2"}"#,
+            r#"{"a": "unterminated}"#,
+            r#"{"a": "\x"}"#,
+            r#"{"a": "\u12"}"#,
+            r#"{"a": "\u+123"}"#,
+            r#"{"a": "\ud83d"}"#,
+            r#"{"a": "\ud83d\u0041"}"#,
+            r#"{"a": "\ude00"}"#,
+        ] {
+            assert_eq!(object(json), None, "{json}");
+        }
+    }
+
+    #[cfg(feature = "runner")]
+    #[test]
+    fn json_files_round_trip() {
+        use super::{JsonFileStore, StateStore};
+        use crate::precheck::ScratchDir;
+
+        let dir = ScratchDir::new().unwrap();
+        let path = dir.path().join("state.json");
+        let records = records(&[
+            ("plain", "1"),
+            ("escaped \"key\"", "line 1\nline 2\t\\"),
+            ("controls", "\u{1}\u{1f}"),
+            ("unicode", "Lliçó 😀"),
+        ]);
+
+        let mut store = JsonFileStore::open(&path).unwrap();
+        for (key, record) in &records {
+            store.store(key, record).unwrap();
+        }
+        store.store("deleted", "x").unwrap();
+        store.delete("deleted").unwrap();
+
+        let reopened = JsonFileStore::open(&path).unwrap();
+        assert_eq!(reopened.records, records);
+        assert_eq!(
+            reopened.load("unicode").unwrap().as_deref(),
+            Some("Lliçó 😀")
+        );
+        assert_eq!(reopened.load("deleted").unwrap(), None);
+    }
+
+    #[cfg(all(feature = "sled", feature = "runner"))]
+    #[test]
+    fn sled_stores() {
+        use super::{SledStore, StateStore};
+        use crate::precheck::ScratchDir;
+
+        // A single database, as reopening its directory right away races
+        // with the background flusher of sled for the lock of the files.
+        let dir = ScratchDir::new().unwrap();
+        let db = sled::open(dir.path()).unwrap();
+        let mut store = SledStore::from_tree(db.open_tree("jutge").unwrap());
+        store.store("a", "1").unwrap();
+        store.store("a", "2").unwrap();
+        store.store("b", "Lliçó 😀").unwrap();
+        store.delete("b").unwrap();
+        store.delete("missing").unwrap();
+        drop(store);
+
+        let store = SledStore::from_tree(db.open_tree("jutge").unwrap());
+        assert_eq!(store.load("a").unwrap().as_deref(), Some("2"));
+        assert_eq!(store.load("b").unwrap(), None);
+
+        let dir = ScratchDir::new().unwrap();
+        let mut store = SledStore::open(dir.path()).unwrap();
+        store.store("a", "1").unwrap();
+        assert_eq!(store.load("a").unwrap().as_deref(), Some("1"));
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn sqlite_stores() {
+        use rusqlite::Connection;
+
+        use super::{SqliteStore, StateStore};
+
+        let mut store =
+            SqliteStore::from_connection(Connection::open_in_memory().unwrap()).unwrap();
+        store.store("a", "1").unwrap();
+        store.store("a", "2").unwrap();
+        store.store("b", "Lliçó 😀").unwrap();
+        assert_eq!(store.load("a").unwrap().as_deref(), Some("2"));
+        assert_eq!(store.load("b").unwrap().as_deref(), Some("Lliçó 😀"));
+
+        store.delete("b").unwrap();
+        store.delete("missing").unwrap();
+        assert_eq!(store.load("b").unwrap(), None);
+    }
+}
@@ -0,0 +1,726 @@
+use std::fmt::Write;
+
+use scraper::node::Element;
+use scraper::{ElementRef, Html, Node};
+
+use crate::package::escape_html;
+use crate::problem_id_types::Localized;
+use crate::sans_io::{FetchAttachment, FetchStatement, Operation, Request, Response};
+use crate::scrape::{selector, text};
+use crate::{Client, Partial, ProblemId, Result};
+
+/// The statement of a <https://jutge.org> problem, as shown in its page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Statement {
+    problem: ProblemId<Localized>,
+    url: String,
+    html: String,
+}
+
+/// How formulas are represented by [`Statement::nodes()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MathMode {
+    /// Formulas are kept as [`StatementNode::InlineMath`] and
+    /// [`StatementNode::BlockMath`] nodes with their TeX source, so they can
+    /// be rendered again.
+    Tex,
+
+    /// Formulas are replaced by their TeX source, as plain text.
+    #[default]
+    Text,
+}
+
+/// A piece of the contents of a [`Statement`], as returned by
+/// [`Statement::nodes()`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum StatementNode {
+    /// Some text, with whitespace collapsed (except in preformatted blocks).
+    Text(String),
+
+    /// A formula inside the text, such as `$x^2$` or `\(x^2\)`. Contains its
+    /// TeX source, without the delimiters.
+    InlineMath(String),
+
+    /// A formula shown on its own line, such as `$$x^2$$` or `\[x^2\]`.
+    /// Contains its TeX source, without the delimiters.
+    BlockMath(String),
+
+    /// A line or paragraph break.
+    Break,
+}
+
+/// A file attached to a [`Statement`], such as an image, an audio clip or a
+/// data set, as returned by [`Statement::attachments()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Attachment {
+    /// The name of the file, such as `figure.png`.
+    pub name: String,
+
+    /// The path of the file in the site, such as
+    /// `/problems/P068688_en/figure.png`.
+    pub path: String,
+
+    /// The MIME type of the file, such as `image/png`, as declared by the
+    /// statement or guessed from the extension of its name.
+    pub mime_type: String,
+}
+
+/// The elements that start a new line in [`Statement::nodes()`].
+const BLOCK_ELEMENTS: &[&str] = &[
+    "blockquote",
+    "br",
+    "caption",
+    "center",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "table",
+    "tr",
+    "ul",
+];
+
+/// The delimiters of formulas written in the text of a statement, and
+/// whether they delimit block formulas. Longer delimiters go first, so `$$`
+/// isn't taken for an empty `$` formula.
+const MATH_DELIMITERS: &[(&str, &str, bool)] = &[
+    ("$$", "$$", true),
+    ("\\[", "\\]", true),
+    ("\\(", "\\)", false),
+    ("$", "$", false),
+];
+
+/// The elements kept by [`Statement::sanitized_html()`].
+const ALLOWED_ELEMENTS: &[&str] = &[
+    "a",
+    "abbr",
+    "b",
+    "blockquote",
+    "br",
+    "caption",
+    "center",
+    "code",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "kbd",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "q",
+    "s",
+    "samp",
+    "small",
+    "span",
+    "strong",
+    "sub",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "tt",
+    "u",
+    "ul",
+    "var",
+];
+
+/// The elements removed by [`Statement::sanitized_html()`] together with
+/// their contents. Other elements that aren't allowed are replaced by their
+/// contents.
+const REMOVED_ELEMENTS: &[&str] = &[
+    "applet", "base", "button", "embed", "form", "frame", "frameset", "head", "iframe", "input",
+    "link", "math", "meta", "noscript", "object", "script", "select", "style", "svg", "template",
+    "textarea", "title",
+];
+
+/// The attributes kept by [`Statement::sanitized_html()`].
+const ALLOWED_ATTRIBUTES: &[&str] = &[
+    "align", "alt", "class", "colspan", "height", "href", "rowspan", "src", "title", "width",
+];
+
+/// The elements and attributes that point to the attachments of a
+/// statement.
+const ATTACHMENT_SOURCES: &[(&str, &str)] = &[
+    ("img", "src"),
+    ("audio", "src"),
+    ("video", "src"),
+    ("source", "src"),
+    ("track", "src"),
+    ("embed", "src"),
+    ("object", "data"),
+    ("a", "href"),
+];
+
+/// The MIME types of the extensions of the files usually attached to
+/// statements.
+const MIME_TYPES: &[(&str, &str)] = &[
+    ("csv", "text/csv"),
+    ("gif", "image/gif"),
+    ("gz", "application/gzip"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("json", "application/json"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+    ("ogg", "audio/ogg"),
+    ("pdf", "application/pdf"),
+    ("png", "image/png"),
+    ("svg", "image/svg+xml"),
+    ("tar", "application/x-tar"),
+    ("txt", "text/plain"),
+    ("wav", "audio/wav"),
+    ("webm", "video/webm"),
+    ("webp", "image/webp"),
+    ("zip", "application/zip"),
+];
+
+/// The elements that have no closing tag.
+const VOID_ELEMENTS: &[&str] = &["br", "hr", "img"];
+
+impl Statement {
+    /// Gets the problem this statement belongs to.
+    #[must_use]
+    pub const fn problem(&self) -> &ProblemId<Localized> {
+        &self.problem
+    }
+
+    /// Gets the HTML of the statement, exactly as served by the site.
+    ///
+    /// This HTML may contain scripts and links relative to the problem page,
+    /// so it shouldn't be embedded as is. See [`Statement::sanitized_html()`].
+    #[must_use]
+    pub fn html(&self) -> &str {
+        &self.html
+    }
+
+    /// Gets the text of the statement, with whitespace collapsed.
+    #[must_use]
+    pub fn text(&self) -> String {
+        let fragment = Html::parse_fragment(&self.html);
+        text(fragment.root_element())
+    }
+
+    /// Splits the statement into text, formulas and breaks, so it can be
+    /// rendered in Markdown or in a terminal.
+    ///
+    /// Formulas are found in the TeX source of the statement, whether it's
+    /// written between the usual delimiters (`$...$`, `\(...\)`, `$$...$$`
+    /// and `\[...\]`), stored in `MathJax` `math/tex` scripts or in the TeX
+    /// annotation of `MathML`. The output rendered by `MathJax`, if any, is
+    /// ignored. With [`MathMode::Text`], formulas are turned into plain text
+    /// instead.
+    #[must_use]
+    pub fn nodes(&self, math: MathMode) -> Vec<StatementNode> {
+        let fragment = Html::parse_fragment(&self.html);
+        let mut pieces = Vec::new();
+        collect_pieces(fragment.root_element(), &mut pieces);
+
+        let mut nodes = Vec::new();
+        let mut raw = String::new();
+        for piece in pieces {
+            match piece {
+                Piece::Raw(text) => raw.push_str(&text),
+                Piece::Node(node) => {
+                    split_math(&std::mem::take(&mut raw), &mut nodes);
+                    nodes.push(node);
+                }
+            }
+        }
+        split_math(&raw, &mut nodes);
+
+        normalize(nodes, math)
+    }
+
+    /// Finds the files attached to the statement and served by the site,
+    /// such as its images, audio clips and data sets, in the order they
+    /// appear. They can be downloaded with [`Client::download_attachment()`].
+    ///
+    /// Links to other pages, such as the versions of the problem in other
+    /// languages, and files hosted elsewhere aren't attachments.
+    #[must_use]
+    pub fn attachments(&self) -> Vec<Attachment> {
+        let fragment = Html::parse_fragment(&self.html);
+
+        let mut attachments: Vec<Attachment> = Vec::new();
+        for element in fragment
+            .root_element()
+            .descendants()
+            .filter_map(ElementRef::wrap)
+        {
+            if let Some(attachment) = self.attachment(element.value()) {
+                if attachments
+                    .iter()
+                    .all(|other| other.path != attachment.path)
+                {
+                    attachments.push(attachment);
+                }
+            }
+        }
+        attachments
+    }
+
+    /// Gets the attachment an element of the statement points to, if any.
+    fn attachment(&self, element: &Element) -> Option<Attachment> {
+        let (_, attribute) = ATTACHMENT_SOURCES
+            .iter()
+            .find(|(name, _)| *name == element.name())?;
+        let origin = self.resolve("/")?;
+        let url = self.resolve(element.attr(attribute)?)?;
+        let path = format!("/{}", url.strip_prefix(&origin)?);
+        let path = path.split('#').next().unwrap_or_default();
+
+        let name = path.split('?').next()?.rsplit('/').next()?;
+        let extension = name
+            .rsplit_once('.')
+            .map(|(_, extension)| extension.to_ascii_lowercase());
+        // Links point to files only if they have an extension, and not the
+        // one of a page.
+        let is_file = extension
+            .as_deref()
+            .is_some_and(|extension| !["htm", "html", "php"].contains(&extension));
+        if name.is_empty() || (element.name() == "a" && !is_file) {
+            return None;
+        }
+
+        let mime_type = element
+            .attr("type")
+            .filter(|mime_type| mime_type.contains('/'))
+            .or_else(|| {
+                let extension = extension?;
+                MIME_TYPES
+                    .iter()
+                    .find(|(known, _)| *known == extension)
+                    .map(|(_, mime_type)| *mime_type)
+            })
+            .unwrap_or("application/octet-stream");
+
+        Some(Attachment {
+            name: name.into(),
+            path: path.into(),
+            mime_type: mime_type.into(),
+        })
+    }
+
+    /// Gets the HTML of the statement, made safe to embed in other
+    /// documents, such as the views of a desktop app or a web dashboard.
+    ///
+    /// Only an allowlist of formatting elements and attributes is kept:
+    /// scripts, styles, frames, forms and their contents are removed, as are
+    /// event handlers and inline styles. Relative links and images are
+    /// rewritten to absolute URLs, so the result doesn't depend on the page
+    /// it's embedded in, and links with a scheme other than `http`, `https`
+    /// or `mailto` (such as `javascript:`) are dropped. Images may only
+    /// point to `http` or `https` URLs, or be `data:image/` URLs.
+    #[must_use]
+    pub fn sanitized_html(&self) -> String {
+        let fragment = Html::parse_fragment(&self.html);
+        let mut sanitized = String::new();
+        self.sanitize_children(fragment.root_element(), &mut sanitized);
+        sanitized
+    }
+
+    /// Writes the sanitized children of an element to `out`.
+    fn sanitize_children(&self, element: ElementRef<'_>, out: &mut String) {
+        for child in element.children() {
+            if let Node::Text(text) = child.value() {
+                out.push_str(&escape_html(text));
+            } else if let Some(child) = ElementRef::wrap(child) {
+                self.sanitize_element(child, out);
+            }
+        }
+    }
+
+    /// Writes a sanitized element, with its children, to `out`.
+    fn sanitize_element(&self, element: ElementRef<'_>, out: &mut String) {
+        let name = element.value().name();
+
+        if REMOVED_ELEMENTS.contains(&name) {
+            return;
+        }
+        if !ALLOWED_ELEMENTS.contains(&name) {
+            self.sanitize_children(element, out);
+            return;
+        }
+
+        out.push('<');
+        out.push_str(name);
+        for (attribute, value) in element.value().attrs() {
+            if !ALLOWED_ATTRIBUTES.contains(&attribute) {
+                continue;
+            }
+
+            let value = match attribute {
+                "href" => self.resolve(value).filter(|url| is_safe_link(url)),
+                "src" => self.resolve(value).filter(|url| is_safe_image(url)),
+                _ => Some(value.into()),
+            };
+            if let Some(value) = value {
+                let _ = write!(out, " {attribute}=\"{}\"", escape_html(&value));
+            }
+        }
+        if name == "a" {
+            out.push_str(" rel=\"noopener noreferrer\"");
+        }
+        out.push('>');
+
+        if VOID_ELEMENTS.contains(&name) {
+            return;
+        }
+
+        self.sanitize_children(element, out);
+        let _ = write!(out, "</{name}>");
+    }
+
+    /// Resolves a URL found in the statement against the URL of the problem
+    /// page. Fragments are kept as is, as they point inside the statement.
+    fn resolve(&self, url: &str) -> Option<String> {
+        let url = url.trim();
+        let (scheme, rest) = self.url.split_once("://")?;
+        let origin_len = scheme.len() + 3 + rest.find('/').unwrap_or(rest.len());
+
+        let resolved = if url.starts_with('#') || has_scheme(url) {
+            url.into()
+        } else if let Some(url) = url.strip_prefix("//") {
+            format!("{scheme}://{url}")
+        } else if url.starts_with('/') {
+            format!("{}{url}", &self.url[..origin_len])
+        } else {
+            let directory = self.url.rfind('/').filter(|&end| end >= origin_len);
+            match directory {
+                Some(end) => format!("{}{url}", &self.url[..=end]),
+                None => format!("{}/{url}", &self.url[..origin_len]),
+            }
+        };
+
+        Some(resolved)
+    }
+}
+
+/// A piece of a statement found while walking its HTML: either text that may
+/// contain formulas, or an already parsed node.
+enum Piece {
+    Raw(String),
+    Node(StatementNode),
+}
+
+/// Walks the children of an element, collecting their text, formulas and
+/// breaks.
+fn collect_pieces(element: ElementRef<'_>, pieces: &mut Vec<Piece>) {
+    for child in element.children() {
+        if let Node::Text(text) = child.value() {
+            pieces.push(Piece::Raw(text.to_string()));
+            continue;
+        }
+        let Some(child) = ElementRef::wrap(child) else {
+            continue;
+        };
+
+        let element = child.value();
+        let name = element.name();
+        if let Some(math) = math_node(child) {
+            pieces.push(Piece::Node(math));
+        } else if name == "script"
+            || REMOVED_ELEMENTS.contains(&name)
+            || element.classes().any(|class| class.starts_with("MathJax"))
+        {
+            // Scripts, styles and the output rendered by MathJax.
+        } else if name == "pre" {
+            pieces.push(Piece::Node(StatementNode::Break));
+            pieces.push(Piece::Node(StatementNode::Text(child.text().collect())));
+            pieces.push(Piece::Node(StatementNode::Break));
+        } else if BLOCK_ELEMENTS.contains(&name) {
+            pieces.push(Piece::Node(StatementNode::Break));
+            collect_pieces(child, pieces);
+            pieces.push(Piece::Node(StatementNode::Break));
+        } else {
+            collect_pieces(child, pieces);
+        }
+    }
+}
+
+/// Gets the formula stored in an element, if it's a `MathJax` script or
+/// `MathML`.
+fn math_node(element: ElementRef<'_>) -> Option<StatementNode> {
+    let value = element.value();
+
+    let (tex, display) = match value.name() {
+        "script" => {
+            let kind = value.attr("type")?;
+            if !kind.starts_with("math/tex") {
+                return None;
+            }
+            (element.text().collect(), kind.contains("mode=display"))
+        }
+        "math" | "mjx-container" => {
+            let tex = element
+                .select(&selector("annotation[encoding=\"application/x-tex\"]"))
+                .next()
+                .map_or_else(|| text(element), |annotation| annotation.text().collect());
+            let display =
+                value.attr("display") == Some("block") || value.attr("display") == Some("true");
+            (tex, display)
+        }
+        _ => return None,
+    };
+
+    let tex = tex.trim().to_owned();
+    Some(if display {
+        StatementNode::BlockMath(tex)
+    } else {
+        StatementNode::InlineMath(tex)
+    })
+}
+
+/// Splits text into text and formulas written between their delimiters,
+/// collapsing the whitespace of the text. `\$` is an escaped dollar sign.
+fn split_math(raw: &str, nodes: &mut Vec<StatementNode>) {
+    let mut text = String::new();
+    let mut rest = raw;
+
+    while let Some(start) = rest.find(['$', '\\']) {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("\\$") {
+            text.push('$');
+            rest = after;
+            continue;
+        }
+
+        let formula = MATH_DELIMITERS.iter().find_map(|&(open, close, display)| {
+            let body = rest.strip_prefix(open)?;
+            let end = find_closing(body, close)?;
+            let tex = &body[..end];
+            if open == "$"
+                && (tex.is_empty()
+                    || tex.starts_with(char::is_whitespace)
+                    || tex.ends_with(char::is_whitespace))
+            {
+                // Most likely a dollar sign, as in "costs $5 or $6".
+                return None;
+            }
+            Some((tex, display, open.len() + end + close.len()))
+        });
+
+        if let Some((tex, display, len)) = formula {
+            nodes.push(StatementNode::Text(collapse_whitespace(&text)));
+            text.clear();
+            let tex = tex.trim().to_owned();
+            nodes.push(if display {
+                StatementNode::BlockMath(tex)
+            } else {
+                StatementNode::InlineMath(tex)
+            });
+            rest = &rest[len..];
+        } else if let Some(c) = rest.chars().next() {
+            text.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    text.push_str(rest);
+    nodes.push(StatementNode::Text(collapse_whitespace(&text)));
+}
+
+/// Finds the closing delimiter of a formula, skipping escaped dollar signs.
+fn find_closing(body: &str, close: &str) -> Option<usize> {
+    body.match_indices(close)
+        .map(|(index, _)| index)
+        .find(|&index| !(close.starts_with('$') && body[..index].ends_with('\\')))
+}
+
+/// Merges adjacent text and breaks, trims the text around breaks, and
+/// removes empty text and breaks at the start and the end.
+fn normalize(nodes: Vec<StatementNode>, math: MathMode) -> Vec<StatementNode> {
+    let mut merged: Vec<StatementNode> = Vec::new();
+    for node in nodes {
+        let node = match (node, math) {
+            (StatementNode::InlineMath(tex) | StatementNode::BlockMath(tex), MathMode::Text) => {
+                StatementNode::Text(tex)
+            }
+            (node, _) => node,
+        };
+
+        match (merged.last_mut(), node) {
+            (Some(StatementNode::Text(last)), StatementNode::Text(text)) => last.push_str(&text),
+            (_, node) => merged.push(node),
+        }
+    }
+
+    let is_break = |node: Option<&StatementNode>| matches!(node, None | Some(StatementNode::Break));
+    let mut trimmed = Vec::with_capacity(merged.len());
+    for (index, node) in merged.iter().enumerate() {
+        match node {
+            StatementNode::Text(text) => {
+                let mut text = text.as_str();
+                if is_break(index.checked_sub(1).and_then(|index| merged.get(index))) {
+                    text = text.trim_start();
+                }
+                if is_break(merged.get(index + 1)) {
+                    text = text.trim_end();
+                }
+                if !text.is_empty() {
+                    trimmed.push(StatementNode::Text(text.into()));
+                }
+            }
+            StatementNode::Break if is_break(trimmed.last()) => {}
+            node => trimmed.push(node.clone()),
+        }
+    }
+    if trimmed.last() == Some(&StatementNode::Break) {
+        trimmed.pop();
+    }
+
+    trimmed
+}
+
+/// Replaces every run of whitespace in a text by a single space.
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut in_whitespace = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !in_whitespace {
+                collapsed.push(' ');
+            }
+            in_whitespace = true;
+        } else {
+            collapsed.push(c);
+            in_whitespace = false;
+        }
+    }
+    collapsed
+}
+
+/// Checks whether a URL starts with a scheme, such as `https:`.
+fn has_scheme(url: &str) -> bool {
+    url.split_once(':').is_some_and(|(scheme, _)| {
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+    })
+}
+
+/// Checks whether a resolved URL can be the target of a link.
+fn is_safe_link(url: &str) -> bool {
+    let url = url.to_ascii_lowercase();
+    url.starts_with('#')
+        || ["http:", "https:", "mailto:"]
+            .iter()
+            .any(|scheme| url.starts_with(scheme))
+}
+
+/// Checks whether a resolved URL can be the source of an image.
+fn is_safe_image(url: &str) -> bool {
+    let url = url.to_ascii_lowercase();
+    ["http:", "https:", "data:image/"]
+        .iter()
+        .any(|scheme| url.starts_with(scheme))
+        && !url.starts_with("data:image/svg")
+}
+
+impl Client {
+    /// Downloads the statement of a problem.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`](crate::Error::NotAuthenticated)
+    /// if the problem requires logging in, or
+    /// [`Error::UnexpectedPageLayout`](crate::Error::UnexpectedPageLayout)
+    /// if the statement can't be found in the problem page.
+    pub fn statement(&self, id: &ProblemId<Localized>) -> Result<Statement> {
+        self.execute(&FetchStatement {
+            problem: id.clone(),
+        })
+    }
+
+    /// Downloads a file attached to a statement, found with
+    /// [`Statement::attachments()`].
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`](crate::Error::NotAuthenticated)
+    /// if the file requires logging in, or
+    /// [`Error::CorruptDownload`](crate::Error::CorruptDownload) if it's
+    /// truncated.
+    pub fn download_attachment(&self, attachment: &Attachment) -> Result<Vec<u8>> {
+        self.execute(&FetchAttachment {
+            path: attachment.path.clone(),
+        })
+    }
+}
+
+impl Operation for FetchStatement {
+    type Output = Statement;
+
+    fn request(&self) -> Request {
+        Request::page(format!("/problems/{}", self.problem)).cacheable()
+    }
+
+    fn parse(&self, response: &Response<'_>) -> Result<Statement> {
+        self.parse_partial(response)?
+            .into_result(self.request().path)
+    }
+
+    /// A statement that can't be found is left empty, keeping its URL.
+    fn parse_partial(&self, response: &Response<'_>) -> Result<Partial<Statement>> {
+        let parts: Vec<_> = response
+            .html()?
+            .select(&selector("#statement, .statement"))
+            .map(|part| part.html())
+            .collect();
+
+        let mut statement = Partial::complete(Statement {
+            problem: self.problem.clone(),
+            url: response.url.into(),
+            html: parts.join("\n"),
+        });
+        if parts.is_empty() {
+            statement.fail("html", "no statement found");
+        }
+        Ok(statement)
+    }
+}
+
+impl Operation for FetchAttachment {
+    type Output = Vec<u8>;
+
+    fn request(&self) -> Request {
+        Request::file(self.path.clone())
+    }
+
+    fn parse(&self, response: &Response<'_>) -> Result<Vec<u8>> {
+        Ok(response.body.to_vec())
+    }
+}
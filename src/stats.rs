@@ -0,0 +1,159 @@
+/// The public statistics of a <https://jutge.org> problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ProblemStats {
+    /// The total number of submissions to the problem.
+    pub submissions: u32,
+
+    /// The number of submissions that got accepted.
+    pub accepted_submissions: u32,
+
+    /// The number of users that submitted a solution to the problem.
+    pub users: u32,
+
+    /// The number of users that got at least one submission accepted.
+    pub accepted_users: u32,
+}
+
+impl ProblemStats {
+    /// Estimates how hard the problem is, as a number between 0 (trivial)
+    /// and 1 (nobody solves it).
+    ///
+    /// The estimate averages the ratio of users that fail to solve the
+    /// problem and the ratio of rejected submissions. Both ratios are
+    /// smoothed with a uniform prior, so problems with few submissions get
+    /// estimates close to 0.5 instead of extreme values.
+    #[must_use]
+    pub fn difficulty_estimate(&self) -> f64 {
+        fn failure_ratio(accepted: u32, total: u32) -> f64 {
+            let accepted = f64::from(accepted.min(total));
+            let total = f64::from(total);
+
+            1.0 - (accepted + 1.0) / (total + 2.0)
+        }
+
+        let users = failure_ratio(self.accepted_users, self.users);
+        let submissions = failure_ratio(self.accepted_submissions, self.submissions);
+
+        f64::midpoint(users, submissions)
+    }
+
+    /// Sorts a list of problems from easiest to hardest, according to
+    /// [`ProblemStats::difficulty_estimate()`].
+    ///
+    /// Each problem is identified by a key of any type (such as a
+    /// [`ProblemId`](crate::ProblemId)), which is returned along with its
+    /// difficulty estimate.
+    pub fn rank_by_difficulty<K>(problems: impl IntoIterator<Item = (K, Self)>) -> Vec<(K, f64)> {
+        let mut ranking: Vec<_> = problems
+            .into_iter()
+            .map(|(key, stats)| (key, stats.difficulty_estimate()))
+            .collect();
+
+        ranking.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+        ranking
+    }
+}
+
+#[cfg(feature = "web-client")]
+mod fetch {
+    use scraper::Html;
+
+    use super::ProblemStats;
+    use crate::problem_id_types::Localized;
+    use crate::sans_io::{FetchProblemStats, Operation, Request, Response};
+    use crate::scrape::{selector, text};
+    use crate::{Client, Partial, ProblemId, Result};
+
+    impl Client {
+        /// Fetches the public statistics of a problem.
+        ///
+        /// # Errors
+        /// Returns [`Error::UnexpectedPageLayout`](crate::Error::UnexpectedPageLayout)
+        /// if the statistics page can't be parsed. With
+        /// [`ClientBuilder::lenient_parsing()`](crate::ClientBuilder::lenient_parsing),
+        /// missing counters are left as zero instead.
+        pub fn problem_stats(&self, id: &ProblemId<Localized>) -> Result<ProblemStats> {
+            self.execute(&FetchProblemStats {
+                problem: id.clone(),
+            })
+        }
+    }
+
+    impl Operation for FetchProblemStats {
+        type Output = ProblemStats;
+
+        fn request(&self) -> Request {
+            Request::page(format!("/problems/{}/statistics", self.problem))
+        }
+
+        fn parse(&self, response: &Response<'_>) -> Result<ProblemStats> {
+            self.parse_partial(response)?
+                .into_result(self.request().path)
+        }
+
+        /// Counters that can't be found are left as zero.
+        fn parse_partial(&self, response: &Response<'_>) -> Result<Partial<ProblemStats>> {
+            Ok(parse_stats(&response.html()?))
+        }
+    }
+
+    fn parse_stats(html: &Html) -> Partial<ProblemStats> {
+        let mut submissions = None;
+        let mut accepted_submissions = None;
+        let mut users = None;
+        let mut accepted_users = None;
+
+        for row in html.select(&selector("table tr")) {
+            let cells: Vec<_> = row.select(&selector("th, td")).map(text).collect();
+            let [label, value, ..] = cells.as_slice() else {
+                continue;
+            };
+            let Ok(value) = value.replace(['.', ',', ' '], "").parse::<u32>() else {
+                continue;
+            };
+
+            let label = label.to_lowercase();
+            let about_users = ["user", "usuari", "utilisat", "benutzer"]
+                .iter()
+                .any(|w| label.contains(w));
+            let about_submissions = ["submi", "envi", "soumi", "einsend"]
+                .iter()
+                .any(|w| label.contains(w));
+            let about_accepted = ["accept", "acept", "akzept"]
+                .iter()
+                .any(|w| label.contains(w));
+
+            let slot = match (about_users, about_submissions, about_accepted) {
+                (true, _, true) => &mut accepted_users,
+                (true, _, false) => &mut users,
+                (false, true, true) => &mut accepted_submissions,
+                (false, true, false) => &mut submissions,
+                (false, false, _) => continue,
+            };
+            slot.get_or_insert(value);
+        }
+
+        let mut stats = Partial::complete(ProblemStats {
+            submissions: submissions.unwrap_or_default(),
+            accepted_submissions: accepted_submissions.unwrap_or_default(),
+            users: users.unwrap_or_default(),
+            accepted_users: accepted_users.unwrap_or_default(),
+        });
+        let counters = [
+            ("submissions", submissions),
+            ("accepted_submissions", accepted_submissions),
+            ("users", users),
+            ("accepted_users", accepted_users),
+        ];
+        for (field, counter) in counters {
+            if counter.is_none() {
+                stats.fail(
+                    field,
+                    format!("missing counter of {}", field.replace('_', " ")),
+                );
+            }
+        }
+        stats
+    }
+}
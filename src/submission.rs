@@ -0,0 +1,700 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::problem_id_types::Localized;
+use crate::workspace::StableHasher;
+use crate::{Compiler, Error, ExecutionTime, MemoryUsage, ProblemId, Result, Timestamp, Verdict};
+
+/// Identifies a submission to a <https://jutge.org> problem.
+///
+/// The site numbers the submissions of each user to each problem
+/// sequentially (`S001`, `S002`, ...), so a submission is identified by the
+/// problem and its number.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SubmissionId {
+    problem: ProblemId<Localized>,
+    number: u32,
+}
+
+impl SubmissionId {
+    /// Creates a `SubmissionId` from a problem and a submission number.
+    #[must_use]
+    pub const fn new(problem: ProblemId<Localized>, number: u32) -> Self {
+        Self { problem, number }
+    }
+
+    /// Gets the problem the submission was sent to.
+    #[must_use]
+    pub const fn problem(&self) -> &ProblemId<Localized> {
+        &self.problem
+    }
+
+    /// Gets the number of the submission.
+    #[must_use]
+    pub const fn number(&self) -> u32 {
+        self.number
+    }
+
+    /// Gets the code the site uses for this submission, such as `S001`.
+    #[must_use]
+    pub fn code(&self) -> String {
+        format!("S{:03}", self.number)
+    }
+}
+
+impl Display for SubmissionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.problem, self.code())
+    }
+}
+
+impl FromStr for SubmissionId {
+    type Err = Error;
+
+    /// Parses a `SubmissionId` in the format used by its `Display`
+    /// implementation, such as `P068688_en/S001`.
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidSubmissionId(s.into());
+
+        let (problem, code) = s.split_once('/').ok_or_else(invalid)?;
+        let number = code
+            .strip_prefix('S')
+            .and_then(|number| number.parse().ok())
+            .ok_or_else(invalid)?;
+
+        Ok(Self::new(problem.parse()?, number))
+    }
+}
+
+/// The status of a submission in the judge queue.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "state", content = "verdict", rename_all = "snake_case")
+)]
+pub enum SubmissionStatus {
+    /// The submission is waiting to be judged.
+    Queued,
+
+    /// The submission is being compiled.
+    Compiling,
+
+    /// The submission is being run against the test cases.
+    Running,
+
+    /// The submission has been judged.
+    Judged(Verdict),
+}
+
+impl SubmissionStatus {
+    /// Checks whether the submission has been judged, so its status won't
+    /// change anymore.
+    #[must_use]
+    pub const fn is_final(&self) -> bool {
+        match self {
+            Self::Judged(verdict) => verdict.is_final(),
+            _ => false,
+        }
+    }
+
+    /// Gets the verdict of the submission, if it has been judged.
+    #[must_use]
+    pub const fn verdict(&self) -> Option<&Verdict> {
+        match self {
+            Self::Judged(verdict) => Some(verdict),
+            _ => None,
+        }
+    }
+}
+
+/// The score given to a submission to a partially-scored problem.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Score {
+    /// The points awarded to the submission.
+    pub awarded: f64,
+
+    /// The maximum points a submission can get.
+    pub total: f64,
+}
+
+impl Score {
+    /// Gets the awarded points as a fraction of the total, between 0 and 1.
+    #[must_use]
+    pub fn fraction(&self) -> f64 {
+        if self.total > 0.0 {
+            self.awarded / self.total
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Display for Score {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.awarded, self.total)
+    }
+}
+
+/// A submission, with everything the judge reported about it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Submission {
+    /// The id of the submission.
+    pub id: SubmissionId,
+
+    /// The status of the submission.
+    pub status: SubmissionStatus,
+
+    /// The score of the submission, if the problem is partially scored and
+    /// the submission has been judged.
+    pub score: Option<Score>,
+
+    /// The textual feedback given by the judge, if any.
+    pub feedback: Option<String>,
+
+    /// The time the submission was made, if the site shows it.
+    pub submitted: Option<Timestamp>,
+
+    /// The compiler the submission was made with, if the site shows it.
+    pub compiler: Option<Compiler>,
+
+    /// The time the submission took to run, if the site shows it.
+    pub time: Option<ExecutionTime>,
+
+    /// The memory the submission used, if the site shows it.
+    pub memory: Option<MemoryUsage>,
+}
+
+/// A hash of the source of a submission, to tell whether a solution has
+/// already been submitted without keeping its source around.
+///
+/// Line endings and trailing whitespace (at the end of each line and of the
+/// source) are ignored, since they don't change what's judged. Hashes are
+/// stable across runs and versions of the crate, so their [`Display`] form
+/// can be stored and compared later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SourceHash(u64);
+
+impl SourceHash {
+    /// Computes the hash of a source.
+    #[must_use]
+    pub fn of(source: &str) -> Self {
+        let mut hasher = StableHasher::new();
+        for line in source.trim_end().lines() {
+            hasher.feed(line.trim_end().as_bytes());
+        }
+        Self(hasher.finish())
+    }
+}
+
+impl Display for SourceHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+#[cfg(feature = "web-client")]
+mod fetch {
+    use scraper::Html;
+
+    use super::{Submission, SubmissionId, SubmissionStatus};
+    use crate::sans_io::{
+        Expect, FetchAlreadyAccepted, FetchSubmission, FetchSubmissionSource,
+        FetchSubmissionStatus, FetchSubmissions, Operation, Request, Response,
+    };
+    use crate::scrape::{parse_score, selector, text};
+    use crate::{Error, Partial, Result, Timestamp, Verdict};
+
+    impl Operation for FetchSubmissionStatus {
+        type Output = SubmissionStatus;
+
+        fn request(&self) -> Request {
+            Request::page(submission_path(&self.id))
+        }
+
+        fn parse(&self, response: &Response<'_>) -> Result<SubmissionStatus> {
+            parse_status(&response.html()?, &self.request().path)
+        }
+    }
+
+    impl Operation for FetchSubmissions {
+        type Output = Vec<SubmissionId>;
+
+        fn request(&self) -> Request {
+            Request::page(format!("/problems/{}/submissions", self.problem))
+        }
+
+        fn parse(&self, response: &Response<'_>) -> Result<Vec<SubmissionId>> {
+            let mut numbers: Vec<u32> = response
+                .html()?
+                .select(&selector("a[href]"))
+                .filter_map(|link| {
+                    let href = link.value().attr("href")?;
+                    let (_, code) = href.trim_end_matches('/').rsplit_once("/submissions/S")?;
+                    code.parse().ok()
+                })
+                .collect();
+            numbers.sort_unstable();
+            numbers.dedup();
+
+            Ok(numbers
+                .into_iter()
+                .map(|number| SubmissionId::new(self.problem.clone(), number))
+                .collect())
+        }
+    }
+
+    impl Operation for FetchAlreadyAccepted {
+        type Output = bool;
+
+        fn request(&self) -> Request {
+            Request::page(format!("/problems/{}/submissions", self.problem))
+        }
+
+        fn parse(&self, response: &Response<'_>) -> Result<bool> {
+            // Each row of the list links to a submission, and shows its
+            // verdict.
+            Ok(response.html()?.select(&selector("tr")).any(|row| {
+                row.select(&selector("a[href*='/submissions/S']"))
+                    .next()
+                    .is_some()
+                    && row
+                        .select(&selector("td"))
+                        .any(|cell| text(cell).parse().is_ok_and(|v: Verdict| v.is_accepted()))
+            }))
+        }
+    }
+
+    impl Operation for FetchSubmissionSource {
+        type Output = String;
+
+        fn request(&self) -> Request {
+            // Sources aren't cached, since the cache of a client isn't tied
+            // to the user logged in.
+            Request {
+                expect: Expect::File,
+                ..Request::page(format!("{}/source", submission_path(&self.id)))
+            }
+        }
+
+        fn parse(&self, response: &Response<'_>) -> Result<String> {
+            Ok(String::from_utf8_lossy(response.body).into_owned())
+        }
+    }
+
+    impl Operation for FetchSubmission {
+        type Output = Submission;
+
+        fn request(&self) -> Request {
+            Request::page(submission_path(&self.id))
+        }
+
+        fn parse(&self, response: &Response<'_>) -> Result<Submission> {
+            self.parse_partial(response)?
+                .into_result(self.request().path)
+        }
+
+        /// A status that can't be found is left as an unknown verdict with
+        /// an empty code.
+        fn parse_partial(&self, response: &Response<'_>) -> Result<Partial<Submission>> {
+            let html = response.html()?;
+            let status = parse_status(&html, &self.request().path).ok();
+
+            let mut score = None;
+            let mut feedback = None;
+            let mut submitted = None;
+            let mut compiler = None;
+            let mut time = None;
+            let mut memory = None;
+            for row in html.select(&selector("table tr")) {
+                let cells: Vec<_> = row.select(&selector("th, td")).map(text).collect();
+                let [label, value, ..] = cells.as_slice() else {
+                    continue;
+                };
+
+                let label = label.to_lowercase();
+                // Whole words, so that labels such as "Annotation" or
+                // "Notes" aren't taken as the score.
+                let words: Vec<_> = label
+                    .split(|c: char| !c.is_alphanumeric())
+                    .filter(|word| !word.is_empty())
+                    .collect();
+                if words.iter().any(|word| {
+                    ["score", "nota", "note"].contains(word)
+                        || word.starts_with("punt")
+                        || word.starts_with("punkt")
+                }) {
+                    score = score.or_else(|| parse_score(value));
+                } else if words.iter().any(|word| {
+                    ["feedback", "coment", "comment", "notes"]
+                        .iter()
+                        .any(|w| word.starts_with(w))
+                }) {
+                    feedback = feedback.or_else(|| Some(value.clone()).filter(|v| !v.is_empty()));
+                } else if ["date", "data", "fecha", "datum"]
+                    .iter()
+                    .any(|w| label.contains(w))
+                {
+                    submitted = submitted.or_else(|| Timestamp::find_in_listing(value));
+                } else if label.contains("compil") {
+                    // The site shows the id of the compiler before its name.
+                    compiler = compiler.or_else(|| value.split_whitespace().next()?.parse().ok());
+                } else if ["memor", "mémoire", "speicher"]
+                    .iter()
+                    .any(|w| label.contains(w))
+                {
+                    memory = memory.or_else(|| value.parse().ok());
+                } else if ["time", "temps", "tiempo", "zeit"]
+                    .iter()
+                    .any(|w| label.contains(w))
+                {
+                    time = time.or_else(|| value.parse().ok());
+                }
+            }
+
+            let found = status.is_some();
+            let status =
+                status.unwrap_or_else(|| SubmissionStatus::Judged(Verdict::Unknown(String::new())));
+            let mut submission = Partial::complete(Submission {
+                id: self.id.clone(),
+                score: score.filter(|_| status.is_final()),
+                status,
+                feedback,
+                submitted,
+                compiler,
+                time,
+                memory,
+            });
+            if !found {
+                submission.fail("status", "no status found");
+            }
+            Ok(submission)
+        }
+    }
+
+    fn submission_path(id: &SubmissionId) -> String {
+        format!("/problems/{}/submissions/{}", id.problem(), id.code())
+    }
+
+    /// Parses the status of a submission. Known verdicts and statuses are
+    /// preferred over unknown verdict codes, which may just be stray text in
+    /// the page.
+    fn parse_status(html: &Html, page: &str) -> Result<SubmissionStatus> {
+        let texts: Vec<_> = html
+            .select(&selector("td, span, strong, div"))
+            .map(text)
+            .collect();
+
+        texts
+            .iter()
+            .find_map(|text| parse_status_text(text))
+            .or_else(|| {
+                texts.iter().find_map(|text| match text.parse() {
+                    Ok(verdict @ Verdict::Unknown(_)) => Some(SubmissionStatus::Judged(verdict)),
+                    _ => None,
+                })
+            })
+            .ok_or_else(|| Error::unexpected_layout(page, "no status found"))
+    }
+
+    /// Parses the status of a submission, as shown by the site in any of its
+    /// languages. Unknown verdict codes are ignored.
+    fn parse_status_text(text: &str) -> Option<SubmissionStatus> {
+        match text.parse::<Verdict>() {
+            Ok(Verdict::Pending) => return Some(SubmissionStatus::Queued),
+            Ok(Verdict::Unknown(_)) | Err(_) => {}
+            Ok(verdict) => return Some(SubmissionStatus::Judged(verdict)),
+        }
+
+        match text.to_lowercase().as_str() {
+            "pending" | "queued" | "pendent" | "pendiente" => Some(SubmissionStatus::Queued),
+            "compiling" | "compilant" | "compilando" => Some(SubmissionStatus::Compiling),
+            "running" | "judging" | "executant" | "ejecutando" => Some(SubmissionStatus::Running),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "client-auth")]
+mod tracking {
+    use std::collections::VecDeque;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use super::{SourceHash, Submission, SubmissionId, SubmissionStatus};
+    use crate::problem_id_types::Localized;
+    use crate::sans_io::{
+        FetchAlreadyAccepted, FetchSubmission, FetchSubmissionSource, FetchSubmissionStatus,
+        FetchSubmissions,
+    };
+    use crate::{Compiler, ProblemId, Result, Session, Verdict};
+
+    impl Session {
+        /// Fetches the current status of a submission.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAuthenticated`](crate::Error::NotAuthenticated)
+        /// if the session has expired, or
+        /// [`Error::UnexpectedPageLayout`](crate::Error::UnexpectedPageLayout)
+        /// if the status can't be found in the submission page.
+        pub fn submission_status(&self, id: &SubmissionId) -> Result<SubmissionStatus> {
+            self.client()
+                .execute(&FetchSubmissionStatus { id: id.clone() })
+        }
+
+        /// Fetches a submission, including its score and the feedback of the
+        /// judge, if any.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAuthenticated`](crate::Error::NotAuthenticated)
+        /// if the session has expired, or
+        /// [`Error::UnexpectedPageLayout`](crate::Error::UnexpectedPageLayout)
+        /// if the status can't be found in the submission page.
+        pub fn submission(&self, id: &SubmissionId) -> Result<Submission> {
+            self.client().execute(&FetchSubmission { id: id.clone() })
+        }
+
+        /// Lists the submissions of the logged-in user to a problem, from the
+        /// oldest to the newest.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAuthenticated`](crate::Error::NotAuthenticated)
+        /// if the session has expired.
+        pub fn submissions(&self, problem: &ProblemId<Localized>) -> Result<Vec<SubmissionId>> {
+            self.client().execute(&FetchSubmissions {
+                problem: problem.clone(),
+            })
+        }
+
+        /// Downloads the source of a submission of the logged-in user.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAuthenticated`](crate::Error::NotAuthenticated)
+        /// if the session has expired.
+        pub fn submission_source(&self, id: &SubmissionId) -> Result<String> {
+            self.client()
+                .execute(&FetchSubmissionSource { id: id.clone() })
+        }
+
+        /// Checks whether the logged-in user already got a submission to a
+        /// problem accepted, so automated submitters can skip it.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAuthenticated`](crate::Error::NotAuthenticated)
+        /// if the session has expired.
+        pub fn already_accepted(&self, problem: &ProblemId<Localized>) -> Result<bool> {
+            self.client().execute(&FetchAlreadyAccepted {
+                problem: problem.clone(),
+            })
+        }
+
+        /// Checks whether a source is the same as the one of the latest
+        /// submission of the logged-in user to a problem, as compared by
+        /// [`SourceHash`], and was sent with the same compiler. Returns that
+        /// submission if it is, so it isn't submitted again. If the site
+        /// doesn't show the compiler of the latest submission, only the
+        /// sources are compared.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAuthenticated`](crate::Error::NotAuthenticated)
+        /// if the session has expired.
+        pub fn duplicate_of_latest(
+            &self,
+            problem: &ProblemId<Localized>,
+            source: &str,
+            compiler: &Compiler,
+        ) -> Result<Option<SubmissionId>> {
+            Ok(self
+                .latest_duplicate(problem, source, compiler)?
+                .map(|submission| submission.id))
+        }
+
+        /// Like [`Session::duplicate_of_latest()`], but returns the whole
+        /// submission.
+        pub(crate) fn latest_duplicate(
+            &self,
+            problem: &ProblemId<Localized>,
+            source: &str,
+            compiler: &Compiler,
+        ) -> Result<Option<Submission>> {
+            let Some(latest) = self.submissions(problem)?.pop() else {
+                return Ok(None);
+            };
+
+            let latest_source = self.submission_source(&latest)?;
+            if SourceHash::of(&latest_source) != SourceHash::of(source) {
+                return Ok(None);
+            }
+
+            let submission = self.submission(&latest)?;
+            Ok(submission
+                .compiler
+                .as_ref()
+                .is_none_or(|latest| latest == compiler)
+                .then_some(submission))
+        }
+
+        /// Follows the progress of a submission through the judge.
+        ///
+        /// The returned iterator polls the status of the submission, and
+        /// yields it every time it changes (e.g. from
+        /// [`SubmissionStatus::Queued`] to [`SubmissionStatus::Compiling`]).
+        /// It ends after yielding the final verdict, or after the first
+        /// error.
+        #[must_use]
+        pub fn verdict_updates(&self, id: &SubmissionId) -> VerdictUpdates<'_> {
+            VerdictUpdates {
+                session: self,
+                id: id.clone(),
+                interval: Duration::from_secs(2),
+                last: None,
+                done: false,
+            }
+        }
+
+        /// Waits for several submissions to be judged.
+        ///
+        /// Instead of following each submission on its own, the returned
+        /// iterator polls all the pending ones in rounds, and yields each
+        /// submission with its verdict as soon as it's final, so the order of
+        /// the results depends on when the judge finishes them. A submission
+        /// whose status can't be fetched is yielded with the error, and isn't
+        /// polled again.
+        ///
+        /// Polls go through the same throttling as every other request of the
+        /// `Client` of the session, so a server asking to slow down delays the
+        /// whole round.
+        #[must_use]
+        pub fn await_verdicts(&self, ids: &[SubmissionId]) -> AwaitVerdicts<'_> {
+            AwaitVerdicts {
+                session: self,
+                pending: ids.to_vec(),
+                ready: VecDeque::new(),
+                interval: Duration::from_secs(2),
+                last_round: None,
+            }
+        }
+    }
+
+    /// An iterator over the status changes of a submission.
+    ///
+    /// This is created by [`Session::verdict_updates()`].
+    #[derive(Debug)]
+    pub struct VerdictUpdates<'a> {
+        session: &'a Session,
+        id: SubmissionId,
+        interval: Duration,
+        last: Option<SubmissionStatus>,
+        done: bool,
+    }
+
+    impl VerdictUpdates<'_> {
+        /// Sets the time to wait between polls. Defaults to 2 seconds.
+        #[must_use]
+        pub fn poll_interval(mut self, interval: Duration) -> Self {
+            self.interval = interval;
+            self
+        }
+    }
+
+    impl Iterator for VerdictUpdates<'_> {
+        type Item = Result<SubmissionStatus>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            while !self.done {
+                if self.last.is_some() {
+                    thread::sleep(self.interval);
+                }
+
+                match self.session.submission_status(&self.id) {
+                    Ok(status) if self.last.as_ref() == Some(&status) => {}
+                    Ok(status) => {
+                        self.done = status.is_final();
+                        self.last = Some(status.clone());
+                        return Some(Ok(status));
+                    }
+                    Err(err) => {
+                        self.done = true;
+                        return Some(Err(err));
+                    }
+                }
+            }
+
+            None
+        }
+    }
+
+    /// An iterator over the verdicts of several submissions, in the order
+    /// they're judged.
+    ///
+    /// This is created by [`Session::await_verdicts()`].
+    #[derive(Debug)]
+    pub struct AwaitVerdicts<'a> {
+        session: &'a Session,
+        pending: Vec<SubmissionId>,
+        ready: VecDeque<(SubmissionId, Result<Verdict>)>,
+        interval: Duration,
+        last_round: Option<Instant>,
+    }
+
+    impl AwaitVerdicts<'_> {
+        /// Sets the time between the starts of two polling rounds. Defaults
+        /// to 2 seconds.
+        #[must_use]
+        pub fn poll_interval(mut self, interval: Duration) -> Self {
+            self.interval = interval;
+            self
+        }
+
+        /// Gets the submissions that haven't been judged yet.
+        #[must_use]
+        pub fn pending(&self) -> &[SubmissionId] {
+            &self.pending
+        }
+
+        /// Polls every pending submission once, moving the judged ones to
+        /// the ready queue.
+        fn poll_round(&mut self) {
+            if let Some(last_round) = self.last_round {
+                thread::sleep(self.interval.saturating_sub(last_round.elapsed()));
+            }
+            self.last_round = Some(Instant::now());
+
+            let session = self.session;
+            let ready = &mut self.ready;
+            self.pending
+                .retain(|id| match session.submission_status(id) {
+                    Ok(SubmissionStatus::Judged(verdict)) if verdict.is_final() => {
+                        ready.push_back((id.clone(), Ok(verdict)));
+                        false
+                    }
+                    Ok(_) => true,
+                    Err(err) => {
+                        ready.push_back((id.clone(), Err(err)));
+                        false
+                    }
+                });
+        }
+    }
+
+    impl Iterator for AwaitVerdicts<'_> {
+        type Item = (SubmissionId, Result<Verdict>);
+
+        fn next(&mut self) -> Option<Self::Item> {
+            while self.ready.is_empty() && !self.pending.is_empty() {
+                self.poll_round();
+            }
+
+            self.ready.pop_front()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let len = self.ready.len() + self.pending.len();
+            (len, Some(len))
+        }
+    }
+}
+
+#[cfg(feature = "client-auth")]
+pub use tracking::{AwaitVerdicts, VerdictUpdates};
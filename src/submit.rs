@@ -0,0 +1,425 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::batch::run_batch;
+use crate::form::FormFile;
+use crate::problem_id_types::Localized;
+use crate::scrape::{error_alert, is_blocked_account_message};
+use crate::units::{unit_seconds, words};
+use crate::{BatchOutcome, Compiler, Error, ProblemId, Result, Session, SubmissionId, Timestamp};
+
+impl Session {
+    /// Submits a solution to a problem.
+    ///
+    /// This is a shortcut for
+    /// `session.submit_to(problem).compiler(compiler).source(source).send()`.
+    /// See [`Session::submit_to()`] for more options.
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`] if the session has expired,
+    /// [`Error::SubmissionQuotaExceeded`] if the user has made too many
+    /// submissions lately, [`Error::AccountBlocked`] if the site has blocked
+    /// the account, or [`Error::SubmissionRejected`] if the site doesn't
+    /// accept the submission for any other reason.
+    pub fn submit(
+        &self,
+        problem: &ProblemId<Localized>,
+        compiler: &Compiler,
+        source: &str,
+    ) -> Result<SubmissionId> {
+        self.submit_to(problem)
+            .compiler(compiler.clone())
+            .source(source)
+            .send()
+    }
+
+    /// Starts a submission to a problem, to be configured with the methods
+    /// of the returned [`SubmissionRequest`] and sent with
+    /// [`SubmissionRequest::send()`].
+    #[must_use]
+    pub fn submit_to(&self, problem: &ProblemId<Localized>) -> SubmissionRequest<'_> {
+        SubmissionRequest {
+            session: self,
+            problem: problem.clone(),
+            compiler: None,
+            source: None,
+            annotation: None,
+            exam: None,
+        }
+    }
+
+    /// Submits several solutions, possibly to different problems.
+    ///
+    /// Up to `concurrency` submissions are uploaded at the same time, but
+    /// they're still spaced according to
+    /// [`ClientBuilder::submit_interval()`](crate::ClientBuilder::submit_interval).
+    /// A `concurrency` of 0 or 1 submits them sequentially.
+    ///
+    /// A failed submission doesn't prevent the rest from being sent: the
+    /// returned [`BatchOutcome`] holds the result of each of them. The
+    /// exceptions are [`Error::SubmissionQuotaExceeded`] and
+    /// [`Error::AccountBlocked`], after which insisting would only make
+    /// things worse: the submissions that weren't sent yet fail with the
+    /// same error.
+    #[must_use]
+    pub fn submit_batch(
+        &self,
+        batch: &[(ProblemId<Localized>, Compiler, String)],
+        concurrency: usize,
+    ) -> BatchOutcome<SubmissionId> {
+        let halted = Mutex::new(None);
+        run_batch(batch, concurrency, |(problem, compiler, source)| {
+            let lock = || halted.lock().unwrap_or_else(PoisonError::into_inner);
+            if let Some(err) = lock().as_ref().and_then(halting_copy) {
+                return Err(err);
+            }
+
+            let result = self.submit(problem, compiler, source);
+            if let Err(err) = &result {
+                let mut halted = lock();
+                if halted.is_none() {
+                    *halted = halting_copy(err);
+                }
+            }
+            result
+        })
+    }
+}
+
+/// A submission to a problem, built with [`Session::submit_to()`].
+#[derive(Debug, Clone)]
+pub struct SubmissionRequest<'a> {
+    session: &'a Session,
+    problem: ProblemId<Localized>,
+    compiler: Option<Compiler>,
+    source: Option<Source>,
+    annotation: Option<String>,
+    exam: Option<String>,
+}
+
+/// What happened to a submission sent with
+/// [`SubmissionRequest::send_guarded()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SubmitOutcome {
+    /// The site received the submission and answered.
+    Submitted(SubmissionId),
+
+    /// The response to the upload was lost, but the site had received the
+    /// submission: it's the latest one of the user, with the same source.
+    Recovered(SubmissionId),
+
+    /// The response to the upload was lost and the site hadn't received the
+    /// submission (or it couldn't be told), so it was sent again.
+    Resubmitted(SubmissionId),
+}
+
+impl SubmitOutcome {
+    /// Gets the id of the submission, however it was made.
+    #[must_use]
+    pub const fn id(&self) -> &SubmissionId {
+        match self {
+            Self::Submitted(id) | Self::Recovered(id) | Self::Resubmitted(id) => id,
+        }
+    }
+
+    /// Converts the outcome into the id of the submission.
+    #[must_use]
+    pub fn into_id(self) -> SubmissionId {
+        match self {
+            Self::Submitted(id) | Self::Recovered(id) | Self::Resubmitted(id) => id,
+        }
+    }
+}
+
+/// Where the source of a [`SubmissionRequest`] comes from.
+#[derive(Debug, Clone)]
+enum Source {
+    Text(String),
+    File(PathBuf),
+}
+
+impl SubmissionRequest<'_> {
+    /// Sets the compiler to use. If it isn't set, it's guessed from the
+    /// extension of the [source file](SubmissionRequest::source_file) (see
+    /// [`Compiler::for_extension()`]).
+    #[must_use]
+    pub fn compiler(mut self, compiler: Compiler) -> Self {
+        self.compiler = Some(compiler);
+        self
+    }
+
+    /// Sets the source code to submit.
+    #[must_use]
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(Source::Text(source.into()));
+        self
+    }
+
+    /// Sets a file to read the source code to submit from. It's read when
+    /// the submission is sent.
+    #[must_use]
+    pub fn source_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.source = Some(Source::File(path.as_ref().into()));
+        self
+    }
+
+    /// Sets an annotation for the submission, which is shown next to it in
+    /// the site, such as `retry 2`.
+    #[must_use]
+    pub fn annotation(mut self, annotation: impl Into<String>) -> Self {
+        self.annotation = Some(annotation.into());
+        self
+    }
+
+    /// Submits to the problem as part of an exam, so the submission counts
+    /// for it.
+    #[must_use]
+    pub fn exam(mut self, exam_id: impl Into<String>) -> Self {
+        self.exam = Some(exam_id.into());
+        self
+    }
+
+    /// Sends the submission.
+    ///
+    /// Submissions are spaced according to
+    /// [`ClientBuilder::submit_interval()`](crate::ClientBuilder::submit_interval),
+    /// so this may block for a while.
+    ///
+    /// # Errors
+    /// Returns [`Error::IncompleteSubmission`] if no source was given or the
+    /// compiler can't be guessed, [`Error::IoError`] if the source file
+    /// can't be read, [`Error::NotAuthenticated`] if the session has
+    /// expired, [`Error::SubmissionQuotaExceeded`] if the user has made too
+    /// many submissions lately, [`Error::AccountBlocked`] if the site has
+    /// blocked the account, or [`Error::SubmissionRejected`] if the site
+    /// doesn't accept the submission for any other reason.
+    ///
+    /// Automated submitters should stop on the first two, rather than
+    /// retrying: the site answers to more submissions with longer blocks.
+    pub fn send(self) -> Result<SubmissionId> {
+        let (filename, source, compiler) = self.prepare()?;
+        self.upload(&filename, &source, &compiler, &mut None)
+    }
+
+    /// Sends the submission, without submitting twice if the response to
+    /// the upload is lost.
+    ///
+    /// When sending it fails with a network error, such as a timeout after
+    /// the source was uploaded, the site may or may not have received the
+    /// submission. Before retrying, this checks whether the latest
+    /// submission of the user to the problem has the same source (as
+    /// compared by [`SourceHash`](crate::SourceHash)) and compiler, and was
+    /// made after the upload was sent, in which case it's the one that was
+    /// sent. If the site doesn't show when the latest submission was made,
+    /// it may be an older one with the same source, so the submission is
+    /// sent again. The returned [`SubmitOutcome`] tells what happened.
+    ///
+    /// The submission is retried once at most.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`SubmissionRequest::send()`], the errors
+    /// of checking the latest submission (see
+    /// [`Session::duplicate_of_latest()`]), and the network error of the
+    /// retry if it fails too.
+    pub fn send_guarded(self) -> Result<SubmitOutcome> {
+        // Leeway for the site showing times to the minute, and for its clock
+        // being slightly behind the local one.
+        const CLOCK_SKEW: i64 = 60;
+
+        let (filename, source, compiler) = self.prepare()?;
+        let mut sent = None;
+        match self.upload(&filename, &source, &compiler, &mut sent) {
+            Err(err) if is_lost_response(&err) => {}
+            result => return result.map(SubmitOutcome::Submitted),
+        }
+
+        let session = self.session;
+        if let Some(latest) = session.latest_duplicate(&self.problem, &source, &compiler)? {
+            let made_since = latest
+                .submitted
+                .zip(sent)
+                .is_some_and(|(submitted, sent)| submitted.unix() >= sent.unix() - CLOCK_SKEW);
+            if made_since {
+                return Ok(SubmitOutcome::Recovered(latest.id));
+            }
+        }
+
+        self.upload(&filename, &source, &compiler, &mut None)
+            .map(SubmitOutcome::Resubmitted)
+    }
+
+    /// Reads the source and picks the compiler of the submission, returning
+    /// them with the name of the uploaded file.
+    fn prepare(&self) -> Result<(String, String, Compiler)> {
+        let (filename, source, extension) = match &self.source {
+            Some(Source::Text(source)) => (None, source.clone(), None),
+            Some(Source::File(path)) => (
+                path.file_name()
+                    .map(|name| name.to_string_lossy().into_owned()),
+                fs::read_to_string(path)?,
+                path.extension().and_then(|ext| ext.to_str()),
+            ),
+            None => return Err(Error::IncompleteSubmission("no source given".into())),
+        };
+        let compiler = match (&self.compiler, extension) {
+            (Some(compiler), _) => compiler.clone(),
+            (None, Some(extension)) => Compiler::for_extension(extension).ok_or_else(|| {
+                Error::IncompleteSubmission(format!(
+                    "no compiler given, and none is known for `.{extension}` files",
+                ))
+            })?,
+            (None, None) => {
+                return Err(Error::IncompleteSubmission("no compiler given".into()));
+            }
+        };
+        let filename = filename.unwrap_or_else(|| format!("solution.{}", compiler.extension()));
+
+        Ok((filename, source, compiler))
+    }
+
+    /// Uploads the submission to the site, setting `sent` to the time the
+    /// upload starts.
+    fn upload(
+        &self,
+        filename: &str,
+        source: &str,
+        compiler: &Compiler,
+        sent: &mut Option<Timestamp>,
+    ) -> Result<SubmissionId> {
+        let page = match &self.exam {
+            Some(exam) => format!("/exams/{exam}/problems/{}", self.problem),
+            None => format!("/problems/{}", self.problem),
+        };
+        let client = self.session.client();
+        let mut form = client.fetch_form(&page, "compiler_id")?;
+        form.set("compiler_id", compiler.id());
+        if let Some(annotation) = &self.annotation {
+            form.set("annotation", annotation);
+        }
+
+        let file = FormFile {
+            field: "file",
+            filename,
+            content_type: "text/plain",
+            data: source.as_bytes(),
+        };
+
+        client.wait_to_submit();
+        *sent = Some(Timestamp::now());
+        let response = client.submit_form_with_files(&form, &[file])?;
+
+        if let Some(number) = submission_number(response.get_url()) {
+            return Ok(SubmissionId::new(self.problem.clone(), number));
+        }
+
+        match error_alert(&client.read_authenticated_html(response)?) {
+            Some(reason) => Err(rejection(reason)),
+            None => Err(Error::unexpected_layout(
+                page,
+                "submitting didn't lead to a submission page",
+            )),
+        }
+    }
+}
+
+/// Builds the error for a submission rejected by the site with a reason.
+fn rejection(reason: String) -> Error {
+    const QUOTA_MESSAGES: [&str; 6] = [
+        "too many submissions",
+        "massa enviaments",
+        "demasiados envíos",
+        "trop de soumissions",
+        "zu viele einsendungen",
+        "quota",
+    ];
+
+    let message = reason.to_lowercase();
+    if QUOTA_MESSAGES.iter().any(|quota| message.contains(quota)) {
+        Error::SubmissionQuotaExceeded {
+            wait: quota_wait(&message),
+        }
+    } else if is_blocked_account_message(&message) {
+        Error::AccountBlocked
+    } else {
+        Error::SubmissionRejected(reason)
+    }
+}
+
+/// Copies an error after which no more submissions should be sent, or
+/// returns `None` for any other error.
+fn halting_copy(err: &Error) -> Option<Error> {
+    match err {
+        Error::SubmissionQuotaExceeded { wait } => {
+            Some(Error::SubmissionQuotaExceeded { wait: *wait })
+        }
+        Error::AccountBlocked => Some(Error::AccountBlocked),
+        _ => None,
+    }
+}
+
+/// Checks whether an error sending a submission is a network error, after
+/// which the site may or may not have received it.
+fn is_lost_response(err: &Error) -> bool {
+    matches!(err, Error::UreqError(err) if matches!(**err, ureq::Error::Transport(_)))
+}
+
+/// Finds how long a quota message, such as `Too many submissions, wait 5
+/// minutes`, asks to wait.
+fn quota_wait(message: &str) -> Option<Duration> {
+    let message = message.to_lowercase();
+
+    words(&message).windows(2).find_map(|pair| {
+        let amount: u64 = pair[0].parse().ok()?;
+        amount
+            .checked_mul(unit_seconds(pair[1])?)
+            .map(Duration::from_secs)
+    })
+}
+
+/// Extracts the submission number from the URL of a submission page.
+fn submission_number(url: &str) -> Option<u32> {
+    let (_, code) = url.trim_end_matches('/').rsplit_once("/submissions/S")?;
+    code.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::quota_wait;
+
+    #[test]
+    fn quota_waits() {
+        assert_eq!(
+            quota_wait("Too many submissions, wait 5 minutes"),
+            Some(Duration::from_mins(5))
+        );
+        assert_eq!(
+            quota_wait("Massa enviaments. Espera 30 segons."),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            quota_wait("Demasiados envíos, espera 2 Horas"),
+            Some(Duration::from_hours(2))
+        );
+        assert_eq!(
+            quota_wait("Trop de soumissions (1 heure)"),
+            Some(Duration::from_hours(1))
+        );
+    }
+
+    #[test]
+    fn messages_without_quota_waits() {
+        assert_eq!(quota_wait("Too many submissions"), None);
+        assert_eq!(quota_wait("Wait a minute"), None);
+        assert_eq!(quota_wait("You have sent 20 submissions"), None);
+    }
+
+    #[test]
+    fn quota_waits_that_overflow() {
+        assert_eq!(quota_wait("wait 18446744073709551615 minutes"), None);
+        assert_eq!(quota_wait("wait 18446744073709551615 hours"), None);
+    }
+}
@@ -0,0 +1,218 @@
+//! Generation of Rust tests from the test cases of a problem.
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use crate::{Result, TestCase};
+
+/// Generates a Rust test module that checks a solution against some test
+/// cases, usually the samples of a problem.
+///
+/// The solution must be a function callable as
+/// `solve(&mut input, &mut output)`, where `input` implements
+/// [`BufRead`](std::io::BufRead) and `output` implements
+/// [`Write`](std::io::Write), such as:
+///
+/// ```text
+/// fn solve(input: &mut impl BufRead, output: &mut impl Write) { ... }
+/// ```
+///
+/// Each test case becomes a `#[test]` that feeds its input to the solution
+/// and asserts that it writes the expected output. The generated code can be
+/// written to a file from a build script and then included with
+/// [`include!`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RustTestHarness {
+    cases: Vec<TestCase>,
+    module: String,
+    function: String,
+    header: Option<String>,
+}
+
+impl RustTestHarness {
+    /// Creates a harness for the given test cases, testing the function
+    /// `super::solve` in a module named `jutge_samples`.
+    #[must_use]
+    pub fn new(cases: impl Into<Vec<TestCase>>) -> Self {
+        Self {
+            cases: cases.into(),
+            module: "jutge_samples".into(),
+            function: "super::solve".into(),
+            header: None,
+        }
+    }
+
+    /// Sets the name of the generated module.
+    #[must_use]
+    pub fn module(mut self, module: &str) -> Self {
+        module.clone_into(&mut self.module);
+        self
+    }
+
+    /// Sets the path of the function to test, as seen from inside the
+    /// generated module.
+    #[must_use]
+    pub fn function(mut self, function: &str) -> Self {
+        function.clone_into(&mut self.function);
+        self
+    }
+
+    /// Sets a comment to add at the start of the generated code, such as the
+    /// problem the test cases come from.
+    #[must_use]
+    pub fn header(mut self, header: &str) -> Self {
+        self.header = Some(header.into());
+        self
+    }
+
+    /// Generates the source code of the test module.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut code = String::new();
+
+        if let Some(header) = &self.header {
+            for line in header.lines() {
+                let _ = writeln!(code, "// {line}");
+            }
+        }
+        let _ = writeln!(code, "// Generated by jutge-rs. Don't edit by hand.");
+        let _ = writeln!(code, "#[cfg(test)]");
+        let _ = writeln!(code, "mod {} {{", self.module);
+        let _ = writeln!(code, "    fn {HELPER}(input: &str, expected: &str) {{");
+        let _ = writeln!(code, "        let mut output = Vec::new();");
+        let _ = writeln!(
+            code,
+            "        {}(&mut ::std::io::BufReader::new(input.as_bytes()), &mut output);",
+            self.function
+        );
+        let _ = writeln!(
+            code,
+            "        let output = String::from_utf8(output).expect(\"output should be UTF-8\");"
+        );
+        let _ = writeln!(code, "        assert_eq!(output, expected);");
+        let _ = writeln!(code, "    }}");
+
+        let mut names = HashSet::new();
+        for case in &self.cases {
+            let name = test_name(&case.name, &mut names);
+            let _ = writeln!(code);
+            let _ = writeln!(code, "    #[test]");
+            let _ = writeln!(code, "    fn {name}() {{");
+            let _ = writeln!(
+                code,
+                "        {HELPER}({:?}, {:?});",
+                case.input, case.expected
+            );
+            let _ = writeln!(code, "    }}");
+        }
+
+        let _ = writeln!(code, "}}");
+        code
+    }
+
+    /// Writes the generated test module to a file.
+    ///
+    /// # Errors
+    /// Returns [`Error::IoError`](crate::Error::IoError) if the file can't be
+    /// written.
+    pub fn write_to(&self, path: impl AsRef<Path>) -> Result<()> {
+        Ok(fs::write(path, self.render())?)
+    }
+}
+
+/// The name of the helper function of the generated module.
+const HELPER: &str = "check";
+
+/// The keywords of Rust, strict and reserved, which can't name a test.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Turns the name of a test case into a unique Rust identifier, which is
+/// neither a keyword nor the name of the helper function.
+fn test_name(name: &str, used: &mut HashSet<String>) -> String {
+    let mut ident: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !ident.starts_with(|c: char| c.is_ascii_alphabetic())
+        || ident == HELPER
+        || KEYWORDS.contains(&ident.as_str())
+    {
+        ident.insert_str(0, "case_");
+    }
+
+    let mut unique = ident.clone();
+    let mut suffix = 2;
+    while !used.insert(unique.clone()) {
+        unique = format!("{ident}_{suffix}");
+        suffix += 1;
+    }
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{test_name, RustTestHarness};
+    use crate::TestCase;
+
+    fn names(cases: &[&str]) -> Vec<String> {
+        let mut used = HashSet::new();
+        cases
+            .iter()
+            .map(|name| test_name(name, &mut used))
+            .collect()
+    }
+
+    #[test]
+    fn plain_names() {
+        assert_eq!(
+            names(&["sample-1", "Sample 2", "1", "sample-1"]),
+            ["sample_1", "sample_2", "case_1", "sample_1_2"]
+        );
+    }
+
+    #[test]
+    fn names_of_keywords() {
+        assert_eq!(
+            names(&["type", "match", "fn", "Self", "typed"]),
+            ["case_type", "case_match", "case_fn", "case_self", "typed"]
+        );
+    }
+
+    #[test]
+    fn name_of_the_helper() {
+        assert_eq!(
+            names(&["check", "Check", "case check"]),
+            ["case_check", "case_check_2", "case_check_3"]
+        );
+    }
+
+    #[test]
+    fn rendered_tests() {
+        let case = |name: &str| TestCase {
+            name: name.into(),
+            input: "1 2\n".into(),
+            expected: "3\n".into(),
+        };
+        let code = RustTestHarness::new([case("check"), case("type")]).render();
+
+        assert_eq!(code.matches("fn check(").count(), 1);
+        assert!(code.contains("    fn case_check() {\n        check(\"1 2\\n\", \"3\\n\");"));
+        assert!(code.contains("    fn case_type() {"));
+    }
+}
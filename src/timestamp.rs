@@ -0,0 +1,647 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{Error, Result};
+
+/// A point in time, such as the time a submission was made or the deadline
+/// of a list of problems.
+///
+/// <https://jutge.org> shows times in the timezone of Barcelona
+/// (Europe/Madrid) without saying so, which is how they're parsed by
+/// [`Timestamp::from_str()`] and displayed by the [`Display`]
+/// implementation. Internally, timestamps are absolute, so they can be
+/// compared and converted to other timezones.
+///
+/// With the `chrono` or `time` features, timestamps can be converted to the
+/// types of those crates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    unix: i64,
+}
+
+/// The Unix time of `0001-01-01 00:00:00` in Europe/Madrid, so that the
+/// times shown by the site start at the year 1.
+const MIN_UNIX: i64 = -62_135_600_400;
+
+/// The Unix time of `9999-12-31 23:59:59` in Europe/Madrid, so that the
+/// times shown by the site never go past the year 9999.
+const MAX_UNIX: i64 = 253_402_297_199;
+
+const HOUR: i64 = 3600;
+
+impl Timestamp {
+    /// Creates a `Timestamp` from the number of seconds since the Unix epoch.
+    ///
+    /// Returns `None` if the timestamp is outside of the years 1 to 9999 in
+    /// the Europe/Madrid timezone.
+    #[must_use]
+    pub const fn from_unix(seconds: i64) -> Option<Self> {
+        if seconds >= MIN_UNIX && seconds <= MAX_UNIX {
+            Some(Self { unix: seconds })
+        } else {
+            None
+        }
+    }
+
+    /// Gets the number of seconds since the Unix epoch.
+    #[must_use]
+    pub const fn unix(&self) -> i64 {
+        self.unix
+    }
+
+    /// Creates a `Timestamp` from a date and a time as shown by the site,
+    /// i.e., in the Europe/Madrid timezone.
+    ///
+    /// Times skipped when the clocks go forward are taken as if they hadn't
+    /// changed yet (so `02:30` becomes `03:30`), and times repeated when the
+    /// clocks go back are taken as the first of them.
+    ///
+    /// Returns `None` if the date or the time isn't valid, or if the year
+    /// isn't between 1 and 9999.
+    #[must_use]
+    pub fn from_madrid(
+        year: i64,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+        second: u32,
+    ) -> Option<Self> {
+        if !(1..=9999).contains(&year) {
+            return None;
+        }
+        let days_in_month = days_in_month(year, month)?;
+        if !(1..=days_in_month).contains(&day) || hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+
+        let local = days_from_civil(year, month, day) * 86_400
+            + i64::from(hour) * HOUR
+            + i64::from(minute) * 60
+            + i64::from(second);
+
+        let summer = local - 2 * HOUR;
+        let unix = if is_summer_time(summer) {
+            summer
+        } else {
+            local - HOUR
+        };
+        Self::from_unix(unix)
+    }
+
+    /// Gets the current time.
+    #[must_use]
+    pub fn now() -> Self {
+        Self::from(SystemTime::now())
+    }
+
+    /// Gets the offset from UTC of the Europe/Madrid timezone at this time,
+    /// in seconds: one hour in winter and two in summer.
+    #[must_use]
+    pub fn madrid_offset(&self) -> i32 {
+        if is_summer_time(self.unix) {
+            7200
+        } else {
+            3600
+        }
+    }
+
+    /// Gets the date and the time of this timestamp in the Europe/Madrid
+    /// timezone, as `(year, month, day, hour, minute, second)`.
+    #[must_use]
+    pub fn to_madrid(&self) -> (i64, u32, u32, u32, u32, u32) {
+        civil_time(self.unix + i64::from(self.madrid_offset()))
+    }
+
+    /// Formats the timestamp in the Europe/Madrid timezone as RFC 3339, such
+    /// as `2024-03-05T14:30:00+01:00`, the format used in JSON output.
+    #[must_use]
+    pub fn to_rfc3339(&self) -> String {
+        let (year, month, day, hour, minute, second) = self.to_madrid();
+        format!(
+            "{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}+{:02}:00",
+            self.madrid_offset() / 3600
+        )
+    }
+
+    /// Gets the date and the time of this timestamp in UTC, as `(year,
+    /// month, day, hour, minute, second)`.
+    pub(crate) fn to_utc(self) -> (i64, u32, u32, u32, u32, u32) {
+        civil_time(self.unix)
+    }
+
+    /// Gets the date of the Monday of the week of this timestamp in the
+    /// Europe/Madrid timezone, as `(year, month, day)`.
+    pub(crate) fn madrid_week_start(self) -> (i64, u32, u32) {
+        let local = self.unix + i64::from(self.madrid_offset());
+        let days = local.div_euclid(86_400);
+        // The Unix epoch was a Thursday.
+        civil_from_days(days - (days + 3).rem_euclid(7))
+    }
+
+    /// Finds the first timestamp in a text, such as `Deadline: 2024-03-05
+    /// 23:59`, in any of the formats accepted by [`Timestamp::from_str()`].
+    #[cfg(feature = "web-client")]
+    pub(crate) fn find_in(text: &str) -> Option<Self> {
+        let words: Vec<_> = text.split_whitespace().collect();
+
+        (0..words.len()).find_map(|start| {
+            let with_time = words.get(start..start + 2).map(|words| words.join(" "));
+            with_time
+                .and_then(|s| s.trim_end_matches(['.', ',', ';', ')']).parse().ok())
+                .or_else(|| {
+                    words[start]
+                        .trim_end_matches(['.', ',', ';', ')'])
+                        .parse()
+                        .ok()
+                })
+        })
+    }
+
+    /// Finds the first timestamp in a text, like [`Timestamp::find_in()`],
+    /// or else a time relative to now, such as `fa 3 minuts`, as shown in
+    /// the listings of submissions (see [`parse_relative_time()`](crate::parse_relative_time)).
+    #[cfg(feature = "web-client")]
+    pub(crate) fn find_in_listing(text: &str) -> Option<Self> {
+        Self::find_in(text).or_else(|| crate::parse_relative_time(text, Self::now()))
+    }
+}
+
+/// The time window in which an exam can be taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExamWindow {
+    /// The time the exam starts.
+    pub start: Timestamp,
+
+    /// The time the exam ends.
+    pub end: Timestamp,
+}
+
+impl ExamWindow {
+    /// Checks whether a time is inside the window.
+    #[must_use]
+    pub fn contains(&self, time: Timestamp) -> bool {
+        (self.start..self.end).contains(&time)
+    }
+}
+
+impl Display for Timestamp {
+    /// Formats the timestamp in the Europe/Madrid timezone, such as
+    /// `2024-03-05 14:30:00 +01:00`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (year, month, day, hour, minute, second) = self.to_madrid();
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02} {hour:02}:{minute:02}:{second:02} +{:02}:00",
+            self.madrid_offset() / 3600
+        )
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = Error;
+
+    /// Parses a timestamp in the formats used by the site, in the
+    /// Europe/Madrid timezone: a date such as `2024-03-05` or `05/03/2024`,
+    /// optionally followed by a time such as `14:30` or `14:30:00`. A
+    /// missing time is taken as midnight.
+    ///
+    /// The formats of the [`Display`] implementation and of
+    /// [`Timestamp::to_rfc3339()`], with an explicit offset, are accepted
+    /// too.
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidTimestamp(s.into());
+
+        let (date, time, offset) = match s.split_once('T') {
+            Some((date, rest)) if !s.contains(char::is_whitespace) => {
+                let at = rest.find(['+', '-', 'Z']).ok_or_else(invalid)?;
+                (date, Some(&rest[..at]), Some(&rest[at..]))
+            }
+            _ => {
+                let mut parts = s.split_whitespace();
+                let date = parts.next().ok_or_else(invalid)?;
+                let time = parts.next();
+                let offset = parts.next();
+                if parts.next().is_some() {
+                    return Err(invalid());
+                }
+                (date, time, offset)
+            }
+        };
+
+        let (year, month, day) = parse_date(date).ok_or_else(invalid)?;
+        let (hour, minute, second) = match time {
+            Some(time) => parse_time(time).ok_or_else(invalid)?,
+            None => (0, 0, 0),
+        };
+
+        match offset {
+            None => Self::from_madrid(year, month, day, hour, minute, second).ok_or_else(invalid),
+            Some(offset) => {
+                let offset = parse_offset(offset).ok_or_else(invalid)?;
+                let days = days_in_month(year, month).ok_or_else(invalid)?;
+                if !(1..=days).contains(&day) {
+                    return Err(invalid());
+                }
+
+                let local = days_from_civil(year, month, day) * 86_400
+                    + i64::from(hour) * HOUR
+                    + i64::from(minute) * 60
+                    + i64::from(second);
+                Self::from_unix(local - offset).ok_or_else(invalid)
+            }
+        }
+    }
+}
+
+impl From<SystemTime> for Timestamp {
+    /// Converts a `SystemTime` to a `Timestamp`, truncating it to whole
+    /// seconds and clamping it to the years 1 to 9999.
+    fn from(time: SystemTime) -> Self {
+        let unix = match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => i64::try_from(since.as_secs()).unwrap_or(MAX_UNIX),
+            Err(err) => i64::try_from(err.duration().as_secs()).map_or(MIN_UNIX, |secs| -secs),
+        };
+
+        Self {
+            unix: unix.clamp(MIN_UNIX, MAX_UNIX),
+        }
+    }
+}
+
+impl From<Timestamp> for SystemTime {
+    fn from(timestamp: Timestamp) -> Self {
+        let since = Duration::from_secs(timestamp.unix.unsigned_abs());
+        if timestamp.unix >= 0 {
+            UNIX_EPOCH + since
+        } else {
+            UNIX_EPOCH - since
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_support {
+    use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+
+    use super::Timestamp;
+    use crate::Error;
+
+    impl From<Timestamp> for DateTime<Utc> {
+        /// # Panics
+        /// Never panics, as every `Timestamp` is in the range of `chrono`.
+        fn from(timestamp: Timestamp) -> Self {
+            DateTime::from_timestamp(timestamp.unix(), 0)
+                .expect("Timestamps should be in the range of chrono")
+        }
+    }
+
+    impl From<Timestamp> for DateTime<FixedOffset> {
+        /// Converts a `Timestamp` to a `DateTime` in the Europe/Madrid
+        /// timezone, as shown by the site.
+        ///
+        /// # Panics
+        /// Never panics, as every `Timestamp` is in the range of `chrono`.
+        fn from(timestamp: Timestamp) -> Self {
+            let offset = FixedOffset::east_opt(timestamp.madrid_offset())
+                .expect("Madrid offset should be valid");
+            DateTime::<Utc>::from(timestamp).with_timezone(&offset)
+        }
+    }
+
+    impl<Tz: TimeZone> TryFrom<DateTime<Tz>> for Timestamp {
+        type Error = Error;
+
+        fn try_from(time: DateTime<Tz>) -> crate::Result<Self> {
+            Self::from_unix(time.timestamp())
+                .ok_or_else(|| Error::InvalidTimestamp(time.to_utc().to_rfc3339()))
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_support {
+    use time::{OffsetDateTime, UtcOffset};
+
+    use super::Timestamp;
+    use crate::Error;
+
+    impl From<Timestamp> for OffsetDateTime {
+        /// Converts a `Timestamp` to an `OffsetDateTime` in the
+        /// Europe/Madrid timezone, as shown by the site.
+        ///
+        /// # Panics
+        /// Never panics, as every `Timestamp` is in the range of `time`.
+        fn from(timestamp: Timestamp) -> Self {
+            let offset = UtcOffset::from_whole_seconds(timestamp.madrid_offset())
+                .expect("Madrid offset should be valid");
+            OffsetDateTime::from_unix_timestamp(timestamp.unix())
+                .expect("Timestamps should be in the range of time")
+                .to_offset(offset)
+        }
+    }
+
+    impl TryFrom<OffsetDateTime> for Timestamp {
+        type Error = Error;
+
+        fn try_from(time: OffsetDateTime) -> crate::Result<Self> {
+            Self::from_unix(time.unix_timestamp())
+                .ok_or_else(|| Error::InvalidTimestamp(time.to_string()))
+        }
+    }
+}
+
+/// Parses a date such as `2024-03-05` or `05/03/2024`.
+fn parse_date(date: &str) -> Option<(i64, u32, u32)> {
+    let numbers: Vec<&str> = date.split(['-', '/']).collect();
+    let [a, b, c] = numbers[..] else {
+        return None;
+    };
+    let all_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if !numbers.iter().all(|n| all_digits(n)) {
+        return None;
+    }
+
+    if a.len() == 4 && date.contains('-') {
+        Some((a.parse().ok()?, b.parse().ok()?, c.parse().ok()?))
+    } else if c.len() == 4 {
+        Some((c.parse().ok()?, b.parse().ok()?, a.parse().ok()?))
+    } else {
+        None
+    }
+}
+
+/// Parses a time such as `14:30` or `14:30:00`.
+fn parse_time(time: &str) -> Option<(u32, u32, u32)> {
+    let numbers: Vec<u32> = time
+        .split(':')
+        .map(|n| (n.len() == 2).then(|| n.parse().ok()).flatten())
+        .collect::<Option<_>>()?;
+
+    let (hour, minute, second) = match numbers[..] {
+        [hour, minute] => (hour, minute, 0),
+        [hour, minute, second] => (hour, minute, second),
+        _ => return None,
+    };
+    (hour < 24 && minute < 60 && second < 60).then_some((hour, minute, second))
+}
+
+/// Parses an offset from UTC such as `+01:00` or `Z`, returning it in
+/// seconds.
+fn parse_offset(offset: &str) -> Option<i64> {
+    if offset == "Z" {
+        return Some(0);
+    }
+    let (sign, offset) = match offset.as_bytes().first()? {
+        b'+' => (1, &offset[1..]),
+        b'-' => (-1, &offset[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = offset.split_once(':')?;
+    let hours: i64 = hours.parse().ok()?;
+    let minutes: i64 = minutes.parse().ok()?;
+    if hours > 14 || minutes > 59 {
+        return None;
+    }
+
+    Some(sign * (hours * HOUR + minutes * 60))
+}
+
+/// Checks whether summer time is in force in Europe/Madrid at a Unix time,
+/// following the rules of the European Union: from 01:00 UTC of the last
+/// Sunday of March to 01:00 UTC of the last Sunday of October.
+fn is_summer_time(unix: i64) -> bool {
+    let (year, _, _) = civil_from_days(unix.div_euclid(86_400));
+
+    let start = last_sunday(year, 3) * 86_400 + HOUR;
+    let end = last_sunday(year, 10) * 86_400 + HOUR;
+    (start..end).contains(&unix)
+}
+
+/// Gets the days since the Unix epoch of the last Sunday of a month.
+fn last_sunday(year: i64, month: u32) -> i64 {
+    let last_day = days_from_civil(year, month, days_in_month(year, month).unwrap_or(28));
+    // The Unix epoch was a Thursday.
+    let weekday = (last_day + 4).rem_euclid(7);
+    last_day - weekday
+}
+
+/// Gets the number of days of a month, or `None` if the month isn't valid.
+fn days_in_month(year: i64, month: u32) -> Option<u32> {
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => Some(31),
+        4 | 6 | 9 | 11 => Some(30),
+        2 if leap => Some(29),
+        2 => Some(28),
+        _ => None,
+    }
+}
+
+/// Gets the days since the Unix epoch of a date in the proleptic Gregorian
+/// calendar.
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let (year, month) = if month <= 2 {
+        (year - 1, i64::from(month) + 9)
+    } else {
+        (year, i64::from(month) - 3)
+    };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * month + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Gets the date and the time of a number of seconds since the Unix epoch,
+/// ignoring timezones, as `(year, month, day, hour, minute, second)`.
+fn civil_time(seconds: i64) -> (i64, u32, u32, u32, u32, u32) {
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+    let seconds = seconds.rem_euclid(86_400);
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let time = (
+        (seconds / HOUR) as u32,
+        (seconds % HOUR / 60) as u32,
+        (seconds % 60) as u32,
+    );
+    (year, month, day, time.0, time.1, time.2)
+}
+
+/// Gets the date in the proleptic Gregorian calendar of a number of days
+/// since the Unix epoch, as `(year, month, day)`.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    (year, month as u32, day as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{civil_from_days, days_from_civil, is_summer_time, last_sunday, Timestamp};
+
+    fn madrid(year: i64, month: u32, day: u32, hour: u32, minute: u32) -> Timestamp {
+        Timestamp::from_madrid(year, month, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn skipped_hour_in_march() {
+        // The clocks went forward from 02:00 to 03:00 on 2024-03-31.
+        assert_eq!(madrid(2024, 3, 31, 1, 59).madrid_offset(), 3600);
+        assert_eq!(madrid(2024, 3, 31, 2, 30), madrid(2024, 3, 31, 3, 30));
+        assert_eq!(madrid(2024, 3, 31, 3, 0).madrid_offset(), 7200);
+        assert_eq!(madrid(2024, 3, 31, 3, 0).unix(), 1_711_846_800);
+    }
+
+    #[test]
+    fn repeated_hour_in_october() {
+        // The clocks went back from 03:00 to 02:00 on 2024-10-27.
+        let first = madrid(2024, 10, 27, 2, 30);
+        assert_eq!(first.madrid_offset(), 7200);
+        assert_eq!(first.unix(), 1_729_989_000);
+
+        let second = Timestamp::from_unix(first.unix() + 3600).unwrap();
+        assert_eq!(second.madrid_offset(), 3600);
+        assert_eq!(second.to_madrid(), first.to_madrid());
+    }
+
+    #[test]
+    fn summer_time_boundaries() {
+        let start = last_sunday(2024, 3) * 86_400 + 3600;
+        let end = last_sunday(2024, 10) * 86_400 + 3600;
+        assert!(!is_summer_time(start - 1));
+        assert!(is_summer_time(start));
+        assert!(is_summer_time(end - 1));
+        assert!(!is_summer_time(end));
+    }
+
+    #[test]
+    fn last_sundays() {
+        assert_eq!(civil_from_days(last_sunday(2024, 3)), (2024, 3, 31));
+        assert_eq!(civil_from_days(last_sunday(2024, 10)), (2024, 10, 27));
+        assert_eq!(civil_from_days(last_sunday(2023, 3)), (2023, 3, 26));
+        assert_eq!(civil_from_days(last_sunday(2023, 10)), (2023, 10, 29));
+    }
+
+    #[test]
+    fn leap_days() {
+        assert!(Timestamp::from_madrid(2024, 2, 29, 12, 0, 0).is_some());
+        assert!(Timestamp::from_madrid(2000, 2, 29, 12, 0, 0).is_some());
+        assert!(Timestamp::from_madrid(2023, 2, 29, 12, 0, 0).is_none());
+        assert!(Timestamp::from_madrid(1900, 2, 29, 12, 0, 0).is_none());
+        assert_eq!(
+            civil_from_days(days_from_civil(2024, 2, 29) + 1),
+            (2024, 3, 1)
+        );
+    }
+
+    #[test]
+    fn rfc3339() {
+        let winter = madrid(2024, 3, 5, 14, 30);
+        assert_eq!(winter.to_rfc3339(), "2024-03-05T14:30:00+01:00");
+        let summer = madrid(2024, 7, 5, 14, 30);
+        assert_eq!(summer.to_rfc3339(), "2024-07-05T14:30:00+02:00");
+
+        for time in [winter, summer] {
+            assert_eq!(time.to_rfc3339().parse::<Timestamp>().unwrap(), time);
+        }
+        assert_eq!("2024-03-05T13:30:00Z".parse::<Timestamp>().unwrap(), winter);
+        assert!("2024-03-05T14:30:00".parse::<Timestamp>().is_err());
+        assert!("2024-03-05T24:30:00+01:00".parse::<Timestamp>().is_err());
+    }
+
+    #[test]
+    fn year_boundaries() {
+        let new_year = madrid(2025, 1, 1, 0, 0);
+        assert_eq!(new_year.to_utc(), (2024, 12, 31, 23, 0, 0));
+        assert_eq!(
+            civil_from_days(days_from_civil(2024, 12, 31) + 1),
+            (2025, 1, 1)
+        );
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+
+    #[test]
+    fn times_out_of_range() {
+        for time in [
+            "2024-03-05 99:99:99 +01:00",
+            "2024-03-05 24:00:00 +01:00",
+            "2024-03-05 23:60:00 +01:00",
+            "2024-03-05 23:59:60 +01:00",
+            "2024-03-05 24:00",
+        ] {
+            assert!(time.parse::<Timestamp>().is_err(), "{time}");
+        }
+        assert!("9999-12-31 23:00:00 +00:00".parse::<Timestamp>().is_err());
+        assert_eq!(
+            "2024-03-05 23:59:59 +01:00".parse::<Timestamp>().ok(),
+            Timestamp::from_madrid(2024, 3, 5, 23, 59, 59)
+        );
+    }
+
+    #[test]
+    fn years_out_of_range() {
+        let last = Timestamp::from_madrid(9999, 12, 31, 23, 59, 59).unwrap();
+        assert_eq!(last.to_string(), "9999-12-31 23:59:59 +01:00");
+        assert_eq!(Timestamp::from_unix(last.unix()), Some(last));
+        assert_eq!(Timestamp::from_unix(last.unix() + 1), None);
+        assert_eq!(Timestamp::from_unix(253_402_300_799), None);
+        assert!(Timestamp::from_madrid(10_000, 1, 1, 0, 0, 0).is_none());
+        assert!(Timestamp::from_madrid(0, 1, 1, 0, 0, 0).is_none());
+        assert!(Timestamp::from_madrid(i64::MAX, 1, 1, 0, 0, 0).is_none());
+        assert!(Timestamp::from_madrid(i64::MIN, 1, 1, 0, 0, 0).is_none());
+    }
+
+    #[test]
+    fn first_timestamp() {
+        let first = Timestamp::from_madrid(1, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(first.to_string(), "0001-01-01 00:00:00 +01:00");
+        assert_eq!(first.to_utc(), (0, 12, 31, 23, 0, 0));
+        assert!(Timestamp::from_madrid(1, 1, 1, 0, 59, 59).is_some());
+        assert_eq!(Timestamp::from_unix(first.unix()), Some(first));
+        assert_eq!(Timestamp::from_unix(first.unix() - 1), None);
+
+        #[cfg(feature = "time")]
+        assert_eq!(
+            Timestamp::try_from(time::OffsetDateTime::from(first)).ok(),
+            Some(first)
+        );
+        #[cfg(feature = "chrono")]
+        assert_eq!(
+            Timestamp::try_from(chrono::DateTime::<chrono::FixedOffset>::from(first)).ok(),
+            Some(first)
+        );
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn last_timestamp_in_time() {
+        let last = Timestamp::from_madrid(9999, 12, 31, 23, 59, 59).unwrap();
+        let time = time::OffsetDateTime::from(last);
+        assert_eq!(time.year(), 9999);
+        assert_eq!(Timestamp::try_from(time).ok(), Some(last));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn last_timestamp_in_chrono() {
+        use chrono::{DateTime, Datelike, FixedOffset};
+
+        let last = Timestamp::from_madrid(9999, 12, 31, 23, 59, 59).unwrap();
+        let time = DateTime::<FixedOffset>::from(last);
+        assert_eq!(time.year(), 9999);
+        assert_eq!(Timestamp::try_from(time).ok(), Some(last));
+    }
+}
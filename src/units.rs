@@ -0,0 +1,352 @@
+//! Quantities shown by <https://jutge.org>, such as the time and the memory
+//! used by a submission, parsed from the way the site displays them.
+
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::{Error, Timestamp};
+
+/// The time a solution took to run, such as `0.123 s`.
+///
+/// It's parsed from a number followed by a unit: `s` (the default, if there
+/// is none), `ms` or `us`, with words such as `seconds` or `segons` also
+/// understood. The decimal separator can be a point or a comma.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+///
+/// use jutge::ExecutionTime;
+///
+/// let time: ExecutionTime = "0,123 s".parse()?;
+/// assert_eq!(time.duration(), Duration::from_millis(123));
+/// assert_eq!(time.to_string(), "0.123 s");
+/// # Ok::<(), jutge::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ExecutionTime(Duration);
+
+impl ExecutionTime {
+    /// Creates an `ExecutionTime` from a duration.
+    #[must_use]
+    pub const fn new(duration: Duration) -> Self {
+        Self(duration)
+    }
+
+    /// Gets the duration of the execution.
+    #[must_use]
+    pub const fn duration(&self) -> Duration {
+        self.0
+    }
+}
+
+impl Display for ExecutionTime {
+    /// Formats the time in seconds, with millisecond precision, such as
+    /// `0.123 s`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{:03} s", self.0.as_secs(), self.0.subsec_millis())
+    }
+}
+
+impl FromStr for ExecutionTime {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidQuantity(s.into());
+        let (number, unit) = split_quantity(s);
+
+        let nanos_per_unit: u64 = match unit.to_lowercase().as_str() {
+            "" | "s" | "sec" | "secs" | "second" | "seconds" | "segon" | "segons" | "segundo"
+            | "segundos" | "seconde" | "secondes" | "sekunde" | "sekunden" => 1_000_000_000,
+            "ms" | "msec" | "millisecond" | "milliseconds" => 1_000_000,
+            "us" | "µs" | "μs" | "microsecond" | "microseconds" => 1_000,
+            _ => return Err(invalid()),
+        };
+        let nanos = scale(number, nanos_per_unit).ok_or_else(invalid)?;
+
+        Ok(Self(Duration::from_nanos(nanos)))
+    }
+}
+
+/// The memory used by a solution, such as `1.2 MB`.
+///
+/// It's parsed from a number followed by a unit: `B` (the default, if there
+/// is none), the decimal `KB`, `MB` and `GB` (powers of 1000), or the binary
+/// `KiB`, `MiB` and `GiB` (powers of 1024), in any case. The decimal
+/// separator can be a point or a comma.
+///
+/// # Example
+/// ```
+/// use jutge::MemoryUsage;
+///
+/// let memory: MemoryUsage = "1.5 MiB".parse()?;
+/// assert_eq!(memory.bytes(), 1_572_864);
+/// assert_eq!(memory.to_string(), "1.6 MB");
+/// # Ok::<(), jutge::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MemoryUsage(u64);
+
+impl MemoryUsage {
+    /// Creates a `MemoryUsage` from a number of bytes.
+    #[must_use]
+    pub const fn new(bytes: u64) -> Self {
+        Self(bytes)
+    }
+
+    /// Gets the number of bytes used.
+    #[must_use]
+    pub const fn bytes(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Display for MemoryUsage {
+    /// Formats the memory in the largest decimal unit it reaches once
+    /// rounded to one decimal, such as `1.2 MB`, or in bytes if it's less
+    /// than a kilobyte, such as `512 B`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const UNITS: [(u64, &str); 3] = [(1_000_000_000, "GB"), (1_000_000, "MB"), (1_000, "KB")];
+
+        if self.0 < 1_000 {
+            return write!(f, "{} B", self.0);
+        }
+        let (tenths, unit) = UNITS
+            .iter()
+            .map(|(size, unit)| {
+                let size = u128::from(*size);
+                ((u128::from(self.0) * 10 + size / 2) / size, *unit)
+            })
+            .find(|(tenths, _)| *tenths >= 10)
+            .unwrap_or_default();
+        write!(f, "{}.{} {unit}", tenths / 10, tenths % 10)
+    }
+}
+
+impl FromStr for MemoryUsage {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidQuantity(s.into());
+        let (number, unit) = split_quantity(s);
+
+        let bytes_per_unit: u64 = match unit.to_lowercase().as_str() {
+            "" | "b" | "byte" | "bytes" => 1,
+            "k" | "kb" => 1_000,
+            "m" | "mb" => 1_000_000,
+            "g" | "gb" => 1_000_000_000,
+            "kib" => 1 << 10,
+            "mib" => 1 << 20,
+            "gib" => 1 << 30,
+            _ => return Err(invalid()),
+        };
+        scale(number, bytes_per_unit).map(Self).ok_or_else(invalid)
+    }
+}
+
+/// Splits a quantity such as `0.123 s` into its number and its unit.
+fn split_quantity(s: &str) -> (&str, &str) {
+    let s = s.trim();
+    let end = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+        .unwrap_or(s.len());
+    (&s[..end], s[end..].trim())
+}
+
+/// Multiplies a decimal number, such as `1,25`, by the size of its unit,
+/// rounding down to a whole number.
+fn scale(number: &str, unit: u64) -> Option<u64> {
+    let (whole, fraction) = number.split_once(['.', ',']).unwrap_or((number, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    let digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if !digits(whole) || !digits(fraction) || fraction.len() > 18 {
+        return None;
+    }
+
+    let mantissa: u128 = format!("{whole}{fraction}").parse().ok()?;
+    let value =
+        mantissa.checked_mul(u128::from(unit))? / 10_u128.pow(fraction.len().try_into().ok()?);
+    value.try_into().ok()
+}
+
+/// Parses a time relative to `now`, as shown by the site in its listings of
+/// submissions, such as `fa 3 minuts`, `hace una hora` or `2 days ago`.
+///
+/// The times are understood in the languages of the site (and in French and
+/// German), down to seconds and up to years, which are taken as 30 and 365
+/// days respectively. Only times in the past are understood. Returns `None`
+/// if the text doesn't hold a relative time.
+///
+/// # Example
+/// ```
+/// use jutge::{parse_relative_time, Timestamp};
+///
+/// let now: Timestamp = "2024-03-05 14:30".parse()?;
+/// let then = parse_relative_time("fa 3 minuts", now);
+/// assert_eq!(then, Some("2024-03-05 14:27".parse()?));
+/// # Ok::<(), jutge::Error>(())
+/// ```
+#[must_use]
+pub fn parse_relative_time(text: &str, now: Timestamp) -> Option<Timestamp> {
+    const ONE: [&str; 10] = [
+        "a", "an", "un", "una", "une", "ein", "eine", "einer", "einem", "einen",
+    ];
+
+    let text = text.to_lowercase();
+    let words = words(&text);
+    let in_the_past = words
+        .iter()
+        .any(|word| ["ago", "fa", "hace", "vor"].contains(word))
+        || words.windows(3).any(|three| three == ["il", "y", "a"]);
+    if !in_the_past {
+        return None;
+    }
+
+    let seconds = words.windows(2).find_map(|pair| {
+        let amount: u64 = if ONE.contains(&pair[0]) {
+            1
+        } else {
+            pair[0].parse().ok()?
+        };
+        amount.checked_mul(unit_seconds(pair[1])?)
+    })?;
+
+    Timestamp::from_unix(now.unix().checked_sub(seconds.try_into().ok()?)?)
+}
+
+/// Splits a text into its words, leaving out spaces and punctuation.
+pub(crate) fn words(text: &str) -> Vec<&str> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Gets the number of seconds in a unit of time written in lowercase, such
+/// as `minuts`, `hora` or `days`, in any of the languages of the site (and in
+/// French and German). Months and years are taken as 30 and 365 days.
+pub(crate) fn unit_seconds(unit: &str) -> Option<u64> {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+
+    let starts = |prefixes: &[&str]| prefixes.iter().any(|prefix| unit.starts_with(prefix));
+    if starts(&["sec", "seg", "sek"]) {
+        Some(1)
+    } else if starts(&["min"]) {
+        Some(MINUTE)
+    } else if starts(&["hour", "hor", "heure", "stund"]) {
+        Some(HOUR)
+    } else if starts(&["day", "dia", "día", "jour", "tag"]) {
+        Some(DAY)
+    } else if starts(&["week", "setman", "semana", "semaine", "woche"]) {
+        Some(7 * DAY)
+    } else if starts(&["month", "mes", "mois", "monat"]) {
+        Some(30 * DAY)
+    } else if starts(&["year", "any", "año", "jahr"]) || ["an", "ans"].contains(&unit) {
+        Some(365 * DAY)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{unit_seconds, ExecutionTime, MemoryUsage};
+
+    fn time(s: &str) -> Option<Duration> {
+        s.parse::<ExecutionTime>().ok().map(|time| time.duration())
+    }
+
+    fn memory(s: &str) -> Option<u64> {
+        s.parse::<MemoryUsage>().ok().map(|memory| memory.bytes())
+    }
+
+    #[test]
+    fn execution_times() {
+        assert_eq!(time("0.123 s"), Some(Duration::from_millis(123)));
+        assert_eq!(time("0,5"), Some(Duration::from_millis(500)));
+        assert_eq!(time(" 2 segons "), Some(Duration::from_secs(2)));
+        assert_eq!(time("1.5 Seconds"), Some(Duration::from_millis(1500)));
+        assert_eq!(time("15ms"), Some(Duration::from_millis(15)));
+        assert_eq!(time("250 µs"), Some(Duration::from_micros(250)));
+        assert_eq!(time(".25 s"), Some(Duration::from_millis(250)));
+        assert_eq!(time("3. s"), Some(Duration::from_secs(3)));
+        assert_eq!(time("0.0000000001 s"), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn invalid_execution_times() {
+        for time in [
+            "",
+            "s",
+            ". s",
+            "1.2.3 s",
+            "1 h",
+            "-1 s",
+            "1,000.5 s",
+            "99999999999 s",
+        ] {
+            assert!(time.parse::<ExecutionTime>().is_err(), "{time}");
+        }
+    }
+
+    #[test]
+    fn execution_times_round_trip() {
+        let time = ExecutionTime::new(Duration::from_millis(1234));
+        assert_eq!(time.to_string(), "1.234 s");
+        assert_eq!(time.to_string().parse::<ExecutionTime>().ok(), Some(time));
+    }
+
+    #[test]
+    fn memory_usages() {
+        assert_eq!(memory("512"), Some(512));
+        assert_eq!(memory("512 bytes"), Some(512));
+        assert_eq!(memory("1.2 MB"), Some(1_200_000));
+        assert_eq!(memory("3,5 kb"), Some(3_500));
+        assert_eq!(memory("1.5 MiB"), Some(1_572_864));
+        assert_eq!(memory("2 GiB"), Some(2 << 30));
+        assert_eq!(memory("1.0005 KB"), Some(1_000));
+    }
+
+    #[test]
+    fn invalid_memory_usages() {
+        for memory in [
+            "",
+            "MB",
+            "1.2.3 MB",
+            "1 TB",
+            "-1 B",
+            "99999999999999999999 B",
+        ] {
+            assert!(memory.parse::<MemoryUsage>().is_err(), "{memory}");
+        }
+    }
+
+    #[test]
+    fn displayed_memory_usages() {
+        assert_eq!(MemoryUsage::new(512).to_string(), "512 B");
+        assert_eq!(MemoryUsage::new(1_000).to_string(), "1.0 KB");
+        assert_eq!(MemoryUsage::new(1_250_000).to_string(), "1.3 MB");
+        assert_eq!(MemoryUsage::new(999_999).to_string(), "1.0 MB");
+        assert_eq!(MemoryUsage::new(949_999).to_string(), "950.0 KB");
+        assert_eq!(MemoryUsage::new(999_999_999).to_string(), "1.0 GB");
+        assert_eq!(MemoryUsage::new(999).to_string(), "999 B");
+        assert_eq!(MemoryUsage::new(u64::MAX).to_string(), "18446744073.7 GB");
+    }
+
+    #[test]
+    fn units_of_time() {
+        assert_eq!(unit_seconds("segons"), Some(1));
+        assert_eq!(unit_seconds("minutes"), Some(60));
+        assert_eq!(unit_seconds("hora"), Some(3600));
+        assert_eq!(unit_seconds("stunden"), Some(3600));
+        assert_eq!(unit_seconds("semaines"), Some(7 * 86_400));
+        assert_eq!(unit_seconds("ans"), Some(365 * 86_400));
+        assert_eq!(unit_seconds("moments"), None);
+    }
+}
@@ -0,0 +1,210 @@
+//! The public profiles of the users of <https://jutge.org>.
+
+/// A user found with [`Client::find_user()`](crate::Client::find_user).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UserSummary {
+    /// The username of the user, which identifies their profile.
+    pub username: String,
+
+    /// The full name of the user.
+    pub name: String,
+}
+
+/// The public profile of a user, as fetched with
+/// [`Client::user_profile()`](crate::Client::user_profile).
+///
+/// Users choose which fields of their profile are public, so all of them
+/// but the name may be missing.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PublicProfile {
+    /// The username of the user.
+    pub username: String,
+
+    /// The full name of the user.
+    pub name: String,
+
+    /// The institution the user belongs to.
+    pub institution: Option<String>,
+
+    /// The country of the user.
+    pub country: Option<String>,
+
+    /// The personal webpage of the user.
+    pub webpage: Option<String>,
+
+    /// A short description of the user.
+    pub description: Option<String>,
+
+    /// The number of problems the user got accepted.
+    pub accepted: Option<u32>,
+}
+
+#[cfg(feature = "web-client")]
+mod fetch {
+    use std::fmt::Write as _;
+
+    use scraper::{ElementRef, Html};
+
+    use super::{PublicProfile, UserSummary};
+    use crate::sans_io::{FetchUserProfile, FetchUserSearch, Operation, Request, Response};
+    use crate::scrape::{selector, text, text_of};
+    use crate::{Client, Error, Partial, Result};
+
+    impl Client {
+        /// Searches the users whose username or name match a query.
+        ///
+        /// The site only lets some users search (e.g. instructors), and only
+        /// lists the users with a public profile.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAuthenticated`] if the site requires logging
+        /// in to search users, in which case this should be called on the
+        /// [`Client`](crate::Session::client) of a
+        /// [`Session`](crate::Session).
+        pub fn find_user(&self, query: &str) -> Result<Vec<UserSummary>> {
+            self.execute(&FetchUserSearch {
+                query: query.into(),
+            })
+        }
+
+        /// Fetches the public profile of a user, or `None` if there's no
+        /// user with that username (or their profile isn't public).
+        ///
+        /// # Errors
+        /// Returns [`Error::UnexpectedPageLayout`] if the name of the user
+        /// can't be found in the profile. With
+        /// [`ClientBuilder::lenient_parsing()`](crate::ClientBuilder::lenient_parsing),
+        /// a missing name is left empty instead.
+        pub fn user_profile(&self, username: &str) -> Result<Option<PublicProfile>> {
+            match self.execute(&FetchUserProfile {
+                username: username.into(),
+            }) {
+                Err(Error::UreqError(err)) if matches!(*err, ureq::Error::Status(404, _)) => {
+                    Ok(None)
+                }
+                result => result.map(Some),
+            }
+        }
+    }
+
+    impl Operation for FetchUserSearch {
+        type Output = Vec<UserSummary>;
+
+        fn request(&self) -> Request {
+            Request::page(format!("/users?search={}", encode_query(&self.query)))
+        }
+
+        fn parse(&self, response: &Response<'_>) -> Result<Vec<UserSummary>> {
+            let mut users: Vec<UserSummary> = Vec::new();
+            for link in response.html()?.select(&selector("a[href^='/users/']")) {
+                let Some(username) = link
+                    .value()
+                    .attr("href")
+                    .and_then(|href| href.strip_prefix("/users/"))
+                    .map(|rest| rest.trim_end_matches('/'))
+                    .filter(|username| !username.is_empty() && !username.contains('/'))
+                else {
+                    continue;
+                };
+                if users.iter().all(|user| user.username != username) {
+                    users.push(UserSummary {
+                        username: username.into(),
+                        name: text(link),
+                    });
+                }
+            }
+
+            Ok(users)
+        }
+    }
+
+    impl Operation for FetchUserProfile {
+        type Output = PublicProfile;
+
+        fn request(&self) -> Request {
+            Request::page(format!("/users/{}", self.username))
+        }
+
+        fn parse(&self, response: &Response<'_>) -> Result<PublicProfile> {
+            self.parse_partial(response)?
+                .into_result(self.request().path)
+        }
+
+        /// A name that can't be found is left empty.
+        fn parse_partial(&self, response: &Response<'_>) -> Result<Partial<PublicProfile>> {
+            let html = response.html()?;
+            let name = text_of(html.root_element(), "h1").filter(|name| !name.is_empty());
+
+            let fields = labelled_fields(&html);
+            let field = |words: &[&str]| {
+                fields
+                    .iter()
+                    .find(|(label, _)| words.iter().any(|w| label.contains(w)))
+                    .map(|(_, value)| value.clone())
+                    .filter(|value| !value.is_empty())
+            };
+
+            let mut profile = Partial::complete(PublicProfile {
+                username: self.username.clone(),
+                name: name.clone().unwrap_or_default(),
+                institution: field(&["institution", "institució", "institución"]),
+                country: field(&["country", "país", "pays", "land"]),
+                webpage: field(&["web"]),
+                description: field(&["description", "descripció", "descripción", "beschreibung"]),
+                accepted: field(&[
+                    "accepted",
+                    "acceptats",
+                    "aceptados",
+                    "acceptés",
+                    "akzeptiert",
+                ])
+                .and_then(|accepted| accepted.parse().ok()),
+            });
+            if name.is_none() {
+                profile.fail("name", "no name found");
+            }
+            Ok(profile)
+        }
+    }
+
+    /// Finds the labelled fields of a page: the rows of its tables with a
+    /// heading and a value, and the terms of its description lists. The
+    /// labels are lowercased.
+    fn labelled_fields(html: &Html) -> Vec<(String, String)> {
+        let mut fields = Vec::new();
+        for row in html.select(&selector("tr")) {
+            if let (Some(label), Some(value)) = (text_of(row, "th"), text_of(row, "td")) {
+                fields.push((label.to_lowercase(), value));
+            }
+        }
+        for term in html.select(&selector("dt")) {
+            if let Some(value) = term
+                .next_siblings()
+                .find_map(ElementRef::wrap)
+                .filter(|value| value.value().name() == "dd")
+            {
+                fields.push((text(term).to_lowercase(), text(value)));
+            }
+        }
+        fields
+    }
+
+    /// Percent-encodes a value for the query of a URL.
+    fn encode_query(value: &str) -> String {
+        let mut encoded = String::with_capacity(value.len());
+        for byte in value.bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                    encoded.push(char::from(byte));
+                }
+                b' ' => encoded.push('+'),
+                byte => {
+                    let _ = write!(encoded, "%{byte:02X}");
+                }
+            }
+        }
+        encoded
+    }
+}
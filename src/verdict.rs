@@ -0,0 +1,143 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{Error, Result};
+
+/// The verdict given by the <https://jutge.org> judge to a submission.
+///
+/// Codes that this version of the crate doesn't know are kept as
+/// [`Verdict::Unknown`], so tools don't break when the site introduces a new
+/// one.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Verdict {
+    /// Accepted (AC): the submission passed all the test cases.
+    Accepted,
+
+    /// Incomplete (IC): the submission passed some of the test cases of a
+    /// partially-scored problem.
+    Incomplete,
+
+    /// Wrong answer (WA): the output doesn't match the expected one.
+    WrongAnswer,
+
+    /// Presentation error (PE): the output is right, except for whitespace.
+    PresentationError,
+
+    /// Compilation error (CE): the submission doesn't compile.
+    CompilationError,
+
+    /// Execution error (EE): the submission crashed, timed out or used too
+    /// much memory.
+    ExecutionError,
+
+    /// Setter error (SE): the problem itself is broken, so the submission
+    /// couldn't be judged.
+    SetterError,
+
+    /// Internal error (IE): the judge failed while judging the submission.
+    InternalError,
+
+    /// Pending: the submission hasn't been judged yet.
+    Pending,
+
+    /// A verdict code unknown to this version of the crate.
+    Unknown(String),
+}
+
+impl Verdict {
+    /// Returns the code the site uses for this verdict, such as `AC`.
+    #[must_use]
+    pub fn code(&self) -> &str {
+        match self {
+            Self::Accepted => "AC",
+            Self::Incomplete => "IC",
+            Self::WrongAnswer => "WA",
+            Self::PresentationError => "PE",
+            Self::CompilationError => "CE",
+            Self::ExecutionError => "EE",
+            Self::SetterError => "SE",
+            Self::InternalError => "IE",
+            Self::Pending => "Pending",
+            Self::Unknown(code) => code,
+        }
+    }
+
+    /// Checks whether the submission was accepted.
+    #[must_use]
+    pub const fn is_accepted(&self) -> bool {
+        matches!(self, Self::Accepted)
+    }
+
+    /// Checks whether the verdict won't change anymore. Only
+    /// [`Verdict::Pending`] isn't final.
+    #[must_use]
+    pub const fn is_final(&self) -> bool {
+        !matches!(self, Self::Pending)
+    }
+
+    /// Checks whether the verdict is the fault of the submission, rather
+    /// than of the problem or the judge. Unknown verdicts are assumed not to
+    /// be.
+    #[must_use]
+    pub const fn is_judgement(&self) -> bool {
+        self.severity().is_some()
+    }
+
+    /// Checks whether this verdict is worse than `other`, i.e., further from
+    /// being accepted.
+    ///
+    /// From best to worst, verdicts are ordered as AC, IC, PE, WA, EE and CE.
+    /// Verdicts that aren't a judgement of the submission (see
+    /// [`Verdict::is_judgement()`]) aren't comparable, so this is `false` if
+    /// any of them is one.
+    #[must_use]
+    pub fn worse_than(&self, other: &Self) -> bool {
+        match (self.severity(), other.severity()) {
+            (Some(this), Some(other)) => this > other,
+            _ => false,
+        }
+    }
+
+    /// Ranks the verdicts that judge the submission, from best to worst.
+    const fn severity(&self) -> Option<u8> {
+        match self {
+            Self::Accepted => Some(0),
+            Self::Incomplete => Some(1),
+            Self::PresentationError => Some(2),
+            Self::WrongAnswer => Some(3),
+            Self::ExecutionError => Some(4),
+            Self::CompilationError => Some(5),
+            Self::SetterError | Self::InternalError | Self::Pending | Self::Unknown(_) => None,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = Error;
+
+    /// Parses a verdict code. Codes made of 2 uppercase ASCII letters that
+    /// aren't known are parsed as [`Verdict::Unknown`].
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "AC" => Ok(Self::Accepted),
+            "IC" => Ok(Self::Incomplete),
+            "WA" => Ok(Self::WrongAnswer),
+            "PE" => Ok(Self::PresentationError),
+            "CE" => Ok(Self::CompilationError),
+            "EE" => Ok(Self::ExecutionError),
+            "SE" => Ok(Self::SetterError),
+            "IE" => Ok(Self::InternalError),
+            "Pending" => Ok(Self::Pending),
+            _ if s.len() == 2 && s.bytes().all(|b| b.is_ascii_uppercase()) => {
+                Ok(Self::Unknown(s.into()))
+            }
+            _ => Err(Error::NotAVerdict),
+        }
+    }
+}
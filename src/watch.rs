@@ -0,0 +1,468 @@
+//! A long-running watcher that keeps a local copy of the state of
+//! <https://jutge.org> courses up to date, in a background thread.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::{Client, Error, Result, Score, Session, StateStore, Timestamp};
+
+/// What a [`Watcher`] tracks, and how often.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchConfig {
+    courses: Vec<String>,
+    interval: Duration,
+    initial_state: WatchState,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            courses: Vec::new(),
+            interval: Duration::from_mins(5),
+            initial_state: WatchState::default(),
+        }
+    }
+}
+
+impl WatchConfig {
+    /// Creates a `WatchConfig` that tracks no course yet.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tracks the lists of problems of a course: which lists it has, and
+    /// their deadlines and scores.
+    #[must_use]
+    pub fn course(mut self, course_id: &str) -> Self {
+        self.courses.push(course_id.into());
+        self
+    }
+
+    /// Sets the time to wait between syncs. Defaults to 5 minutes.
+    #[must_use]
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the state the watcher starts from, such as the one it had when
+    /// the process last stopped (see [`Watcher::state()`]), so the changes
+    /// made in the meantime are reported in the first sync.
+    #[must_use]
+    pub fn initial_state(mut self, state: WatchState) -> Self {
+        self.initial_state = state;
+        self
+    }
+}
+
+/// The local copy of the state of the courses tracked by a [`Watcher`].
+///
+/// The state can be kept in a [`StateStore`] (see [`Watcher::with_store()`])
+/// as text, with a line per list of problems holding its course, its id, its
+/// deadline (as a Unix time) and its points (as `awarded/total`), separated
+/// by tabs, and a `-` for the ones that are missing. Courses without lists
+/// have a line with just their id.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WatchState {
+    /// The tracked courses, by id.
+    pub courses: BTreeMap<String, CourseState>,
+}
+
+impl Display for WatchState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (course_id, course) in &self.courses {
+            if course.lists.is_empty() {
+                writeln!(f, "{course_id}")?;
+            }
+            for (list_id, list) in &course.lists {
+                write!(f, "{course_id}\t{list_id}\t")?;
+                match list.deadline {
+                    Some(deadline) => write!(f, "{}\t", deadline.unix())?,
+                    None => write!(f, "-\t")?,
+                }
+                match list.score {
+                    Some(score) => writeln!(f, "{}/{}", score.awarded, score.total)?,
+                    None => writeln!(f, "-")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for WatchState {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut state = Self::default();
+
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            let invalid = || Error::InvalidState(format!("malformed line: {line}"));
+            let fields: Vec<_> = line.split('\t').collect();
+            match fields.as_slice() {
+                [course_id] => {
+                    state.courses.entry((*course_id).into()).or_default();
+                }
+                [course_id, list_id, deadline, score] => {
+                    let deadline = match *deadline {
+                        "-" => None,
+                        unix => Some(
+                            unix.parse()
+                                .ok()
+                                .and_then(Timestamp::from_unix)
+                                .ok_or_else(invalid)?,
+                        ),
+                    };
+                    let score = match *score {
+                        "-" => None,
+                        score => {
+                            let (awarded, total) = score.split_once('/').ok_or_else(invalid)?;
+                            Some(Score {
+                                awarded: awarded.parse().map_err(|_| invalid())?,
+                                total: total.parse().map_err(|_| invalid())?,
+                            })
+                        }
+                    };
+                    state
+                        .courses
+                        .entry((*course_id).into())
+                        .or_default()
+                        .lists
+                        .insert((*list_id).into(), ListState { deadline, score });
+                }
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(state)
+    }
+}
+
+/// The state of a course tracked by a [`Watcher`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CourseState {
+    /// The lists of problems of the course, by id.
+    pub lists: BTreeMap<String, ListState>,
+}
+
+/// The state of a list of problems tracked by a [`Watcher`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ListState {
+    /// The deadline of the list, if it has one.
+    pub deadline: Option<Timestamp>,
+
+    /// The points of the user in the list, if the site shows them.
+    pub score: Option<Score>,
+}
+
+/// A change in the state of the courses tracked by a [`Watcher`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Change {
+    /// A new list of problems appeared in a course.
+    ListAdded {
+        /// The course of the list.
+        course_id: String,
+
+        /// The new list.
+        list_id: String,
+
+        /// The state of the new list.
+        state: ListState,
+    },
+
+    /// A list of problems disappeared from a course.
+    ListRemoved {
+        /// The course of the list.
+        course_id: String,
+
+        /// The removed list.
+        list_id: String,
+    },
+
+    /// The deadline of a list of problems was set, moved or removed.
+    DeadlineChanged {
+        /// The course of the list.
+        course_id: String,
+
+        /// The list.
+        list_id: String,
+
+        /// The previous deadline.
+        old: Option<Timestamp>,
+
+        /// The new deadline.
+        new: Option<Timestamp>,
+    },
+
+    /// The points of the user in a list of problems changed.
+    ScoreChanged {
+        /// The course of the list.
+        course_id: String,
+
+        /// The list.
+        list_id: String,
+
+        /// The previous points.
+        old: Option<Score>,
+
+        /// The new points.
+        new: Option<Score>,
+    },
+}
+
+impl Client {
+    /// Starts a [`Watcher`] of the courses in `config`, in a session resumed
+    /// with [`Client::resume()`].
+    ///
+    /// # Errors
+    /// Returns [`Error::NotAuthenticated`] if the `Client` isn't logged in.
+    pub fn watch(&self, config: WatchConfig) -> Result<Watcher> {
+        Ok(Watcher::start(self.resume()?, config))
+    }
+}
+
+/// Keeps a [`WatchState`] up to date in a background thread, and reports
+/// every [`Change`] to it.
+///
+/// This is created by [`Client::watch()`] or [`Watcher::start()`]. The
+/// watcher syncs every tracked course right away, and then every
+/// [`WatchConfig::poll_interval()`]. The courses that aren't in the initial
+/// state are recorded silently in their first sync, and the changes found in
+/// later syncs are sent in order to the [`Watcher::events()`] channel. The
+/// errors of a sync are sent there too, and the watcher keeps trying in the
+/// next ones.
+///
+/// Iterating over the watcher receives its events, blocking until the next
+/// one. The background thread stops when the watcher is dropped.
+///
+/// ```no_run
+/// use jutge::{Client, WatchConfig};
+///
+/// let client = Client::new();
+/// let watcher = client.watch(WatchConfig::new().course("PRO1"))?;
+/// for change in watcher {
+///     println!("{:?}", change?);
+/// }
+/// # Ok::<(), jutge::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Watcher {
+    state: Arc<Mutex<WatchState>>,
+    events: Receiver<Result<Change>>,
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Watcher {
+    /// The key of the state of a watcher in its [`StateStore`].
+    pub const STATE_KEY: &'static str = "watch";
+
+    /// Starts watching the courses in `config` with a session.
+    #[must_use]
+    pub fn start(session: Session, config: WatchConfig) -> Self {
+        Self::spawn(session, config, None)
+    }
+
+    /// Starts watching the courses in `config` with a session, keeping the
+    /// state in a store.
+    ///
+    /// The watcher starts from the state saved in the store under
+    /// [`Watcher::STATE_KEY`], if there's one (instead of the
+    /// [`WatchConfig::initial_state()`]), and saves it there after every
+    /// sync, so a restarted watcher reports the changes made while it was
+    /// stopped. The errors of saving the state are sent to
+    /// [`Watcher::events()`].
+    ///
+    /// # Errors
+    /// Fails like [`StateStore::get()`] if the saved state can't be read.
+    pub fn with_store(
+        session: Session,
+        mut config: WatchConfig,
+        store: impl StateStore + Send + 'static,
+    ) -> Result<Self> {
+        if let Some(state) = store.get(Self::STATE_KEY)? {
+            config.initial_state = state;
+        }
+        Ok(Self::spawn(session, config, Some(Box::new(store))))
+    }
+
+    fn spawn(
+        session: Session,
+        config: WatchConfig,
+        mut store: Option<Box<dyn StateStore + Send>>,
+    ) -> Self {
+        let state = Arc::new(Mutex::new(config.initial_state.clone()));
+        let (events_sender, events) = mpsc::channel();
+        let (stop, stopped) = mpsc::channel();
+
+        let thread = {
+            let state = Arc::clone(&state);
+            thread::spawn(move || loop {
+                if !sync(&session, &config, &state, &events_sender) {
+                    return;
+                }
+                if let Some(store) = &mut store {
+                    let saved = state
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .to_string();
+                    if let Err(err) = store.store(Self::STATE_KEY, &saved) {
+                        if events_sender.send(Err(err)).is_err() {
+                            return;
+                        }
+                    }
+                }
+                match stopped.recv_timeout(config.interval) {
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => return,
+                }
+            })
+        };
+
+        Self {
+            state,
+            events,
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+
+    /// Gets a copy of the current state, as of the last sync.
+    #[must_use]
+    pub fn state(&self) -> WatchState {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Gets the channel the changes (and the errors of the syncs) are sent
+    /// to.
+    #[must_use]
+    pub const fn events(&self) -> &Receiver<Result<Change>> {
+        &self.events
+    }
+}
+
+impl Iterator for Watcher {
+    type Item = Result<Change>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.events.recv().ok()
+    }
+}
+
+impl Drop for Watcher {
+    /// Stops the background thread, waiting for the sync in progress (if
+    /// any) to finish.
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Syncs every course of `config` once, sending the changes to `events`.
+///
+/// Returns `false` if nobody is listening to the events anymore.
+fn sync(
+    session: &Session,
+    config: &WatchConfig,
+    state: &Mutex<WatchState>,
+    events: &Sender<Result<Change>>,
+) -> bool {
+    for course_id in &config.courses {
+        let new = match fetch_course(session, course_id) {
+            Ok(new) => new,
+            Err(err) => {
+                if events.send(Err(err)).is_err() {
+                    return false;
+                }
+                continue;
+            }
+        };
+
+        let old = state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .courses
+            .insert(course_id.clone(), new.clone());
+        for change in old
+            .map(|old| changes(course_id, &old, &new))
+            .unwrap_or_default()
+        {
+            if events.send(Ok(change)).is_err() {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Fetches the current state of a course.
+fn fetch_course(session: &Session, course_id: &str) -> Result<CourseState> {
+    let mut lists = BTreeMap::new();
+    for list_id in session.course_lists(course_id)? {
+        let score = match session.list_score(course_id, &list_id) {
+            Ok(score) => Some(score),
+            Err(Error::UnexpectedPageLayout { .. }) => None,
+            Err(err) => return Err(err),
+        };
+        let state = ListState {
+            deadline: session.list_deadline(course_id, &list_id)?,
+            score,
+        };
+        lists.insert(list_id, state);
+    }
+
+    Ok(CourseState { lists })
+}
+
+/// Finds the changes from the `old` state of a course to the `new` one.
+fn changes(course_id: &str, old: &CourseState, new: &CourseState) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for (list_id, new) in &new.lists {
+        let Some(old) = old.lists.get(list_id) else {
+            changes.push(Change::ListAdded {
+                course_id: course_id.into(),
+                list_id: list_id.clone(),
+                state: *new,
+            });
+            continue;
+        };
+
+        if old.deadline != new.deadline {
+            changes.push(Change::DeadlineChanged {
+                course_id: course_id.into(),
+                list_id: list_id.clone(),
+                old: old.deadline,
+                new: new.deadline,
+            });
+        }
+        if old.score != new.score {
+            changes.push(Change::ScoreChanged {
+                course_id: course_id.into(),
+                list_id: list_id.clone(),
+                old: old.score,
+                new: new.score,
+            });
+        }
+    }
+    for list_id in old.lists.keys().filter(|id| !new.lists.contains_key(*id)) {
+        changes.push(Change::ListRemoved {
+            course_id: course_id.into(),
+            list_id: list_id.clone(),
+        });
+    }
+
+    changes
+}
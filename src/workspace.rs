@@ -0,0 +1,238 @@
+//! Tracking of the problems downloaded to a local workspace.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::problem_id_types::Localized;
+use crate::{Error, ProblemId, Result, TestCase};
+
+/// A hash of the contents of a problem: its statement (including its limits)
+/// and its sample test cases.
+///
+/// Fingerprints are stable across runs and versions of the crate, so they can
+/// be stored and compared later to detect that a problem was edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fingerprint(u64);
+
+impl Fingerprint {
+    /// Computes the fingerprint of a problem from the text of its statement
+    /// and its samples.
+    #[must_use]
+    pub fn of(statement: &str, samples: &[TestCase]) -> Self {
+        let mut hasher = StableHasher::new();
+        hasher.feed(statement.as_bytes());
+        for case in samples {
+            hasher.feed(case.name.as_bytes());
+            hasher.feed(case.input.as_bytes());
+            hasher.feed(case.expected.as_bytes());
+        }
+
+        Self(hasher.finish())
+    }
+}
+
+/// A 64-bit FNV-1a hasher, which (unlike the hashers of `std`) is guaranteed
+/// not to change, so its hashes can be stored.
+pub(crate) struct StableHasher(u64);
+
+impl StableHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    pub(crate) const fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    /// Hashes a field, followed by a 0 byte so consecutive fields can't be
+    /// confused.
+    pub(crate) fn feed(&mut self, bytes: &[u8]) {
+        for byte in bytes.iter().chain(&[0]) {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    pub(crate) const fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Display for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl FromStr for Fingerprint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.len() != 16 {
+            return Err(Error::InvalidManifest(format!("invalid fingerprint: {s}")));
+        }
+
+        u64::from_str_radix(s, 16)
+            .map(Self)
+            .map_err(|_| Error::InvalidManifest(format!("invalid fingerprint: {s}")))
+    }
+}
+
+/// The manifest of a workspace: the problems that have been downloaded to it,
+/// with the [`Fingerprint`] they had at the time.
+///
+/// Sync tools can compare the stored fingerprints with the current ones (see
+/// [`Client::problem_fingerprint()`](crate::Client::problem_fingerprint)) and
+/// only download the problems that changed upstream.
+///
+/// The manifest is stored as a text file with a line per problem, holding its
+/// id and its fingerprint separated by a space.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Manifest {
+    problems: BTreeMap<ProblemId<Localized>, Fingerprint>,
+}
+
+impl Manifest {
+    /// The name of the manifest file in a workspace.
+    pub const FILE_NAME: &'static str = "jutge.manifest";
+
+    /// Creates an empty `Manifest`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads a manifest from a file, returning an empty one if the file
+    /// doesn't exist.
+    ///
+    /// # Errors
+    /// Returns [`Error::IoError`] if the file can't be read, or
+    /// [`Error::InvalidManifest`] if it's malformed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => contents.parse(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Writes the manifest to a file.
+    ///
+    /// # Errors
+    /// Returns [`Error::IoError`] if the file can't be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        Ok(fs::write(path, self.to_string())?)
+    }
+
+    /// Gets the fingerprint recorded for a problem, if it's in the manifest.
+    #[must_use]
+    pub fn get(&self, id: &ProblemId<Localized>) -> Option<Fingerprint> {
+        self.problems.get(id).copied()
+    }
+
+    /// Records the fingerprint of a problem, returning the previous one.
+    pub fn insert(
+        &mut self,
+        id: ProblemId<Localized>,
+        fingerprint: Fingerprint,
+    ) -> Option<Fingerprint> {
+        self.problems.insert(id, fingerprint)
+    }
+
+    /// Removes a problem from the manifest, returning its fingerprint.
+    pub fn remove(&mut self, id: &ProblemId<Localized>) -> Option<Fingerprint> {
+        self.problems.remove(id)
+    }
+
+    /// Checks whether a problem is in the manifest with the given
+    /// fingerprint, i.e., whether its local copy is up to date.
+    #[must_use]
+    pub fn is_current(&self, id: &ProblemId<Localized>, fingerprint: Fingerprint) -> bool {
+        self.get(id) == Some(fingerprint)
+    }
+
+    /// Iterates over the problems of the manifest, sorted by id.
+    pub fn iter(&self) -> impl Iterator<Item = (&ProblemId<Localized>, Fingerprint)> {
+        self.problems
+            .iter()
+            .map(|(id, fingerprint)| (id, *fingerprint))
+    }
+}
+
+impl Display for Manifest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (id, fingerprint) in self.iter() {
+            writeln!(f, "{id} {fingerprint}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Manifest {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut manifest = Self::new();
+
+        for line in s.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let Some((id, fingerprint)) = line.split_once(' ') else {
+                return Err(Error::InvalidManifest(format!("malformed line: {line}")));
+            };
+            let id = id
+                .parse()
+                .map_err(|_| Error::InvalidManifest(format!("invalid problem id: {id}")))?;
+            manifest.insert(id, fingerprint.trim().parse()?);
+        }
+
+        Ok(manifest)
+    }
+}
+
+#[cfg(feature = "web-client")]
+mod fetch {
+    use scraper::Html;
+
+    use super::Fingerprint;
+    use crate::problem_id_types::Localized;
+    use crate::scrape::{selector, text};
+    use crate::{samples_from_zip, Client, Error, ProblemId, Result};
+
+    impl Client {
+        /// Computes the current [`Fingerprint`] of a problem, downloading its
+        /// statement and its samples.
+        ///
+        /// The caches of the `Client` are always refreshed (unless it's
+        /// offline), so a later call to [`Client::samples()`] doesn't download
+        /// the samples again.
+        ///
+        /// # Errors
+        /// Returns [`Error::NotAuthenticated`] if the problem requires logging
+        /// in, or [`Error::UnexpectedPageLayout`] if the statement can't be
+        /// found in the problem page.
+        pub fn problem_fingerprint(&self, id: &ProblemId<Localized>) -> Result<Fingerprint> {
+            let path = format!("/problems/{id}");
+            let page = self.get_cached_page(&path, true)?;
+            let zip = self.get_cached_file(&format!("/problems/{id}/zip"), true)?;
+            let samples = samples_from_zip(&zip)?;
+            Ok(Fingerprint::of(&statement_text(&page, &path)?, &samples))
+        }
+    }
+
+    /// Extracts the text of the statement and the limits of a problem from
+    /// its page, leaving out the parts that depend on the user.
+    fn statement_text(html: &Html, page: &str) -> Result<String> {
+        let parts: Vec<_> = html
+            .select(&selector("#statement, .statement, #limits, .limits"))
+            .map(text)
+            .collect();
+
+        if parts.is_empty() {
+            return Err(Error::unexpected_layout(page, "no statement found"));
+        }
+
+        Ok(parts.join("\n"))
+    }
+}
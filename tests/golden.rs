@@ -0,0 +1,13 @@
+use jutge::fixtures::{FixtureServer, GOLDEN_TESTS};
+
+#[test]
+fn golden() {
+    let server = FixtureServer::start().expect("fixture server should start");
+    let client = server.client();
+
+    for test in GOLDEN_TESTS {
+        if let Err(err) = test.run(&client) {
+            panic!("{}: {err}", test.name);
+        }
+    }
+}